@@ -13,8 +13,9 @@ pub fn plot_component() -> Html {
         plot.set_layout(layout);
 
         async move {
-            plotly::bindings::new_plot(id, &plot).await;
-            Ok(())
+            plotly::bindings::new_plot(id, &plot)
+                .await
+                .map_err(|_| ())
         }
     });
     // Only on first render
@@ -1,4 +1,4 @@
-use plotly::callbacks::ClickEvent;
+use plotly::callbacks::{ClickEvent, DataValue};
 use plotly::{Histogram, Plot, Scatter, common::Mode, histogram::Bins};
 use web_sys::js_sys::Math;
 use yew::prelude::*;
@@ -38,16 +38,19 @@ pub fn plot_component() -> Html {
         let layout = plotly::Layout::new().title("Click Event Callback Example in Yew");
         fig.set_layout(layout);
         async move {
-            plotly::bindings::new_plot(id, &fig).await;
+            plotly::bindings::new_plot(id, &fig)
+                .await
+                .map_err(|_| ())?;
             plotly::callbacks::bind_click(id, move |event| {
                 let pt = &event.points[0];
-                x_clone.set(pt.x);
-                y_clone.set(pt.y);
+                x_clone.set(pt.x.as_ref().and_then(DataValue::as_f64));
+                y_clone.set(pt.y.as_ref().and_then(DataValue::as_f64));
                 curve_clone.set(pt.curve_number);
                 point_numbers_clone.set(pt.point_numbers.clone());
                 point_number_clone.set(pt.point_number);
                 click_event_clone.set(event);
-            });
+            })
+            .forget();
             Ok(())
         }
     });
@@ -0,0 +1,647 @@
+//! Feeding trace data from [Apache Arrow](https://arrow.apache.org) arrays.
+//!
+//! [`ArrowTrace`] wraps any trace and fills data attributes such as `x`, `y`,
+//! `z`, `text`, `marker.color` or `marker.size` straight from Arrow arrays.
+//! Null-free integer and all floating point arrays are passed to `plotly.js`
+//! as typed arrays built from the Arrow value buffer, with nulls in floating
+//! point arrays becoming `NaN` gaps. All other arrays are converted to JSON
+//! values, with nulls mapped to `null`.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//!
+//! use arrow_array::{Float64Array, RecordBatch, StringArray};
+//! use plotly::arrow::ArrowTrace;
+//! use plotly::common::Mode;
+//! use plotly::{Plot, Scatter};
+//!
+//! let batch = RecordBatch::try_from_iter([
+//!     ("time", Arc::new(Float64Array::from(vec![1.0, 2.0, 3.0])) as _),
+//!     ("value", Arc::new(Float64Array::from(vec![Some(4.0), None, Some(6.0)])) as _),
+//!     ("label", Arc::new(StringArray::from(vec!["a", "b", "c"])) as _),
+//! ])
+//! .unwrap();
+//!
+//! let trace = ArrowTrace::from_batch(
+//!     Scatter::<f64, f64>::default().mode(Mode::LinesMarkers),
+//!     &batch,
+//!     &[("x", "time"), ("y", "value"), ("text", "label")],
+//! )
+//! .unwrap();
+//!
+//! let mut plot = Plot::new();
+//! plot.add_trace(Box::new(trace));
+//! ```
+
+use arrow_array::{cast::AsArray, Array, RecordBatch};
+use arrow_cast::display::{ArrayFormatter, FormatOptions};
+use arrow_schema::{ArrowError, DataType};
+use serde::ser::{SerializeMap, SerializeStruct};
+use serde::{Serialize, Serializer};
+use serde_json::{Number, Value};
+
+use crate::{common::TypedArray, Trace};
+
+/// A trace whose data attributes are taken from Arrow arrays.
+#[derive(Clone)]
+pub struct ArrowTrace {
+    trace: Box<dyn Trace>,
+    columns: Vec<(String, Value)>,
+}
+
+impl ArrowTrace {
+    /// Wrap `trace`, whose other attributes (mode, name, styling, ...) are
+    /// kept as they are.
+    pub fn new<T: Trace + 'static>(trace: Box<T>) -> Self {
+        Self {
+            trace,
+            columns: Vec::new(),
+        }
+    }
+
+    /// Set the attribute at `path` from `array`. Nested attributes are given
+    /// as dotted paths, e.g. `"marker.size"` or `"error_y.array"`.
+    pub fn column(mut self, path: &str, array: &dyn Array) -> Result<Self, ArrowError> {
+        self.columns
+            .push((path.to_string(), array_to_value(array)?));
+        Ok(self)
+    }
+
+    /// Wrap `trace` and set each attribute path in `columns` from the
+    /// `RecordBatch` column of the given name.
+    pub fn from_batch<T: Trace + 'static>(
+        trace: Box<T>,
+        batch: &RecordBatch,
+        columns: &[(&str, &str)],
+    ) -> Result<Self, ArrowError> {
+        columns
+            .iter()
+            .try_fold(Self::new(trace), |trace, (path, name)| {
+                let array = batch.column_by_name(name).ok_or_else(|| {
+                    ArrowError::SchemaError(format!("column `{name}` not found in record batch"))
+                })?;
+                trace.column(path, array)
+            })
+    }
+}
+
+impl Serialize for ArrowTrace {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut columns = Columns::default();
+        for (path, value) in &self.columns {
+            columns.insert(path, value);
+        }
+        Merged {
+            value: &*self.trace,
+            columns: &columns,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl Trace for ArrowTrace {
+    fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+/// The columns of an [`ArrowTrace`], grouped by the keys of their paths.
+#[derive(Default)]
+struct Columns<'a>(Vec<(&'a str, Column<'a>)>);
+
+enum Column<'a> {
+    Value(&'a Value),
+    Nested(Columns<'a>),
+}
+
+impl<'a> Columns<'a> {
+    /// Set the column at the dotted `path`, replacing a column set before at
+    /// the same path or at one of its parents.
+    fn insert(&mut self, path: &'a str, value: &'a Value) {
+        let (key, rest) = match path.split_once('.') {
+            Some((key, rest)) => (key, Some(rest)),
+            None => (path, None),
+        };
+        let position = match self.0.iter().position(|(k, _)| *k == key) {
+            Some(position) => position,
+            None => {
+                self.0.push((key, Column::Nested(Columns::default())));
+                self.0.len() - 1
+            }
+        };
+        let column = &mut self.0[position].1;
+        match rest {
+            None => *column = Column::Value(value),
+            Some(rest) => {
+                if let Column::Value(_) = column {
+                    *column = Column::Nested(Columns::default());
+                }
+                if let Column::Nested(columns) = column {
+                    columns.insert(rest, value);
+                }
+            }
+        }
+    }
+}
+
+impl Serialize for Columns<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(key, column)| (key, column)))
+    }
+}
+
+impl Serialize for Column<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Column::Value(value) => value.serialize(serializer),
+            Column::Nested(columns) => columns.serialize(serializer),
+        }
+    }
+}
+
+/// `value` with `columns` written into its fields while it is serialized.
+/// A value that does not serialize as a struct or map is replaced by the
+/// columns.
+struct Merged<'a, T: ?Sized> {
+    value: &'a T,
+    columns: &'a Columns<'a>,
+}
+
+impl<T: Serialize + ?Sized> Serialize for Merged<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.value.serialize(MergeSerializer {
+            serializer,
+            columns: self.columns,
+        })
+    }
+}
+
+struct MergeSerializer<'a, S> {
+    serializer: S,
+    columns: &'a Columns<'a>,
+}
+
+impl<'a, S: Serializer> MergeSerializer<'a, S> {
+    fn replace(self) -> Result<S::Ok, S::Error> {
+        self.columns.serialize(self.serializer)
+    }
+
+    fn replace_compound(self) -> Result<Replace<'a, S>, S::Error> {
+        Ok(Replace {
+            serializer: self.serializer,
+            columns: self.columns,
+        })
+    }
+
+    fn merge_map(self) -> Result<MergeMap<'a, S::SerializeMap>, S::Error> {
+        Ok(MergeMap {
+            map: self.serializer.serialize_map(None)?,
+            columns: self.columns,
+            written: vec![false; self.columns.0.len()],
+            key: None,
+        })
+    }
+}
+
+macro_rules! replace_scalars {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method(self, _: $ty) -> Result<S::Ok, S::Error> {
+                self.replace()
+            }
+        )*
+    };
+}
+
+impl<'a, S: Serializer> Serializer for MergeSerializer<'a, S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+    type SerializeSeq = Replace<'a, S>;
+    type SerializeTuple = Replace<'a, S>;
+    type SerializeTupleStruct = Replace<'a, S>;
+    type SerializeTupleVariant = Replace<'a, S>;
+    type SerializeMap = MergeMap<'a, S::SerializeMap>;
+    type SerializeStruct = MergeMap<'a, S::SerializeMap>;
+    type SerializeStructVariant = Replace<'a, S>;
+
+    replace_scalars!(
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+        serialize_bytes(&[u8]),
+        serialize_unit_struct(&'static str),
+    );
+
+    fn serialize_none(self) -> Result<S::Ok, S::Error> {
+        self.replace()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<S::Ok, S::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<S::Ok, S::Error> {
+        self.replace()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+    ) -> Result<S::Ok, S::Error> {
+        self.replace()
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<S::Ok, S::Error> {
+        self.replace()
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, S::Error> {
+        self.replace_compound()
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, S::Error> {
+        self.replace_compound()
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, S::Error> {
+        self.replace_compound()
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, S::Error> {
+        self.replace_compound()
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, S::Error> {
+        self.merge_map()
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStruct, S::Error> {
+        self.merge_map()
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, S::Error> {
+        self.replace_compound()
+    }
+}
+
+/// Drops the elements of a value that is replaced by columns.
+struct Replace<'a, S> {
+    serializer: S,
+    columns: &'a Columns<'a>,
+}
+
+macro_rules! replace_compound {
+    ($($trait:ident::$method:ident),* $(,)?) => {
+        $(
+            impl<S: Serializer> serde::ser::$trait for Replace<'_, S> {
+                type Ok = S::Ok;
+                type Error = S::Error;
+
+                fn $method<T: Serialize + ?Sized>(&mut self, _: &T) -> Result<(), S::Error> {
+                    Ok(())
+                }
+
+                fn end(self) -> Result<S::Ok, S::Error> {
+                    self.columns.serialize(self.serializer)
+                }
+            }
+        )*
+    };
+}
+
+replace_compound!(
+    SerializeSeq::serialize_element,
+    SerializeTuple::serialize_element,
+    SerializeTupleStruct::serialize_field,
+    SerializeTupleVariant::serialize_field,
+);
+
+impl<S: Serializer> serde::ser::SerializeStructVariant for Replace<'_, S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        _: &'static str,
+        _: &T,
+    ) -> Result<(), S::Error> {
+        Ok(())
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.columns.serialize(self.serializer)
+    }
+}
+
+/// Writes the fields of a struct or map, replacing or merging those with
+/// columns, and then the columns that matched no field.
+struct MergeMap<'a, M> {
+    map: M,
+    columns: &'a Columns<'a>,
+    written: Vec<bool>,
+    key: Option<Value>,
+}
+
+impl<M: SerializeMap> MergeMap<'_, M> {
+    fn entry<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), M::Error> {
+        let Some(position) = self.columns.0.iter().position(|(k, _)| *k == key) else {
+            return self.map.serialize_entry(key, value);
+        };
+        self.written[position] = true;
+        match &self.columns.0[position].1 {
+            Column::Value(column) => self.map.serialize_entry(key, column),
+            Column::Nested(columns) => self.map.serialize_entry(key, &Merged { value, columns }),
+        }
+    }
+
+    fn finish(mut self) -> Result<M::Ok, M::Error> {
+        for ((key, column), written) in self.columns.0.iter().zip(self.written) {
+            if !written {
+                self.map.serialize_entry(key, column)?;
+            }
+        }
+        self.map.end()
+    }
+}
+
+impl<M: SerializeMap> SerializeStruct for MergeMap<'_, M> {
+    type Ok = M::Ok;
+    type Error = M::Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), M::Error> {
+        self.entry(key, value)
+    }
+
+    fn end(self) -> Result<M::Ok, M::Error> {
+        self.finish()
+    }
+}
+
+impl<M: SerializeMap> SerializeMap for MergeMap<'_, M> {
+    type Ok = M::Ok;
+    type Error = M::Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), M::Error> {
+        self.key = Some(serde_json::to_value(key).map_err(serde::ser::Error::custom)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), M::Error> {
+        match self.key.take() {
+            Some(Value::String(key)) => self.entry(&key, value),
+            Some(key) => self.map.serialize_entry(&key, value),
+            None => Err(serde::ser::Error::custom("map value without a key")),
+        }
+    }
+
+    fn end(self) -> Result<M::Ok, M::Error> {
+        self.finish()
+    }
+}
+
+/// Convert an Arrow array into the JSON `plotly.js` expects for a data
+/// attribute, preferring a typed array where [`TypedArray::from_arrow`]
+/// supports the array.
+pub fn array_to_value(array: &dyn Array) -> Result<Value, ArrowError> {
+    match TypedArray::from_arrow(array) {
+        Some(typed) => Ok(serde_json::to_value(typed).unwrap()),
+        None => array_values(array).map(Value::Array),
+    }
+}
+
+/// Convert an Arrow array into one JSON value per element, mapping nulls to
+/// `null`. Dates and timestamps are formatted as ISO 8601 strings.
+pub fn array_values(array: &dyn Array) -> Result<Vec<Value>, ArrowError> {
+    let data_type = array.data_type();
+    if data_type.is_signed_integer() {
+        let array = arrow_cast::cast(array, &DataType::Int64)?;
+        let array = array.as_primitive::<arrow_array::types::Int64Type>();
+        return Ok(array
+            .iter()
+            .map(|v| v.map_or(Value::Null, Value::from))
+            .collect());
+    }
+    if data_type.is_unsigned_integer() {
+        let array = arrow_cast::cast(array, &DataType::UInt64)?;
+        let array = array.as_primitive::<arrow_array::types::UInt64Type>();
+        return Ok(array
+            .iter()
+            .map(|v| v.map_or(Value::Null, Value::from))
+            .collect());
+    }
+    if data_type.is_floating() {
+        let array = arrow_cast::cast(array, &DataType::Float64)?;
+        let array = array.as_primitive::<arrow_array::types::Float64Type>();
+        return Ok(array
+            .iter()
+            .map(|v| {
+                v.and_then(Number::from_f64)
+                    .map_or(Value::Null, Value::Number)
+            })
+            .collect());
+    }
+
+    match data_type {
+        DataType::Boolean => Ok(array
+            .as_boolean()
+            .iter()
+            .map(|v| v.map_or(Value::Null, Value::Bool))
+            .collect()),
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View | DataType::Dictionary(..) => {
+            let array = arrow_cast::cast(array, &DataType::Utf8)?;
+            Ok(array
+                .as_string::<i32>()
+                .iter()
+                .map(|v| v.map_or(Value::Null, |s| Value::String(s.to_string())))
+                .collect())
+        }
+        _ => {
+            let formatter = ArrayFormatter::try_new(array, &FormatOptions::default())?;
+            Ok((0..array.len())
+                .map(|i| {
+                    if array.is_null(i) {
+                        Value::Null
+                    } else {
+                        Value::String(formatter.value(i).to_string())
+                    }
+                })
+                .collect())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow_array::{
+        BooleanArray, Date32Array, DictionaryArray, Float32Array, Float64Array, Int32Array,
+        Int64Array, StringArray, UInt8Array,
+    };
+    use serde_json::{json, to_value};
+
+    use super::*;
+    use crate::{
+        common::{Marker, Mode},
+        Scatter,
+    };
+
+    #[test]
+    fn typed_array_for_null_free_numbers() {
+        let array = Int32Array::from(vec![1, 2, 3]);
+        assert_eq!(
+            array_to_value(&array).unwrap(),
+            to_value(TypedArray::from_i32(&[1, 2, 3])).unwrap()
+        );
+
+        let array = UInt8Array::from(vec![1, 2]);
+        assert_eq!(
+            array_to_value(&array).unwrap(),
+            to_value(TypedArray::from_u8(&[1, 2])).unwrap()
+        );
+    }
+
+    #[test]
+    fn sliced_array_uses_offset() {
+        let array = Float64Array::from(vec![1.0, 2.0, 3.0, 4.0]);
+        let sliced = array.slice(1, 2);
+        assert_eq!(
+            array_to_value(&sliced).unwrap(),
+            to_value(TypedArray::from_f64(&[2.0, 3.0])).unwrap()
+        );
+    }
+
+    #[test]
+    fn float_nulls_become_nan() {
+        let array = Float32Array::from(vec![Some(1.0), None]);
+        assert_eq!(
+            array_to_value(&array).unwrap(),
+            to_value(TypedArray::from_f32(&[1.0, f32::NAN])).unwrap()
+        );
+    }
+
+    #[test]
+    fn json_values_with_nulls() {
+        let array = Int64Array::from(vec![Some(1), None, Some(3)]);
+        assert_eq!(array_to_value(&array).unwrap(), json!([1, null, 3]));
+
+        let array = StringArray::from(vec![Some("a"), None]);
+        assert_eq!(array_to_value(&array).unwrap(), json!(["a", null]));
+
+        let array = BooleanArray::from(vec![Some(true), None]);
+        assert_eq!(array_to_value(&array).unwrap(), json!([true, null]));
+
+        let array: DictionaryArray<arrow_array::types::Int8Type> =
+            vec!["x", "y", "x"].into_iter().collect();
+        assert_eq!(array_to_value(&array).unwrap(), json!(["x", "y", "x"]));
+
+        let array = Date32Array::from(vec![Some(0), None, Some(365)]);
+        assert_eq!(
+            array_to_value(&array).unwrap(),
+            json!(["1970-01-01", null, "1971-01-01"])
+        );
+    }
+
+    #[test]
+    fn trace_from_batch() {
+        let batch = RecordBatch::try_from_iter([
+            ("t", Arc::new(Float64Array::from(vec![1.0, 2.0])) as _),
+            ("label", Arc::new(StringArray::from(vec!["a", "b"])) as _),
+            ("size", Arc::new(Int64Array::from(vec![Some(4), None])) as _),
+        ])
+        .unwrap();
+
+        let trace = ArrowTrace::from_batch(
+            Scatter::<f64, f64>::default().mode(Mode::Markers),
+            &batch,
+            &[("x", "t"), ("text", "label"), ("marker.size", "size")],
+        )
+        .unwrap();
+
+        let value = to_value(&trace).unwrap();
+        assert_eq!(value["type"], json!("scatter"));
+        assert_eq!(value["mode"], json!("markers"));
+        assert_eq!(
+            value["x"],
+            to_value(TypedArray::from_f64(&[1.0, 2.0])).unwrap()
+        );
+        assert_eq!(value["text"], json!(["a", "b"]));
+        assert_eq!(value["marker"]["size"], json!([4, null]));
+
+        let trace = ArrowTrace::new(
+            Scatter::new(vec![0.0], vec![0.0])
+                .name("data")
+                .marker(Marker::new().color("red")),
+        )
+        .column("y", &Float64Array::from(vec![3.0]))
+        .unwrap()
+        .column("marker.size", &Int64Array::from(vec![Some(5), None]))
+        .unwrap();
+        let json = serde_json::to_string(&trace).unwrap();
+        assert_eq!(json.matches("\"y\"").count(), 1);
+        let value: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["x"], json!([0.0]));
+        assert_eq!(value["y"], to_value(TypedArray::from_f64(&[3.0])).unwrap());
+        assert_eq!(value["name"], json!("data"));
+        assert_eq!(value["marker"], json!({"color": "red", "size": [5, null]}));
+
+        assert!(ArrowTrace::from_batch(
+            Box::new(Scatter::<f64, f64>::default()),
+            &batch,
+            &[("y", "missing")]
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,389 @@
+//! `plotly-export`: renders Plotly JSON figure files to images and HTML.
+//!
+//! Every input is a `.json` file holding a Plotly figure, a directory whose
+//! `.json` files are rendered, or a glob pattern such as `figures/*.json`.
+//! The output files are named after the inputs, with the extension of each
+//! requested format; inputs whose outputs would overwrite each other are
+//! rejected. All images of a run are exported in a single browser session.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use askama::Template;
+use clap::{Parser, ValueEnum};
+use plotly::layout::themes::BuiltinTheme;
+use plotly::Plot;
+use plotly_static::{Backend, ExportJob, ImageFormat, StaticExporterBuilder};
+use serde_json::Value;
+
+#[derive(Parser)]
+#[command(name = "plotly-export")]
+#[command(about = "Render Plotly JSON figures to PNG, JPEG, WEBP, SVG, PDF or HTML")]
+#[command(version)]
+struct Cli {
+    /// Figure files, directories of figure files or glob patterns
+    #[arg(required = true)]
+    inputs: Vec<String>,
+
+    /// Output formats, comma separated
+    #[arg(short, long, value_enum, value_delimiter = ',', default_value = "png")]
+    format: Vec<Format>,
+
+    /// Directory of the output files (default: the directory of each input)
+    #[arg(short, long)]
+    out_dir: Option<PathBuf>,
+
+    /// Image width in pixels
+    #[arg(long, default_value_t = 800)]
+    width: usize,
+
+    /// Image height in pixels
+    #[arg(long, default_value_t = 600)]
+    height: usize,
+
+    /// Image scale factor
+    #[arg(short, long, default_value_t = 1.0)]
+    scale: f64,
+
+    /// Built-in theme applied to every figure
+    #[arg(short, long, value_enum)]
+    theme: Option<Theme>,
+
+    /// Use offline mode (bundled JavaScript)
+    #[arg(long)]
+    offline: bool,
+
+    /// Launch Chrome directly instead of using WebDriver (requires the
+    /// `static_export_cdp` feature)
+    #[arg(long)]
+    cdp: bool,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum Format {
+    Png,
+    Jpeg,
+    Webp,
+    Svg,
+    Pdf,
+    Html,
+}
+
+impl Format {
+    /// The format of the exported image, `None` for HTML.
+    fn image_format(self) -> Option<ImageFormat> {
+        match self {
+            Format::Png => Some(ImageFormat::PNG),
+            Format::Jpeg => Some(ImageFormat::JPEG),
+            Format::Webp => Some(ImageFormat::WEBP),
+            Format::Svg => Some(ImageFormat::SVG),
+            Format::Pdf => Some(ImageFormat::PDF),
+            Format::Html => None,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+#[value(rename_all = "snake_case")]
+enum Theme {
+    Default,
+    PlotlyWhite,
+    PlotlyDark,
+    Seaborn,
+    SeabornWhitegrid,
+    SeabornDark,
+    Matplotlib,
+    Plotnine,
+}
+
+impl From<Theme> for BuiltinTheme {
+    fn from(theme: Theme) -> Self {
+        match theme {
+            Theme::Default => BuiltinTheme::Default,
+            Theme::PlotlyWhite => BuiltinTheme::PlotlyWhite,
+            Theme::PlotlyDark => BuiltinTheme::PlotlyDark,
+            Theme::Seaborn => BuiltinTheme::Seaborn,
+            Theme::SeabornWhitegrid => BuiltinTheme::SeabornWhitegrid,
+            Theme::SeabornDark => BuiltinTheme::SeabornDark,
+            Theme::Matplotlib => BuiltinTheme::Matplotlib,
+            Theme::Plotnine => BuiltinTheme::Plotnine,
+        }
+    }
+}
+
+#[derive(Template)]
+#[template(path = "plot.html", escape = "none")]
+struct HtmlTemplate<'a> {
+    plot: &'a Value,
+    js_scripts: &'a str,
+}
+
+/// The figure files named by `inputs`, in order.
+fn figure_files(inputs: &[String]) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut files = Vec::new();
+    for input in inputs {
+        let path = Path::new(input);
+        if path.is_dir() {
+            let mut entries = fs::read_dir(path)?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<Vec<_>, _>>()?;
+            entries.retain(|path| path.is_file() && is_json(path));
+            entries.sort();
+            files.extend(entries);
+        } else if path.exists() {
+            files.push(path.to_path_buf());
+        } else if input.contains(['*', '?', '[']) {
+            let matches = glob::glob(input)?
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .filter(|path| path.is_file())
+                .collect::<Vec<_>>();
+            if matches.is_empty() {
+                return Err(format!("No files match '{input}'").into());
+            }
+            files.extend(matches);
+        } else {
+            return Err(format!("No such file or directory: {input}").into());
+        }
+    }
+    Ok(files)
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"))
+}
+
+/// Read the figure in `path`, with `theme` applied.
+fn read_figure(path: &Path, theme: Option<Theme>) -> Result<Value, Box<dyn Error>> {
+    let mut figure: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+    if !figure.is_object() {
+        return Err("Invalid figure: expected a JSON object".into());
+    }
+    if let Some(theme) = theme {
+        let layout = figure
+            .as_object_mut()
+            .unwrap()
+            .entry("layout")
+            .or_insert_with(|| Value::Object(Default::default()));
+        let Some(layout) = layout.as_object_mut() else {
+            return Err("Invalid figure: the layout is not a JSON object".into());
+        };
+        let template = serde_json::to_value(BuiltinTheme::from(theme).build())?;
+        layout.insert("template".to_string(), template);
+    }
+    Ok(figure)
+}
+
+/// The path of the output files of `input`, without extension.
+fn output_stem(input: &Path, out_dir: Option<&Path>) -> PathBuf {
+    let name = input.file_stem().unwrap_or_default();
+    match out_dir {
+        Some(dir) => dir.join(name),
+        None => input.with_file_name(name),
+    }
+}
+
+/// The output stems of `files`, failing if two inputs would be written to
+/// the same files, e.g. `a/plot.json` and `b/plot.json` with `--out-dir`.
+fn output_stems(files: &[PathBuf], out_dir: Option<&Path>) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut inputs: HashMap<PathBuf, &Path> = HashMap::new();
+    let mut stems = Vec::with_capacity(files.len());
+    for file in files {
+        let stem = output_stem(file, out_dir);
+        if let Some(other) = inputs.insert(stem.clone(), file) {
+            return Err(format!(
+                "{} and {} would both be written to {}",
+                other.display(),
+                file.display(),
+                stem.display()
+            )
+            .into());
+        }
+        stems.push(stem);
+    }
+    Ok(stems)
+}
+
+fn write_html(dst: &Path, figure: &Value, offline: bool) -> Result<(), Box<dyn Error>> {
+    let js_scripts = if offline {
+        Plot::offline_js_sources()
+    } else {
+        Plot::online_cdn_js()
+    };
+    let tmpl = HtmlTemplate {
+        plot: figure,
+        js_scripts: &js_scripts,
+    };
+    fs::write(dst, tmpl.render()?)?;
+    Ok(())
+}
+
+/// Render all figures of `cli`, returning whether every output was written.
+fn run(cli: &Cli) -> Result<bool, Box<dyn Error>> {
+    let files = figure_files(&cli.inputs)?;
+    let stems = output_stems(&files, cli.out_dir.as_deref())?;
+    if let Some(dir) = &cli.out_dir {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut success = true;
+    let mut jobs = Vec::new();
+    for (file, stem) in files.iter().zip(stems) {
+        let figure = match read_figure(file, cli.theme) {
+            Ok(figure) => figure,
+            Err(e) => {
+                eprintln!("{}: {e}", file.display());
+                success = false;
+                continue;
+            }
+        };
+        for format in &cli.format {
+            match format.image_format() {
+                Some(image_format) => jobs.push(ExportJob::new(
+                    stem.clone(),
+                    figure.clone(),
+                    image_format,
+                    cli.width,
+                    cli.height,
+                    cli.scale,
+                )),
+                None => {
+                    let dst = stem.with_extension("html");
+                    match write_html(&dst, &figure, cli.offline) {
+                        Ok(()) => println!("{}", dst.display()),
+                        Err(e) => {
+                            eprintln!("{}: {e}", dst.display());
+                            success = false;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    if jobs.is_empty() {
+        return Ok(success);
+    }
+
+    let mut builder = StaticExporterBuilder::default().offline_mode(cli.offline);
+    if cli.cdp {
+        builder = builder.backend(Backend::Cdp);
+    }
+    let mut exporter = builder.build()?;
+    let results = exporter.write_batch(&jobs);
+    exporter.close();
+
+    for job in results? {
+        match job.result {
+            Ok(()) => println!("{} ({} ms)", job.dst.display(), job.duration.as_millis()),
+            Err(e) => {
+                eprintln!("{}: {e}", job.dst.display());
+                success = false;
+            }
+        }
+    }
+    Ok(success)
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(&cli) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("plotly_export_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn expand_files_directories_and_globs() {
+        let dir = temp_dir("inputs");
+        for name in ["b.json", "a.json", "notes.txt"] {
+            fs::write(dir.join(name), "{}").unwrap();
+        }
+        let dir_name = dir.to_string_lossy().into_owned();
+
+        let files = figure_files(std::slice::from_ref(&dir_name)).unwrap();
+        assert_eq!(files, [dir.join("a.json"), dir.join("b.json")]);
+
+        let pattern = format!("{dir_name}/b*");
+        let files = figure_files(&[pattern, format!("{dir_name}/notes.txt")]).unwrap();
+        assert_eq!(files, [dir.join("b.json"), dir.join("notes.txt")]);
+
+        assert!(figure_files(&[format!("{dir_name}/missing.json")]).is_err());
+        assert!(figure_files(&[format!("{dir_name}/*.csv")]).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn apply_theme_and_write_html() {
+        let dir = temp_dir("html");
+        let path = dir.join("figure.json");
+        fs::write(&path, r#"{"data": [{"type": "bar", "y": [1, 2]}]}"#).unwrap();
+
+        let figure = read_figure(&path, None).unwrap();
+        assert!(figure.get("layout").is_none());
+        let figure = read_figure(&path, Some(Theme::PlotlyDark)).unwrap();
+        assert_eq!(
+            figure["layout"]["template"],
+            serde_json::to_value(BuiltinTheme::PlotlyDark.build()).unwrap()
+        );
+
+        let dst = output_stem(&path, None).with_extension("html");
+        assert_eq!(dst, dir.join("figure.html"));
+        write_html(&dst, &figure, false).unwrap();
+        let html = fs::read_to_string(&dst).unwrap();
+        assert!(html.contains("Plotly.newPlot(graph_div, {\"data\":[{"));
+        assert!(html.contains("https://cdn.plot.ly/plotly-"));
+
+        fs::write(&path, "[1, 2]").unwrap();
+        assert!(read_figure(&path, None).is_err());
+        fs::write(&path, json!({"data": [], "layout": 1}).to_string()).unwrap();
+        assert!(read_figure(&path, Some(Theme::Seaborn)).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn output_files_go_to_out_dir() {
+        assert_eq!(
+            output_stem(Path::new("figures/sales.json"), Some(Path::new("out"))),
+            PathBuf::from("out/sales")
+        );
+        let files = [
+            PathBuf::from("q1/sales.json"),
+            PathBuf::from("q2/sales.json"),
+        ];
+        assert_eq!(
+            output_stems(&files, None).unwrap(),
+            [PathBuf::from("q1/sales"), PathBuf::from("q2/sales")]
+        );
+        let error = output_stems(&files, Some(Path::new("out"))).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "q1/sales.json and q2/sales.json would both be written to out/sales"
+        );
+        assert_eq!(Format::Html.image_format().map(|f| f.to_string()), None);
+        assert_eq!(
+            Format::Jpeg.image_format().map(|f| f.to_string()),
+            Some("jpeg".to_string())
+        );
+    }
+}
@@ -1,11 +1,22 @@
 //! Bindings to the underlying plotly.js Javascript API. To be used in a WASM
 //! context, where it is assumed that a remote copy of the Javascript Plotly
 //! library is available, (i.e. via a CDN).
+//!
+//! Every function takes the `id` of an existing HTML `div` element and
+//! returns the error raised by plotly.js, or by serializing the arguments, as
+//! a `JsValue` instead of panicking.
 
+use plotly_derive::FieldSetter;
+use serde::Serialize;
+use serde_json::{Map, Value};
 use wasm_bindgen::prelude::*;
-use wasm_bindgen_futures::js_sys::Object;
+use wasm_bindgen_futures::js_sys::{Array, Object, JSON};
 
-use crate::Plot;
+use crate::{
+    layout::{Animation, Frame},
+    stream::StreamUpdate,
+    Plot, Relayout, Restyle, Trace,
+};
 
 #[wasm_bindgen]
 extern "C" {
@@ -14,6 +25,130 @@ extern "C" {
 
     #[wasm_bindgen(catch, js_namespace = Plotly, js_name = react)]
     async fn react_(id: &str, obj: &Object) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, js_namespace = Plotly, js_name = restyle)]
+    async fn restyle_(id: &str, update: &JsValue, indices: &JsValue) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, js_namespace = Plotly, js_name = relayout)]
+    async fn relayout_(id: &str, update: &JsValue) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, js_namespace = Plotly, js_name = update)]
+    async fn update_(
+        id: &str,
+        trace_update: &JsValue,
+        layout_update: &JsValue,
+        indices: &JsValue,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, js_namespace = Plotly, js_name = addTraces)]
+    async fn add_traces_(id: &str, traces: &JsValue, indices: &JsValue)
+        -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, js_namespace = Plotly, js_name = deleteTraces)]
+    async fn delete_traces_(id: &str, indices: &JsValue) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, js_namespace = Plotly, js_name = moveTraces)]
+    async fn move_traces_(
+        id: &str,
+        current_indices: &JsValue,
+        new_indices: &JsValue,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, js_namespace = Plotly, js_name = extendTraces)]
+    async fn extend_traces_(
+        id: &str,
+        update: &JsValue,
+        indices: &JsValue,
+        max_points: &JsValue,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, js_namespace = Plotly, js_name = prependTraces)]
+    async fn prepend_traces_(
+        id: &str,
+        update: &JsValue,
+        indices: &JsValue,
+        max_points: &JsValue,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, js_namespace = Plotly, js_name = animate)]
+    async fn animate_(id: &str, frames: &JsValue, options: &JsValue) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, js_namespace = Plotly, js_name = addFrames)]
+    async fn add_frames_(id: &str, frames: &JsValue, indices: &JsValue)
+        -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, js_namespace = Plotly, js_name = purge)]
+    fn purge_(id: &str) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, js_namespace = Plotly, js_name = toImage)]
+    async fn to_image_(id: &str, options: &JsValue) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, js_namespace = Plotly, js_name = downloadImage)]
+    async fn download_image_(id: &str, options: &JsValue) -> Result<JsValue, JsValue>;
+}
+
+/// The image formats supported by [`to_image`] and [`download_image`].
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageFormat {
+    /// Portable Network Graphics format
+    PNG,
+    /// Joint Photographic Experts Group format
+    JPEG,
+    /// WebP format (Google's image format)
+    WEBP,
+    /// Scalable Vector Graphics format
+    SVG,
+}
+
+/// Options of [`to_image`] and [`download_image`]. Unset options take the
+/// plotly.js defaults: PNG format at the size of the plot.
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, FieldSetter)]
+pub struct ImageOptions {
+    format: Option<ImageFormat>,
+    width: Option<usize>,
+    height: Option<usize>,
+    scale: Option<f64>,
+    /// The name of the downloaded file, without extension. Only used by
+    /// [`download_image`].
+    filename: Option<String>,
+}
+
+impl ImageOptions {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+fn js_error(error: impl std::fmt::Display) -> JsValue {
+    JsValue::from_str(&error.to_string())
+}
+
+/// Convert `value` into a plain Javascript value via its JSON representation,
+/// so that maps become objects.
+fn to_js<T: Serialize + ?Sized>(value: &T) -> Result<JsValue, JsValue> {
+    JSON::parse(&serde_json::to_string(value).map_err(js_error)?)
+}
+
+/// Convert optional trace indices, leaving them `undefined` when absent.
+fn indices_to_js(indices: Option<&[usize]>) -> Result<JsValue, JsValue> {
+    match indices {
+        Some(indices) => to_js(indices),
+        None => Ok(JsValue::UNDEFINED),
+    }
+}
+
+/// Merge the objects that `updates` serialize to into a single update object.
+fn merge_updates<T: Serialize>(updates: &[T]) -> Result<JsValue, JsValue> {
+    let mut merged = Map::new();
+    for update in updates {
+        match serde_json::to_value(update).map_err(js_error)? {
+            Value::Object(update) => merged.extend(update),
+            update => return Err(js_error(format!("expected an update object, got {update}"))),
+        }
+    }
+    to_js(&merged)
 }
 
 /// A wrapper around the plotly.js [newPlot](https://plotly.com/javascript/plotlyjs-function-reference/#plotlynewplot)
@@ -22,13 +157,8 @@ extern "C" {
 /// The function signature is slightly constrained in that `id` is a `&str`
 /// which represents the `id` of an existing HTML `div` element, rather than
 /// also allowing an instance of a `div` element, itself.
-pub async fn new_plot(id: &str, plot: &Plot) {
-    let plot_obj = &plot.to_js_object();
-
-    // This will only fail if the Rust Plotly library has produced
-    // plotly-incompatible JSON. An error here should have been handled by the
-    // library, rather than down here.
-    new_plot_(id, plot_obj).await.expect("Error plotting chart");
+pub async fn new_plot(id: &str, plot: &Plot) -> Result<(), JsValue> {
+    new_plot_(id, &plot.to_js_object()).await.map(|_| ())
 }
 
 /// A wrapper around the plotly.js [react](https://plotly.com/javascript/plotlyjs-function-reference/#react)
@@ -37,11 +167,150 @@ pub async fn new_plot(id: &str, plot: &Plot) {
 /// The function signature is slightly constrained in that `id` is a `&str`
 /// which represents the `id` of an existing HTML `div` element, rather than
 /// also allowing an instance of a `div` element, itself.
-pub async fn react(id: &str, plot: &Plot) {
-    let plot_obj = &plot.to_js_object();
+pub async fn react(id: &str, plot: &Plot) -> Result<(), JsValue> {
+    react_(id, &plot.to_js_object()).await.map(|_| ())
+}
+
+/// A wrapper around the plotly.js [restyle](https://plotly.com/javascript/plotlyjs-function-reference/#plotlyrestyle)
+/// function.
+///
+/// The `updates` are merged into a single update object, which is applied to
+/// the traces at `trace_indices`, or to all traces if `None`. As in plotly.js,
+/// an array value is spread over the selected traces, one element per trace.
+pub async fn restyle<R: Restyle>(
+    id: &str,
+    updates: &[R],
+    trace_indices: Option<&[usize]>,
+) -> Result<(), JsValue> {
+    restyle_(id, &merge_updates(updates)?, &indices_to_js(trace_indices)?)
+        .await
+        .map(|_| ())
+}
+
+/// A wrapper around the plotly.js [relayout](https://plotly.com/javascript/plotlyjs-function-reference/#plotlyrelayout)
+/// function.
+pub async fn relayout<L: Relayout>(id: &str, updates: &[L]) -> Result<(), JsValue> {
+    relayout_(id, &merge_updates(updates)?).await.map(|_| ())
+}
+
+/// A wrapper around the plotly.js [update](https://plotly.com/javascript/plotlyjs-function-reference/#plotlyupdate)
+/// function, combining [`restyle`] and [`relayout`] in a single redraw.
+pub async fn update<R: Restyle, L: Relayout>(
+    id: &str,
+    trace_updates: &[R],
+    layout_updates: &[L],
+    trace_indices: Option<&[usize]>,
+) -> Result<(), JsValue> {
+    update_(
+        id,
+        &merge_updates(trace_updates)?,
+        &merge_updates(layout_updates)?,
+        &indices_to_js(trace_indices)?,
+    )
+    .await
+    .map(|_| ())
+}
+
+/// A wrapper around the plotly.js [addTraces](https://plotly.com/javascript/plotlyjs-function-reference/#plotlyaddtraces)
+/// function.
+///
+/// The traces are inserted at `new_indices`, or appended if `None`.
+pub async fn add_traces(
+    id: &str,
+    traces: &[Box<dyn Trace>],
+    new_indices: Option<&[usize]>,
+) -> Result<(), JsValue> {
+    add_traces_(id, &to_js(traces)?, &indices_to_js(new_indices)?)
+        .await
+        .map(|_| ())
+}
+
+/// A wrapper around the plotly.js [deleteTraces](https://plotly.com/javascript/plotlyjs-function-reference/#plotlydeletetraces)
+/// function.
+pub async fn delete_traces(id: &str, indices: &[usize]) -> Result<(), JsValue> {
+    delete_traces_(id, &to_js(indices)?).await.map(|_| ())
+}
+
+/// A wrapper around the plotly.js [moveTraces](https://plotly.com/javascript/plotlyjs-function-reference/#plotlymovetraces)
+/// function.
+///
+/// The traces at `current_indices` are moved to `new_indices`, or to the end
+/// if `None`.
+pub async fn move_traces(
+    id: &str,
+    current_indices: &[usize],
+    new_indices: Option<&[usize]>,
+) -> Result<(), JsValue> {
+    move_traces_(id, &to_js(current_indices)?, &indices_to_js(new_indices)?)
+        .await
+        .map(|_| ())
+}
+
+/// A wrapper around the plotly.js [extendTraces](https://plotly.com/javascript/plotlyjs-function-reference/#plotlyextendtraces)
+/// function.
+pub async fn extend_traces(id: &str, update: &StreamUpdate) -> Result<(), JsValue> {
+    let [update, indices, max_points] = stream_args(update)?;
+    extend_traces_(id, &update, &indices, &max_points)
+        .await
+        .map(|_| ())
+}
+
+/// A wrapper around the plotly.js [prependTraces](https://plotly.com/javascript/plotlyjs-function-reference/#plotlyprependtraces)
+/// function.
+pub async fn prepend_traces(id: &str, update: &StreamUpdate) -> Result<(), JsValue> {
+    let [update, indices, max_points] = stream_args(update)?;
+    prepend_traces_(id, &update, &indices, &max_points)
+        .await
+        .map(|_| ())
+}
+
+/// The arguments of `extendTraces` and `prependTraces`, with `maxPoints`
+/// left `undefined` when not set.
+fn stream_args(update: &StreamUpdate) -> Result<[JsValue; 3], JsValue> {
+    let args: Array = to_js(&update.args().map_err(js_error)?)?.into();
+    Ok([args.get(0), args.get(1), args.get(2)])
+}
+
+/// A wrapper around the plotly.js [animate](https://plotly.com/javascript/plotlyjs-function-reference/#plotlyanimate)
+/// function.
+pub async fn animate(id: &str, animation: &Animation) -> Result<(), JsValue> {
+    let args: Array = to_js(animation)?.into();
+    animate_(id, &args.get(0), &args.get(1)).await.map(|_| ())
+}
+
+/// A wrapper around the plotly.js [addFrames](https://plotly.com/javascript/plotlyjs-function-reference/#plotlyaddframes)
+/// function.
+///
+/// The frames are inserted at `indices`, or appended if `None`.
+pub async fn add_frames(
+    id: &str,
+    frames: &[Frame],
+    indices: Option<&[usize]>,
+) -> Result<(), JsValue> {
+    add_frames_(id, &to_js(frames)?, &indices_to_js(indices)?)
+        .await
+        .map(|_| ())
+}
+
+/// A wrapper around the plotly.js [purge](https://plotly.com/javascript/plotlyjs-function-reference/#plotlypurge)
+/// function, removing the plot and its event handlers from the `div`.
+pub fn purge(id: &str) -> Result<(), JsValue> {
+    purge_(id).map(|_| ())
+}
+
+/// A wrapper around the plotly.js [toImage](https://plotly.com/javascript/plotlyjs-function-reference/#plotlytoimage)
+/// function, returning the image as a data URL.
+pub async fn to_image(id: &str, options: &ImageOptions) -> Result<String, JsValue> {
+    let url = to_image_(id, &to_js(options)?).await?;
+    url.as_string()
+        .ok_or_else(|| js_error("toImage did not return a string"))
+}
 
-    // This will only fail if the Rust Plotly library has produced
-    // plotly-incompatible JSON. An error here should have been handled by the
-    // library, rather than down here.
-    react_(id, plot_obj).await.expect("Error plotting chart");
+/// A wrapper around the plotly.js [downloadImage](https://plotly.com/javascript/plotlyjs-function-reference/#plotlydownloadimage)
+/// function, returning the name of the downloaded file.
+pub async fn download_image(id: &str, options: &ImageOptions) -> Result<String, JsValue> {
+    let filename = download_image_(id, &to_js(options)?).await?;
+    filename
+        .as_string()
+        .ok_or_else(|| js_error("downloadImage did not return a string"))
 }
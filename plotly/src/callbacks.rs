@@ -1,23 +1,32 @@
-use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
 use wasm_bindgen::prelude::*;
 use web_sys::{js_sys::Function, HtmlElement};
 
-/// Provides utilities for binding Plotly.js click events to Rust closures
-/// via `wasm-bindgen`.  
-///  
+pub use crate::events::{
+    ClickEvent, ClickPoint, DataValue, LegendClickEvent, Point, PointsEvent, RelayoutEvent,
+    RestyleEvent, SelectionEvent, SliderChangeEvent, SliderStep,
+};
+
+/// Provides utilities for binding Plotly.js events to Rust closures via
+/// `wasm-bindgen`.
+///
 /// This module defines a `PlotlyDiv` foreign type for the Plotly `<div>`
-/// element, a high-level `bind_click` function to wire up Rust callbacks, and  
-/// the `ClickPoint`/`ClickEvent` data structures to deserialize event payloads.
+/// element and one `bind_*` function per event, which deserializes the event
+/// payload into the matching struct of [`crate::events`]. Each `bind_*`
+/// function returns an [`EventListener`] that removes the listener again when
+/// dropped.
 #[wasm_bindgen]
 extern "C" {
 
     /// A wrapper around the JavaScript `HTMLElement` representing a Plotly
     /// `<div>`.
     ///
-    /// This type extends `web_sys::HtmlElement` and exposes Plotly’s  
-    /// `.on(eventName, callback)` method for attaching event listeners.
+    /// This type extends `web_sys::HtmlElement` and exposes Plotly’s
+    /// `.on(eventName, callback)` and `.removeListener(eventName, callback)`
+    /// methods for attaching and detaching event listeners.
 
     #[wasm_bindgen(extends= HtmlElement, js_name=HTMLElement)]
+    #[derive(Clone)]
     type PlotlyDiv;
 
     /// Attach a JavaScript event listener to this Plotly `<div>`.
@@ -27,11 +36,117 @@ extern "C" {
     /// - `cb`: A JS `Function` to invoke when the event fires.
     ///
     /// # Panics
-    /// This method assumes the underlying element is indeed a Plotly div  
+    /// This method assumes the underlying element is indeed a Plotly div
     /// and that the Plotly.js library has been loaded on the page.
 
     #[wasm_bindgen(method,structural,js_name=on)]
     fn on(this: &PlotlyDiv, event: &str, cb: &Function);
+
+    /// Detach a JavaScript event listener previously attached with `on`.
+
+    #[wasm_bindgen(method,structural,js_name=removeListener)]
+    fn remove_listener(this: &PlotlyDiv, event: &str, cb: &Function);
+}
+
+type Handler = Closure<dyn FnMut(JsValue) -> JsValue>;
+
+/// A Rust callback registered on a Plotly `<div>` by one of the `bind_*`
+/// functions.
+///
+/// The listener is removed from the `<div>` when the `EventListener` is
+/// dropped. Call [`EventListener::forget`] to keep it for the lifetime of the
+/// page instead.
+#[must_use = "the listener is removed when the `EventListener` is dropped"]
+pub struct EventListener {
+    div: PlotlyDiv,
+    event: &'static str,
+    closure: Option<Handler>,
+}
+
+impl EventListener {
+    /// The Plotly event name this listener is registered for.
+    pub fn event(&self) -> &'static str {
+        self.event
+    }
+
+    /// Keep the listener registered for the lifetime of the page, leaking
+    /// the Rust closure.
+    pub fn forget(mut self) {
+        if let Some(closure) = self.closure.take() {
+            closure.forget();
+        }
+    }
+}
+
+impl Drop for EventListener {
+    fn drop(&mut self) {
+        if let Some(closure) = self.closure.take() {
+            self.div
+                .remove_listener(self.event, closure.as_ref().unchecked_ref());
+        }
+    }
+}
+
+/// Register `handler` for `event` on the `<div>` with id `div_id`.
+///
+/// # Panics
+/// Panics if there is no element with id `div_id`.
+fn listen<H>(div_id: &str, event: &'static str, handler: H) -> EventListener
+where
+    H: 'static + FnMut(JsValue) -> JsValue,
+{
+    let closure = Closure::wrap(Box::new(handler) as Box<dyn FnMut(JsValue) -> JsValue>);
+    let div: PlotlyDiv = get_div(div_id).expect("Could not get Div element by Id");
+    div.on(event, closure.as_ref().unchecked_ref());
+    EventListener {
+        div,
+        event,
+        closure: Some(closure),
+    }
+}
+
+/// Register `cb` for an `event` whose payload deserializes into `E`.
+/// Payloads that do not deserialize, e.g. the `undefined` sent with
+/// `plotly_selected` when a selection is cleared, are skipped.
+fn bind_event<E, F>(div_id: &str, event: &'static str, mut cb: F) -> EventListener
+where
+    E: DeserializeOwned,
+    F: 'static + FnMut(E),
+{
+    listen(div_id, event, move |data| {
+        if let Ok(event) = serde_wasm_bindgen::from_value(data) {
+            cb(event);
+        }
+        JsValue::UNDEFINED
+    })
+}
+
+/// Register `cb` for an `event` that carries no payload.
+fn bind_unit_event<F>(div_id: &str, event: &'static str, mut cb: F) -> EventListener
+where
+    F: 'static + FnMut(),
+{
+    listen(div_id, event, move |_| {
+        cb();
+        JsValue::UNDEFINED
+    })
+}
+
+/// Register `cb` for a legend `event`. Returning `false` from `cb` cancels
+/// the default legend behavior of plotly.js. Payloads that do not deserialize
+/// are skipped, keeping the default behavior.
+fn bind_legend_event<F>(div_id: &str, event: &'static str, mut cb: F) -> EventListener
+where
+    F: 'static + FnMut(LegendClickEvent) -> bool,
+{
+    listen(
+        div_id,
+        event,
+        move |data| match serde_wasm_bindgen::from_value(data) {
+            Ok(event) => JsValue::from_bool(cb(event)),
+            Err(_) => JsValue::UNDEFINED,
+        },
+    )
 }
 
 /// Bind a Rust callback to the Plotly `plotly_click` event on a given `<div>`.
@@ -46,96 +161,135 @@ extern "C" {
 ///
 /// # Details
 /// 1. Looks up the element by `div_id`, converts it to `PlotlyDiv`.
-/// 2. Wraps a `Closure<dyn FnMut(JsValue)>` that deserializes the JS event into
-///    our `ClickEvent` type via `serde_wasm_bindgen`.
+/// 2. Wraps a `Closure<dyn FnMut(JsValue) -> JsValue>` that deserializes the
+///    JS event into our `ClickEvent` type via `serde_wasm_bindgen`.
 /// 3. Calls `plot_div.on("plotly_click", …)` to register the listener.
-/// 4. Forgets the closure so it lives for the lifetime of the page.
+/// 4. Returns an `EventListener` which removes the listener when dropped.
+///
+/// # Panics
+/// Panics if there is no element with id `div_id`.
 ///
 /// # Example
 /// ```ignore
 /// bind_click("my-plot", |evt| {
 ///     web_sys::console::log_1(&format!("{:?}", evt).into());
-/// });
+/// })
+/// .forget();
 /// ```
-pub fn bind_click<F>(div_id: &str, mut cb: F)
+pub fn bind_click<F>(div_id: &str, cb: F) -> EventListener
 where
     F: 'static + FnMut(ClickEvent),
 {
-    let closure = Closure::wrap(Box::new(move |event: JsValue| {
-        let event: ClickEvent =
-            serde_wasm_bindgen::from_value(event).expect("Could not serialize the event");
-        cb(event);
-    }) as Box<dyn FnMut(JsValue)>);
+    bind_event(div_id, "plotly_click", cb)
+}
 
-    let plot_div: PlotlyDiv = get_div(div_id).expect("Could not get Div element by Id");
-    plot_div.on("plotly_click", closure.as_ref().unchecked_ref());
-    closure.forget();
+/// Bind a Rust callback to the Plotly `plotly_hover` event on a given `<div>`.
+pub fn bind_hover<F>(div_id: &str, cb: F) -> EventListener
+where
+    F: 'static + FnMut(PointsEvent),
+{
+    bind_event(div_id, "plotly_hover", cb)
 }
 
-fn get_div(tag: &str) -> Option<PlotlyDiv> {
-    web_sys::window()?
-        .document()?
-        .get_element_by_id(tag)?
-        .dyn_into()
-        .ok()
+/// Bind a Rust callback to the Plotly `plotly_unhover` event on a given
+/// `<div>`.
+pub fn bind_unhover<F>(div_id: &str, cb: F) -> EventListener
+where
+    F: 'static + FnMut(PointsEvent),
+{
+    bind_event(div_id, "plotly_unhover", cb)
 }
 
-/// Represents a single point from a Plotly click event.
-///
-/// Fields mirror Plotly’s `event.points[i]` properties, all optional
-/// where appropriate:
-///
-/// - `curve_number`: The zero-based index of the trace that was clicked.
-/// - `point_numbers`: An optional list of indices if multiple points were
-///   selected.
-/// - `point_number`: The index of the specific point clicked (if singular).
-/// - `x`, `y`, `z`: Optional numeric coordinates in data space.
-/// - `lat`, `lon`: Optional geographic coordinates (for map plots).
-///
-/// # Serialization
-/// Uses `serde` with `camelCase` field names to match Plotly’s JS API.
-#[derive(Debug, Deserialize, Serialize, Default)]
-#[serde(rename_all = "camelCase")]
-pub struct ClickPoint {
-    pub curve_number: usize,
-    pub point_numbers: Option<Vec<usize>>,
-    pub point_number: Option<usize>,
-    pub x: Option<f64>,
-    pub y: Option<f64>,
-    pub z: Option<f64>,
-    pub lat: Option<f64>,
-    pub lon: Option<f64>,
-}
-
-/// Provide a default single-point vector for `ClickEvent::points`.
-///
-/// Returns `vec![ClickPoint::default()]` so deserialization always yields
-/// at least one element rather than an empty vector.
-fn default_click_event() -> Vec<ClickPoint> {
-    vec![ClickPoint::default()]
+/// Bind a Rust callback to the Plotly `plotly_selected` event on a given
+/// `<div>`, fired when a box or lasso selection is completed. Clearing a
+/// selection fires the event without points, which is not passed to `cb`;
+/// use [`bind_deselect`] for it.
+pub fn bind_selected<F>(div_id: &str, cb: F) -> EventListener
+where
+    F: 'static + FnMut(SelectionEvent),
+{
+    bind_event(div_id, "plotly_selected", cb)
 }
 
-/// The top-level payload for a Plotly click event.
-///
-/// - `points`: A `Vec<ClickPoint>` containing all clicked points. Defaults to
-///   the result of `default_click_event` to ensure `points` is non-empty even
-///   if Plotly sends no data.
-///
-/// # Serialization
-/// Uses `serde` with `camelCase` names and a custom default so you can
-/// call `event.points` without worrying about missing values.
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(rename_all = "camelCase", default)]
-pub struct ClickEvent {
-    #[serde(default = "default_click_event")]
-    pub points: Vec<ClickPoint>,
+/// Bind a Rust callback to the Plotly `plotly_selecting` event on a given
+/// `<div>`, fired repeatedly while a selection is being drawn.
+pub fn bind_selecting<F>(div_id: &str, cb: F) -> EventListener
+where
+    F: 'static + FnMut(SelectionEvent),
+{
+    bind_event(div_id, "plotly_selecting", cb)
 }
 
-/// A `Default` implementation yielding an empty `points` vector.
-///
-/// Useful when you need a zero-event placeholder (e.g., initial state).
-impl Default for ClickEvent {
-    fn default() -> Self {
-        ClickEvent { points: vec![] }
-    }
+/// Bind a Rust callback to the Plotly `plotly_deselect` event on a given
+/// `<div>`, fired when a selection is cleared.
+pub fn bind_deselect<F>(div_id: &str, cb: F) -> EventListener
+where
+    F: 'static + FnMut(),
+{
+    bind_unit_event(div_id, "plotly_deselect", cb)
+}
+
+/// Bind a Rust callback to the Plotly `plotly_relayout` event on a given
+/// `<div>`, fired after zooming, panning or any other layout change.
+pub fn bind_relayout<F>(div_id: &str, cb: F) -> EventListener
+where
+    F: 'static + FnMut(RelayoutEvent),
+{
+    bind_event(div_id, "plotly_relayout", cb)
+}
+
+/// Bind a Rust callback to the Plotly `plotly_restyle` event on a given
+/// `<div>`, fired after trace attributes change, e.g. when toggling
+/// visibility from the legend.
+pub fn bind_restyle<F>(div_id: &str, cb: F) -> EventListener
+where
+    F: 'static + FnMut(RestyleEvent),
+{
+    bind_event(div_id, "plotly_restyle", cb)
+}
+
+/// Bind a Rust callback to the Plotly `plotly_legendclick` event on a given
+/// `<div>`. Return `false` from `cb` to prevent the default toggling of the
+/// trace's visibility.
+pub fn bind_legend_click<F>(div_id: &str, cb: F) -> EventListener
+where
+    F: 'static + FnMut(LegendClickEvent) -> bool,
+{
+    bind_legend_event(div_id, "plotly_legendclick", cb)
+}
+
+/// Bind a Rust callback to the Plotly `plotly_legenddoubleclick` event on a
+/// given `<div>`. Return `false` from `cb` to prevent the default isolation
+/// of the trace.
+pub fn bind_legend_double_click<F>(div_id: &str, cb: F) -> EventListener
+where
+    F: 'static + FnMut(LegendClickEvent) -> bool,
+{
+    bind_legend_event(div_id, "plotly_legenddoubleclick", cb)
+}
+
+/// Bind a Rust callback to the Plotly `plotly_animated` event on a given
+/// `<div>`, fired when an animation finishes.
+pub fn bind_animated<F>(div_id: &str, cb: F) -> EventListener
+where
+    F: 'static + FnMut(),
+{
+    bind_unit_event(div_id, "plotly_animated", cb)
+}
+
+/// Bind a Rust callback to the Plotly `plotly_sliderchange` event on a given
+/// `<div>`.
+pub fn bind_slider_change<F>(div_id: &str, cb: F) -> EventListener
+where
+    F: 'static + FnMut(SliderChangeEvent),
+{
+    bind_event(div_id, "plotly_sliderchange", cb)
+}
+
+fn get_div(tag: &str) -> Option<PlotlyDiv> {
+    web_sys::window()?
+        .document()?
+        .get_element_by_id(tag)?
+        .dyn_into()
+        .ok()
 }
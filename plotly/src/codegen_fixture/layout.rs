@@ -0,0 +1,132 @@
+//! Layout
+//!
+//! This file is generated by `plotly_codegen` from plotly.js `plot-schema.json`.
+//! Do not edit it by hand; re-run the generator instead.
+
+use plotly_derive::FieldSetter;
+use serde::Serialize;
+
+use crate::{color::Color, private::NumOrString};
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum LayoutTitleXanchor {
+    #[serde(rename = "auto")]
+    Auto,
+    #[serde(rename = "left")]
+    Left,
+    #[serde(rename = "center")]
+    Center,
+    #[serde(rename = "right")]
+    Right,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, FieldSetter)]
+pub struct LayoutTitle {
+    /// Sets the plot's title.
+    text: Option<String>,
+    x: Option<f64>,
+    xanchor: Option<LayoutTitleXanchor>,
+}
+
+impl LayoutTitle {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum LayoutXaxisType {
+    #[serde(rename = "-")]
+    Minus,
+    #[serde(rename = "linear")]
+    Linear,
+    #[serde(rename = "log")]
+    Log,
+    #[serde(rename = "date")]
+    Date,
+    #[serde(rename = "category")]
+    Category,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum LayoutXaxisAnchor {
+    #[serde(rename = "free")]
+    Free,
+    /// A value matching `^x([2-9]|[1-9][0-9]+)?( domain)?$`.
+    /// A value matching `^y([2-9]|[1-9][0-9]+)?( domain)?$`.
+    #[serde(untagged)]
+    Other(String),
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, FieldSetter)]
+pub struct LayoutXaxis {
+    r#type: Option<LayoutXaxisType>,
+    range: Option<Vec<NumOrString>>,
+    anchor: Option<LayoutXaxisAnchor>,
+    tickangle: Option<f64>,
+    nticks: Option<i64>,
+}
+
+impl LayoutXaxis {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, FieldSetter)]
+pub struct LayoutAnnotations {
+    text: Option<String>,
+    x: Option<serde_json::Value>,
+    showarrow: Option<bool>,
+}
+
+impl LayoutAnnotations {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum LayoutBarmode {
+    #[serde(rename = "stack")]
+    Stack,
+    #[serde(rename = "group")]
+    Group,
+    #[serde(rename = "overlay")]
+    Overlay,
+    #[serde(rename = "relative")]
+    Relative,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum LayoutScattermode {
+    #[serde(rename = "group")]
+    Group,
+    #[serde(rename = "overlay")]
+    Overlay,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, FieldSetter)]
+#[field_setter(kind = "layout")]
+pub struct Layout {
+    title: Option<LayoutTitle>,
+    width: Option<f64>,
+    showlegend: Option<bool>,
+    paper_bgcolor: Option<Box<dyn Color>>,
+    colorway: Option<Vec<Box<dyn Color>>>,
+    xaxis: Option<LayoutXaxis>,
+    annotations: Option<Vec<LayoutAnnotations>>,
+    barmode: Option<LayoutBarmode>,
+    scattermode: Option<LayoutScattermode>,
+    bargap: Option<f64>,
+}
+
+impl Layout {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
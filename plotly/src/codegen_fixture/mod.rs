@@ -0,0 +1,6 @@
+//! Trace and layout types generated from the plotly.js `plot-schema.json`
+//!
+//! This file is generated by `plotly_codegen`. Do not edit it by hand.
+
+pub mod layout;
+pub mod traces;
@@ -0,0 +1,57 @@
+//! Bar trace
+//!
+//! This file is generated by `plotly_codegen` from plotly.js `plot-schema.json`.
+//! Do not edit it by hand; re-run the generator instead.
+
+use plotly_derive::FieldSetter;
+use serde::Serialize;
+
+use crate::{color::Color, common::Dim, private::NumOrStringCollection, Trace};
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum BarOrientation {
+    #[serde(rename = "v")]
+    V,
+    #[serde(rename = "h")]
+    H,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, FieldSetter)]
+pub struct BarMarker {
+    colors: Option<Vec<Box<dyn Color>>>,
+    color: Option<Dim<Box<dyn Color>>>,
+}
+
+impl BarMarker {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+/// The data visualized by the span of the bars is set in `y` if `orientation`
+/// is set to *v* (the default).
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, FieldSetter)]
+#[field_setter(box_self, kind = "trace")]
+pub struct Bar {
+    #[field_setter(default = "\"bar\".to_string()")]
+    r#type: String,
+    x: Option<NumOrStringCollection>,
+    y: Option<NumOrStringCollection>,
+    orientation: Option<BarOrientation>,
+    width: Option<Dim<f64>>,
+    marker: Option<BarMarker>,
+}
+
+impl Bar {
+    pub fn new() -> Box<Self> {
+        Box::default()
+    }
+}
+
+impl Trace for Bar {
+    fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
@@ -0,0 +1,9 @@
+//! Generated trace types
+//!
+//! This file is generated by `plotly_codegen`. Do not edit it by hand.
+
+pub mod scatter;
+pub mod bar;
+
+pub use scatter::Scatter;
+pub use bar::Bar;
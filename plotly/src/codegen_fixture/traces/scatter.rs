@@ -0,0 +1,151 @@
+//! Scatter trace
+//!
+//! This file is generated by `plotly_codegen` from plotly.js `plot-schema.json`.
+//! Do not edit it by hand; re-run the generator instead.
+
+use plotly_derive::FieldSetter;
+use serde::Serialize;
+
+use crate::{color::Color, common::{ColorScale, Dim}, private::{NumOrString, NumOrStringCollection}, Trace};
+
+/// Sets the area to fill with a solid color.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum ScatterFill {
+    #[serde(rename = "none")]
+    None,
+    #[serde(rename = "tozeroy")]
+    Tozeroy,
+    #[serde(rename = "tozerox")]
+    Tozerox,
+    #[serde(rename = "toself")]
+    Toself,
+    #[serde(rename = "tonext")]
+    Tonext,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum ScatterMarkerSizemode {
+    #[serde(rename = "diameter")]
+    Diameter,
+    #[serde(rename = "area")]
+    Area,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, FieldSetter)]
+pub struct ScatterMarkerLine {
+    width: Option<Dim<f64>>,
+    dash: Option<String>,
+}
+
+impl ScatterMarkerLine {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, FieldSetter)]
+pub struct ScatterMarker {
+    /// Sets the marker color.
+    color: Option<Dim<Box<dyn Color>>>,
+    size: Option<Dim<f64>>,
+    symbol: Option<Dim<serde_json::Value>>,
+    angle: Option<Dim<f64>>,
+    maxdisplayed: Option<i64>,
+    colorscale: Option<ColorScale>,
+    sizemode: Option<ScatterMarkerSizemode>,
+    line: Option<ScatterMarkerLine>,
+}
+
+impl ScatterMarker {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum ScatterLineShape {
+    #[serde(rename = "linear")]
+    Linear,
+    #[serde(rename = "spline")]
+    Spline,
+    #[serde(rename = "hv")]
+    Hv,
+    #[serde(rename = "vh")]
+    Vh,
+    #[serde(rename = "hvh")]
+    Hvh,
+    #[serde(rename = "vhv")]
+    Vhv,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, FieldSetter)]
+pub struct ScatterLine {
+    shape: Option<ScatterLineShape>,
+    color: Option<Box<dyn Color>>,
+}
+
+impl ScatterLine {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, FieldSetter)]
+pub struct ScatterTransforms {
+    enabled: Option<bool>,
+    target: Option<Vec<NumOrString>>,
+}
+
+impl ScatterTransforms {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+/// The scatter trace type encompasses line charts, scatter charts, text charts,
+/// and bubble charts.
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, FieldSetter)]
+#[field_setter(box_self, kind = "trace")]
+pub struct Scatter {
+    #[field_setter(default = "\"scatter\".to_string()")]
+    r#type: String,
+    /// Sets the trace name.
+    name: Option<String>,
+    visible: Option<serde_json::Value>,
+    opacity: Option<f64>,
+    legendrank: Option<f64>,
+    /// Sets the x coordinates.
+    x: Option<NumOrStringCollection>,
+    /// Sets the y coordinates.
+    y: Option<NumOrStringCollection>,
+    xaxis: Option<String>,
+    text: Option<Dim<String>>,
+    hoverinfo: Option<Dim<String>>,
+    mode: Option<String>,
+    /// Sets the area to fill with a solid color.
+    fill: Option<ScatterFill>,
+    connectgaps: Option<bool>,
+    customdata: Option<NumOrStringCollection>,
+    meta: Option<Dim<serde_json::Value>>,
+    selectedpoints: Option<serde_json::Value>,
+    marker: Option<ScatterMarker>,
+    line: Option<ScatterLine>,
+    transforms: Option<Vec<ScatterTransforms>>,
+}
+
+impl Scatter {
+    pub fn new() -> Box<Self> {
+        Box::default()
+    }
+}
+
+impl Trace for Scatter {
+    fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
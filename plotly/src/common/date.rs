@@ -0,0 +1,303 @@
+//! Conversion of `chrono` and `time` dates into the string format used by
+//! `plotly.js` date axes.
+//!
+//! `plotly.js` has no notion of time zones: a date-time is drawn at the wall
+//! clock time written in its string. Converting a zoned date-time with
+//! `From` therefore converts it to UTC first, so that date-times from
+//! different zones share a common axis. To show the wall clock time of
+//! another zone, convert them with `PlotlyDate::from_chrono_in` or
+//! `PlotlyDate::from_time_in` instead.
+
+use std::fmt::Write as _;
+
+use serde::{Serialize, Serializer};
+
+use crate::private::NumOrString;
+
+/// A date or date-time formatted as `yyyy-mm-dd HH:MM:SS.fffffffff`, the
+/// format understood by `plotly.js`. Time components that are zero at the end
+/// are omitted.
+///
+/// `PlotlyDate` can be used as trace data, e.g. `Scatter::new(dates, values)`
+/// with `dates: Vec<PlotlyDate>`, and converts into the values accepted by
+/// `AxisRange`, `RangeBreak`, `Shape` and `Annotation` positions.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PlotlyDate(String);
+
+impl PlotlyDate {
+    fn from_parts((year, month, day): (i32, u32, u32), time: Option<(u32, u32, u32, u32)>) -> Self {
+        let mut date = format!("{year:04}-{month:02}-{day:02}");
+        if let Some((hour, minute, second, nanosecond)) = time {
+            write!(date, " {hour:02}:{minute:02}:{second:02}").unwrap();
+            if nanosecond > 0 {
+                let fraction = format!("{nanosecond:09}");
+                write!(date, ".{}", fraction.trim_end_matches('0')).unwrap();
+            }
+        }
+        Self(date)
+    }
+
+    /// The formatted date.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Convert every element of `dates`, e.g. to pass a date column to a
+    /// trace constructor.
+    pub fn from_dates<I>(dates: I) -> Vec<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<Self>,
+    {
+        dates.into_iter().map(Into::into).collect()
+    }
+}
+
+impl Serialize for PlotlyDate {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl From<PlotlyDate> for NumOrString {
+    fn from(date: PlotlyDate) -> Self {
+        NumOrString::S(date.0)
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, TimeZone, Timelike};
+
+    use super::PlotlyDate;
+    use crate::private::NumOrString;
+
+    impl PlotlyDate {
+        /// Convert `date_time` to the time zone `tz` and format its wall
+        /// clock time there.
+        pub fn from_chrono_in<Tz1: TimeZone, Tz2: TimeZone>(
+            date_time: &DateTime<Tz1>,
+            tz: &Tz2,
+        ) -> Self {
+            date_time.with_timezone(tz).naive_local().into()
+        }
+    }
+
+    impl From<NaiveDate> for PlotlyDate {
+        fn from(date: NaiveDate) -> Self {
+            Self::from_parts((date.year(), date.month(), date.day()), None)
+        }
+    }
+
+    impl From<NaiveDateTime> for PlotlyDate {
+        fn from(date_time: NaiveDateTime) -> Self {
+            Self::from_parts(
+                (date_time.year(), date_time.month(), date_time.day()),
+                Some((
+                    date_time.hour(),
+                    date_time.minute(),
+                    date_time.second(),
+                    date_time.nanosecond(),
+                )),
+            )
+        }
+    }
+
+    /// Formats the UTC time of `date_time`, see [`PlotlyDate::from_chrono_in`]
+    /// for other time zones.
+    impl<Tz: TimeZone> From<DateTime<Tz>> for PlotlyDate {
+        fn from(date_time: DateTime<Tz>) -> Self {
+            date_time.naive_utc().into()
+        }
+    }
+
+    impl From<NaiveDate> for NumOrString {
+        fn from(date: NaiveDate) -> Self {
+            PlotlyDate::from(date).into()
+        }
+    }
+
+    impl From<NaiveDateTime> for NumOrString {
+        fn from(date_time: NaiveDateTime) -> Self {
+            PlotlyDate::from(date_time).into()
+        }
+    }
+
+    impl<Tz: TimeZone> From<DateTime<Tz>> for NumOrString {
+        fn from(date_time: DateTime<Tz>) -> Self {
+            PlotlyDate::from(date_time).into()
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use time::{Date, OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+    use super::PlotlyDate;
+    use crate::private::NumOrString;
+
+    impl PlotlyDate {
+        /// Convert `date_time` to the UTC offset `offset` and format its wall
+        /// clock time there.
+        pub fn from_time_in(date_time: OffsetDateTime, offset: UtcOffset) -> Self {
+            let date_time = date_time.to_offset(offset);
+            PrimitiveDateTime::new(date_time.date(), date_time.time()).into()
+        }
+    }
+
+    impl From<Date> for PlotlyDate {
+        fn from(date: Date) -> Self {
+            Self::from_parts((date.year(), date.month() as u32, date.day() as u32), None)
+        }
+    }
+
+    impl From<PrimitiveDateTime> for PlotlyDate {
+        fn from(date_time: PrimitiveDateTime) -> Self {
+            Self::from_parts(
+                (
+                    date_time.year(),
+                    date_time.month() as u32,
+                    date_time.day() as u32,
+                ),
+                Some((
+                    date_time.hour() as u32,
+                    date_time.minute() as u32,
+                    date_time.second() as u32,
+                    date_time.nanosecond(),
+                )),
+            )
+        }
+    }
+
+    /// Formats the UTC time of `date_time`, see [`PlotlyDate::from_time_in`]
+    /// for other offsets.
+    impl From<OffsetDateTime> for PlotlyDate {
+        fn from(date_time: OffsetDateTime) -> Self {
+            Self::from_time_in(date_time, UtcOffset::UTC)
+        }
+    }
+
+    impl From<Date> for NumOrString {
+        fn from(date: Date) -> Self {
+            PlotlyDate::from(date).into()
+        }
+    }
+
+    impl From<PrimitiveDateTime> for NumOrString {
+        fn from(date_time: PrimitiveDateTime) -> Self {
+            PlotlyDate::from(date_time).into()
+        }
+    }
+
+    impl From<OffsetDateTime> for NumOrString {
+        fn from(date_time: OffsetDateTime) -> Self {
+            PlotlyDate::from(date_time).into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, to_value};
+
+    use super::*;
+
+    #[test]
+    fn format_parts() {
+        assert_eq!(
+            PlotlyDate::from_parts((2024, 3, 9), None).as_str(),
+            "2024-03-09"
+        );
+        assert_eq!(
+            PlotlyDate::from_parts((2024, 3, 9), Some((7, 5, 0, 0))).as_str(),
+            "2024-03-09 07:05:00"
+        );
+        assert_eq!(
+            PlotlyDate::from_parts((2024, 3, 9), Some((7, 5, 0, 250_000_000))).as_str(),
+            "2024-03-09 07:05:00.25"
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_dates() {
+        use chrono::{FixedOffset, NaiveDate, TimeZone, Utc};
+
+        use crate::layout::{Annotation, AxisRange, Shape};
+
+        let date = NaiveDate::from_ymd_opt(2024, 3, 9).unwrap();
+        assert_eq!(
+            to_value(PlotlyDate::from(date)).unwrap(),
+            json!("2024-03-09")
+        );
+
+        let utc = Utc.with_ymd_and_hms(2024, 3, 9, 23, 30, 0).unwrap();
+        let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+        assert_eq!(PlotlyDate::from(utc).as_str(), "2024-03-09 23:30:00");
+        assert_eq!(
+            PlotlyDate::from(utc.with_timezone(&tokyo)).as_str(),
+            "2024-03-09 23:30:00"
+        );
+        assert_eq!(
+            PlotlyDate::from_chrono_in(&utc, &tokyo).as_str(),
+            "2024-03-10 08:30:00"
+        );
+
+        let range = AxisRange::new(date, utc);
+        assert_eq!(
+            to_value(range).unwrap(),
+            json!(["2024-03-09", "2024-03-09 23:30:00"])
+        );
+        let shape = Shape::new().x0(date).x1(utc);
+        assert_eq!(
+            to_value(shape).unwrap(),
+            json!({"x0": "2024-03-09", "x1": "2024-03-09 23:30:00"})
+        );
+        let annotation = Annotation::new().x(utc);
+        assert_eq!(
+            to_value(annotation).unwrap(),
+            json!({"x": "2024-03-09 23:30:00"})
+        );
+        assert_eq!(
+            to_value(PlotlyDate::from_dates([date, date.succ_opt().unwrap()])).unwrap(),
+            json!(["2024-03-09", "2024-03-10"])
+        );
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_dates() {
+        use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+        use crate::layout::RangeBreak;
+
+        let date = Date::from_calendar_date(2024, Month::March, 9).unwrap();
+        assert_eq!(PlotlyDate::from(date).as_str(), "2024-03-09");
+
+        let time = Time::from_hms_milli(23, 30, 0, 125).unwrap();
+        let utc = PrimitiveDateTime::new(date, time).assume_utc();
+        let offset = UtcOffset::from_hms(-5, 0, 0).unwrap();
+        assert_eq!(PlotlyDate::from(utc).as_str(), "2024-03-09 23:30:00.125");
+        assert_eq!(
+            PlotlyDate::from(utc.to_offset(offset)).as_str(),
+            "2024-03-09 23:30:00.125"
+        );
+        assert_eq!(
+            PlotlyDate::from_time_in(utc, offset).as_str(),
+            "2024-03-09 18:30:00.125"
+        );
+
+        let range_break = RangeBreak::new().bounds(
+            OffsetDateTime::UNIX_EPOCH,
+            OffsetDateTime::UNIX_EPOCH + time::Duration::days(1),
+        );
+        assert_eq!(
+            to_value(range_break).unwrap(),
+            json!({"bounds": ["1970-01-01 00:00:00", "1970-01-02 00:00:00"]})
+        );
+    }
+}
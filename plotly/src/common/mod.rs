@@ -1,8 +1,16 @@
 pub mod color;
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub mod date;
+pub mod point;
+pub mod typed_array;
 
 use plotly_derive::FieldSetter;
 use serde::{Serialize, Serializer};
 
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub use self::date::PlotlyDate;
+pub use self::point::{Point3D, TriangleIndex};
+pub use self::typed_array::{DataEncoding, TypedArray};
 use crate::{
     color::{Color, ColorArray},
     private,
@@ -0,0 +1,169 @@
+//! Three dimensional points accepted by `Scatter3D::from_points` and
+//! `Mesh3D::from_triangles`.
+//!
+//! Points are given as `[T; 3]` arrays or `(T, T, T)` tuples, or, with the
+//! `nalgebra` and `glam` features enabled, as the point and vector types of
+//! those crates.
+
+/// A type holding the `x`, `y` and `z` coordinates of a point.
+pub trait Point3D {
+    type Scalar;
+
+    /// The coordinates of the point, in `x`, `y`, `z` order.
+    fn to_xyz(&self) -> [Self::Scalar; 3];
+}
+
+/// An integer type used in triangle index buffers.
+pub trait TriangleIndex: Copy {
+    fn to_usize(self) -> usize;
+}
+
+macro_rules! triangle_index {
+    ($($ty:ty),*) => {
+        $(
+            impl TriangleIndex for $ty {
+                fn to_usize(self) -> usize {
+                    self as usize
+                }
+            }
+        )*
+    };
+}
+
+triangle_index!(u8, u16, u32, u64, usize);
+
+impl<T: Copy> Point3D for [T; 3] {
+    type Scalar = T;
+
+    fn to_xyz(&self) -> [T; 3] {
+        *self
+    }
+}
+
+impl<T: Copy> Point3D for (T, T, T) {
+    type Scalar = T;
+
+    fn to_xyz(&self) -> [T; 3] {
+        [self.0, self.1, self.2]
+    }
+}
+
+impl<P: Point3D> Point3D for &P {
+    type Scalar = P::Scalar;
+
+    fn to_xyz(&self) -> [P::Scalar; 3] {
+        (*self).to_xyz()
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl<T: nalgebra::Scalar + Copy> Point3D for nalgebra::Point3<T> {
+    type Scalar = T;
+
+    fn to_xyz(&self) -> [T; 3] {
+        [self.x, self.y, self.z]
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl<T: nalgebra::Scalar + Copy> Point3D for nalgebra::Vector3<T> {
+    type Scalar = T;
+
+    fn to_xyz(&self) -> [T; 3] {
+        [self.x, self.y, self.z]
+    }
+}
+
+#[cfg(feature = "glam")]
+impl Point3D for glam::Vec3 {
+    type Scalar = f32;
+
+    fn to_xyz(&self) -> [f32; 3] {
+        self.to_array()
+    }
+}
+
+#[cfg(feature = "glam")]
+impl Point3D for glam::Vec3A {
+    type Scalar = f32;
+
+    fn to_xyz(&self) -> [f32; 3] {
+        self.to_array()
+    }
+}
+
+#[cfg(feature = "glam")]
+impl Point3D for glam::DVec3 {
+    type Scalar = f64;
+
+    fn to_xyz(&self) -> [f64; 3] {
+        self.to_array()
+    }
+}
+
+/// Split points into separate `x`, `y` and `z` coordinate vectors.
+pub(crate) fn split_points<P: Point3D>(points: &[P]) -> [Vec<P::Scalar>; 3] {
+    let mut x = Vec::with_capacity(points.len());
+    let mut y = Vec::with_capacity(points.len());
+    let mut z = Vec::with_capacity(points.len());
+    for point in points {
+        let [px, py, pz] = point.to_xyz();
+        x.push(px);
+        y.push(py);
+        z.push(pz);
+    }
+    [x, y, z]
+}
+
+/// Split triangles into the `i`, `j` and `k` vertex index vectors of a mesh.
+pub(crate) fn split_triangles<I: TriangleIndex>(triangles: &[[I; 3]]) -> [Vec<usize>; 3] {
+    let mut i = Vec::with_capacity(triangles.len());
+    let mut j = Vec::with_capacity(triangles.len());
+    let mut k = Vec::with_capacity(triangles.len());
+    for [a, b, c] in triangles {
+        i.push(a.to_usize());
+        j.push(b.to_usize());
+        k.push(c.to_usize());
+    }
+    [i, j, k]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_array_and_tuple_points() {
+        let [x, y, z] = split_points(&[[1, 2, 3], [4, 5, 6]]);
+        assert_eq!((x, y, z), (vec![1, 4], vec![2, 5], vec![3, 6]));
+
+        let [x, y, z] = split_points(&[(1.0, 2.0, 3.0)]);
+        assert_eq!((x, y, z), (vec![1.0], vec![2.0], vec![3.0]));
+    }
+
+    #[test]
+    fn split_triangle_indices() {
+        let [i, j, k] = split_triangles(&[[0u32, 1, 2], [2, 3, 0]]);
+        assert_eq!((i, j, k), (vec![0, 2], vec![1, 3], vec![2, 0]));
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn nalgebra_points() {
+        let points = [
+            nalgebra::Point3::new(1.0f32, 2.0, 3.0),
+            nalgebra::Point3::new(4.0, 5.0, 6.0),
+        ];
+        let [x, y, z] = split_points(&points);
+        assert_eq!((x, y, z), (vec![1.0, 4.0], vec![2.0, 5.0], vec![3.0, 6.0]));
+        assert_eq!(nalgebra::Vector3::new(1, 2, 3).to_xyz(), [1, 2, 3]);
+    }
+
+    #[cfg(feature = "glam")]
+    #[test]
+    fn glam_points() {
+        assert_eq!(glam::Vec3::new(1.0, 2.0, 3.0).to_xyz(), [1.0, 2.0, 3.0]);
+        assert_eq!(glam::Vec3A::new(1.0, 2.0, 3.0).to_xyz(), [1.0, 2.0, 3.0]);
+        assert_eq!(glam::DVec3::new(1.0, 2.0, 3.0).to_xyz(), [1.0, 2.0, 3.0]);
+    }
+}
@@ -0,0 +1,1234 @@
+//! Binary (`bdata`) encoding of numeric arrays.
+//!
+//! Plotly.js 3 accepts typed arrays in place of plain JSON arrays, given as
+//! `{"dtype": "f8", "bdata": "<base64>", "shape": "rows,cols"}`. Encoding large
+//! numeric vectors this way makes the serialized figure several times smaller
+//! and lets `Plotly.newPlot` skip parsing the decimal text.
+
+use base64::{engine::general_purpose, Engine as _};
+use serde::ser::{
+    self, Impossible, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
+    SerializeTuple, SerializeTupleStruct, SerializeTupleVariant, Serializer,
+};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Trace attributes whose arrays must stay plain JSON, everything nested in
+/// them included.
+///
+/// plotly.js only accepts typed arrays for `data_array` attributes. These keys
+/// name the `info_array` attributes of the plotly.js schema that are long
+/// enough to be encoded, e.g. `domain.x`, the `range` and `constraintrange`
+/// of `parcoords` dimensions and `colorscale` pairs, and attributes taking
+/// either a number or a fixed-size array. Extend the list when a new plotly.js
+/// version adds such an attribute to a trace.
+const INFO_ARRAY_KEYS: &[&str] = &[
+    "domain",
+    "range",
+    "constraintrange",
+    "colorscale",
+    "bounds",
+    "zmin",
+    "zmax",
+];
+
+/// Arrays shorter than this are kept as JSON, as encoding them gains nothing.
+const MIN_ENCODED_LEN: usize = 16;
+
+/// Controls how the numeric data of a [`crate::Plot`] is serialized.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DataEncoding {
+    /// Numeric arrays are written as plain JSON arrays.
+    #[default]
+    Json,
+    /// Numeric vectors and matrices of the traces are written as base64
+    /// encoded typed arrays.
+    TypedArray,
+}
+
+/// Element type of a [`TypedArray`], using the plotly.js `dtype` names.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DType {
+    I1,
+    U1,
+    I2,
+    U2,
+    I4,
+    U4,
+    F4,
+    F8,
+}
+
+/// A base64 encoded typed array as understood by plotly.js.
+///
+/// # Examples
+///
+/// ```
+/// use plotly::common::TypedArray;
+///
+/// let array = TypedArray::from_f64(&[1.0, 2.0]);
+///
+/// let expected = serde_json::json!({
+///     "dtype": "f8",
+///     "bdata": "AAAAAAAA8D8AAAAAAAAAQA=="
+/// });
+///
+/// assert_eq!(serde_json::to_value(array).unwrap(), expected);
+/// ```
+#[serde_with::skip_serializing_none]
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct TypedArray {
+    dtype: DType,
+    bdata: String,
+    shape: Option<String>,
+}
+
+macro_rules! typed_array_from {
+    ($($fn_name:ident, $ty:ty, $dtype:expr;)*) => {
+        $(
+            #[doc = concat!("Encode a slice of `", stringify!($ty), "` values.")]
+            pub fn $fn_name(values: &[$ty]) -> Self {
+                let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+                Self::from_le_bytes($dtype, &bytes)
+            }
+        )*
+    };
+}
+
+impl TypedArray {
+    /// Create a typed array from raw little-endian bytes of the given element
+    /// type.
+    pub fn from_le_bytes(dtype: DType, bytes: &[u8]) -> Self {
+        Self {
+            dtype,
+            bdata: general_purpose::STANDARD.encode(bytes),
+            shape: None,
+        }
+    }
+
+    typed_array_from! {
+        from_i8, i8, DType::I1;
+        from_u8, u8, DType::U1;
+        from_i16, i16, DType::I2;
+        from_u16, u16, DType::U2;
+        from_i32, i32, DType::I4;
+        from_u32, u32, DType::U4;
+        from_f32, f32, DType::F4;
+        from_f64, f64, DType::F8;
+    }
+
+    /// Interpret the encoded values as a row-major matrix of the given
+    /// dimensions.
+    pub fn shape(mut self, rows: usize, columns: usize) -> Self {
+        self.shape = Some(format!("{rows},{columns}"));
+        self
+    }
+
+    /// Encode a JSON array of numbers, or a rectangular JSON array of arrays of
+    /// numbers, choosing the smallest `dtype` that represents every value
+    /// exactly. Returns `None` for anything else, e.g. arrays containing
+    /// strings or `null`.
+    pub fn from_value(value: &Value) -> Option<Self> {
+        let mut buffer = NumberBuffer::default();
+        for item in value.as_array()? {
+            buffer.push(item).ok()?;
+        }
+        (buffer.len > 0).then(|| buffer.typed_array())
+    }
+
+    /// Encode integers with the smallest `dtype` holding all of them, `f8` if
+    /// they do not fit in 32 bits.
+    fn from_integers(values: &[i64]) -> Self {
+        let min = values.iter().copied().min().unwrap_or(0);
+        let max = values.iter().copied().max().unwrap_or(0);
+        macro_rules! narrow {
+            ($ty:ty, $dtype:expr) => {{
+                let bytes: Vec<u8> = values
+                    .iter()
+                    .flat_map(|&v| (v as $ty).to_le_bytes())
+                    .collect();
+                Self::from_le_bytes($dtype, &bytes)
+            }};
+        }
+        if min >= 0 && max <= u8::MAX as i64 {
+            narrow!(u8, DType::U1)
+        } else if min >= 0 && max <= u16::MAX as i64 {
+            narrow!(u16, DType::U2)
+        } else if min >= 0 && max <= u32::MAX as i64 {
+            narrow!(u32, DType::U4)
+        } else if min >= i8::MIN as i64 && max <= i8::MAX as i64 {
+            narrow!(i8, DType::I1)
+        } else if min >= i16::MIN as i64 && max <= i16::MAX as i64 {
+            narrow!(i16, DType::I2)
+        } else if min >= i32::MIN as i64 && max <= i32::MAX as i64 {
+            narrow!(i32, DType::I4)
+        } else {
+            narrow!(f64, DType::F8)
+        }
+    }
+}
+
+#[cfg(feature = "plotly_arrow")]
+impl TypedArray {
+    /// Encode a numeric Arrow array, copying its value buffer directly where
+    /// the element type has a `plotly.js` `dtype`.
+    ///
+    /// Nulls in floating point arrays are encoded as `NaN`, which `plotly.js`
+    /// shows as gaps. Returns `None` for integer arrays containing nulls,
+    /// 64-bit integers that cannot be represented exactly as `f64` and
+    /// non-numeric arrays.
+    pub fn from_arrow(array: &dyn arrow_array::Array) -> Option<Self> {
+        use arrow_array::{cast::AsArray, types::*};
+        use arrow_schema::DataType;
+
+        macro_rules! buffer {
+            ($arrow_ty:ty, $fn_name:ident, $dtype:expr) => {{
+                let values = array.as_primitive::<$arrow_ty>().values();
+                if cfg!(target_endian = "little") {
+                    Self::from_le_bytes($dtype, values.inner().as_slice())
+                } else {
+                    Self::$fn_name(values)
+                }
+            }};
+        }
+
+        let has_nulls = array.null_count() > 0;
+        let typed = match array.data_type() {
+            DataType::Float32 if has_nulls => {
+                let values: Vec<f32> = array
+                    .as_primitive::<Float32Type>()
+                    .iter()
+                    .map(|v| v.unwrap_or(f32::NAN))
+                    .collect();
+                Self::from_f32(&values)
+            }
+            DataType::Float64 if has_nulls => {
+                let values: Vec<f64> = array
+                    .as_primitive::<Float64Type>()
+                    .iter()
+                    .map(|v| v.unwrap_or(f64::NAN))
+                    .collect();
+                Self::from_f64(&values)
+            }
+            DataType::Float32 => buffer!(Float32Type, from_f32, DType::F4),
+            DataType::Float64 => buffer!(Float64Type, from_f64, DType::F8),
+            _ if has_nulls => return None,
+            DataType::Int8 => buffer!(Int8Type, from_i8, DType::I1),
+            DataType::UInt8 => buffer!(UInt8Type, from_u8, DType::U1),
+            DataType::Int16 => buffer!(Int16Type, from_i16, DType::I2),
+            DataType::UInt16 => buffer!(UInt16Type, from_u16, DType::U2),
+            DataType::Int32 => buffer!(Int32Type, from_i32, DType::I4),
+            DataType::UInt32 => buffer!(UInt32Type, from_u32, DType::U4),
+            DataType::Int64 => {
+                let values = array.as_primitive::<Int64Type>().values();
+                Self::from_wide_integers(values.iter().map(|&v| v as i128))?
+            }
+            DataType::UInt64 => {
+                let values = array.as_primitive::<UInt64Type>().values();
+                Self::from_wide_integers(values.iter().map(|&v| v as i128))?
+            }
+            _ => return None,
+        };
+        Some(typed)
+    }
+
+    /// Narrow 64-bit integers to `i4`/`u4` when they fit, otherwise encode
+    /// them as `f8` if that is exact.
+    fn from_wide_integers(values: impl Iterator<Item = i128> + Clone) -> Option<Self> {
+        const MAX_EXACT_F64: i128 = 1 << 53;
+        let min = values.clone().min().unwrap_or(0);
+        let max = values.clone().max().unwrap_or(0);
+        if min >= i32::MIN as i128 && max <= i32::MAX as i128 {
+            Some(Self::from_i32(
+                &values.map(|v| v as i32).collect::<Vec<_>>(),
+            ))
+        } else if min >= 0 && max <= u32::MAX as i128 {
+            Some(Self::from_u32(
+                &values.map(|v| v as u32).collect::<Vec<_>>(),
+            ))
+        } else if min >= -MAX_EXACT_F64 && max <= MAX_EXACT_F64 {
+            Some(Self::from_f64(
+                &values.map(|v| v as f64).collect::<Vec<_>>(),
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+/// Which numeric arrays an [`Encoded`] value replaces with typed arrays.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Scope {
+    /// A list of traces, all of whose numeric data is encoded
+    Traces,
+    /// A list of frames, whose `data` is a list of traces
+    Frames,
+    /// A serialized figure, with `data` and `frames`
+    #[cfg_attr(not(feature = "serve"), allow(dead_code))]
+    Figure,
+}
+
+impl Scope {
+    /// The scope of the value of the field `key`, `None` if it is serialized
+    /// unchanged.
+    fn field(self, key: &str) -> Option<Scope> {
+        match self {
+            Scope::Traces => (!INFO_ARRAY_KEYS.contains(&key)).then_some(Scope::Traces),
+            Scope::Frames => (key == "data").then_some(Scope::Traces),
+            Scope::Figure => match key {
+                "data" => Some(Scope::Traces),
+                "frames" => Some(Scope::Frames),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Serializes a value with the numeric arrays of its traces written as typed
+/// arrays.
+///
+/// The arrays are encoded while serializing: only the elements of a sequence
+/// that may become a typed array are buffered, everything else is passed
+/// straight through to the wrapped serializer.
+pub(crate) struct Encoded<'a, T: ?Sized> {
+    value: &'a T,
+    scope: Scope,
+}
+
+impl<'a, T: ?Sized> Encoded<'a, T> {
+    /// Encode a list of traces.
+    pub(crate) fn traces(value: &'a T) -> Self {
+        Self {
+            value,
+            scope: Scope::Traces,
+        }
+    }
+
+    /// Encode the traces of a list of frames.
+    pub(crate) fn frames(value: &'a T) -> Self {
+        Self {
+            value,
+            scope: Scope::Frames,
+        }
+    }
+
+    /// Encode the traces and frames of a figure.
+    #[cfg_attr(not(feature = "serve"), allow(dead_code))]
+    pub(crate) fn figure(value: &'a T) -> Self {
+        Self {
+            value,
+            scope: Scope::Figure,
+        }
+    }
+
+    fn new(value: &'a T, scope: Scope) -> Self {
+        Self { value, scope }
+    }
+}
+
+impl<T: Serialize + ?Sized> Serialize for Encoded<'_, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.value.serialize(Encoder {
+            serializer,
+            scope: self.scope,
+        })
+    }
+}
+
+/// A serializer writing numeric arrays to `serializer` as typed arrays.
+struct Encoder<S> {
+    serializer: S,
+    scope: Scope,
+}
+
+macro_rules! forward {
+    ($($method:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            fn $method(self, $($arg: $ty),*) -> Result<S::Ok, S::Error> {
+                self.serializer.$method($($arg),*)
+            }
+        )*
+    };
+}
+
+impl<S: Serializer> Serializer for Encoder<S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+    type SerializeSeq = SeqEncoder<S>;
+    type SerializeTuple = Compound<S::SerializeTuple>;
+    type SerializeTupleStruct = Compound<S::SerializeTupleStruct>;
+    type SerializeTupleVariant = Compound<S::SerializeTupleVariant>;
+    type SerializeMap = MapEncoder<S::SerializeMap>;
+    type SerializeStruct = Compound<S::SerializeStruct>;
+    type SerializeStructVariant = Compound<S::SerializeStructVariant>;
+
+    forward! {
+        serialize_bool(v: bool);
+        serialize_i8(v: i8);
+        serialize_i16(v: i16);
+        serialize_i32(v: i32);
+        serialize_i64(v: i64);
+        serialize_i128(v: i128);
+        serialize_u8(v: u8);
+        serialize_u16(v: u16);
+        serialize_u32(v: u32);
+        serialize_u64(v: u64);
+        serialize_u128(v: u128);
+        serialize_f32(v: f32);
+        serialize_f64(v: f64);
+        serialize_char(v: char);
+        serialize_str(v: &str);
+        serialize_bytes(v: &[u8]);
+        serialize_none();
+        serialize_unit();
+        serialize_unit_struct(name: &'static str);
+        serialize_unit_variant(name: &'static str, index: u32, variant: &'static str);
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<S::Ok, S::Error> {
+        self.serializer
+            .serialize_some(&Encoded::new(value, self.scope))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        self.serializer
+            .serialize_newtype_struct(name, &Encoded::new(value, self.scope))
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        self.serializer.serialize_newtype_variant(
+            name,
+            index,
+            variant,
+            &Encoded::new(value, self.scope),
+        )
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqEncoder<S>, S::Error> {
+        let mut seq = SeqEncoder {
+            scope: self.scope,
+            serializer: Some(self.serializer),
+            len,
+            buffer: NumberBuffer::default(),
+            seq: None,
+        };
+        if self.scope != Scope::Traces {
+            seq.stream()?;
+        }
+        Ok(seq)
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, S::Error> {
+        Ok(Compound {
+            inner: self.serializer.serialize_tuple(len)?,
+            scope: self.scope,
+        })
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, S::Error> {
+        Ok(Compound {
+            inner: self.serializer.serialize_tuple_struct(name, len)?,
+            scope: self.scope,
+        })
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, S::Error> {
+        Ok(Compound {
+            inner: self
+                .serializer
+                .serialize_tuple_variant(name, index, variant, len)?,
+            scope: self.scope,
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, S::Error> {
+        Ok(MapEncoder {
+            inner: self.serializer.serialize_map(len)?,
+            scope: self.scope,
+            value_scope: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, S::Error> {
+        Ok(Compound {
+            inner: self.serializer.serialize_struct(name, len)?,
+            scope: self.scope,
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, S::Error> {
+        Ok(Compound {
+            inner: self
+                .serializer
+                .serialize_struct_variant(name, index, variant, len)?,
+            scope: self.scope,
+        })
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.serializer.is_human_readable()
+    }
+}
+
+/// A sequence that is buffered while its elements are numbers or rows of
+/// numbers, and written as a typed array if all of them are.
+struct SeqEncoder<S: Serializer> {
+    scope: Scope,
+    /// The serializer, until the sequence is known not to be a typed array
+    serializer: Option<S>,
+    len: Option<usize>,
+    buffer: NumberBuffer,
+    seq: Option<S::SerializeSeq>,
+}
+
+impl<S: Serializer> SeqEncoder<S> {
+    /// Start writing the sequence element by element, beginning with the
+    /// buffered elements.
+    fn stream(&mut self) -> Result<&mut S::SerializeSeq, S::Error> {
+        if let Some(serializer) = self.serializer.take() {
+            let mut seq = serializer.serialize_seq(self.len)?;
+            let buffer = std::mem::take(&mut self.buffer);
+            for index in 0..buffer.len {
+                seq.serialize_element(&Encoded::new(&buffer.element(index), self.scope))?;
+            }
+            self.seq = Some(seq);
+        }
+        self.seq
+            .as_mut()
+            .ok_or_else(|| ser::Error::custom("sequence already ended"))
+    }
+}
+
+impl<S: Serializer> SerializeSeq for SeqEncoder<S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), S::Error> {
+        if self.seq.is_none() && self.buffer.push(value).is_ok() {
+            return Ok(());
+        }
+        let scope = self.scope;
+        self.stream()?
+            .serialize_element(&Encoded::new(value, scope))
+    }
+
+    fn end(mut self) -> Result<S::Ok, S::Error> {
+        if self.buffer.len >= MIN_ENCODED_LEN {
+            if let Some(serializer) = self.serializer.take() {
+                return self.buffer.typed_array().serialize(serializer);
+            }
+        }
+        self.stream()?;
+        match self.seq {
+            Some(seq) => seq.end(),
+            None => Err(ser::Error::custom("sequence already ended")),
+        }
+    }
+}
+
+/// The numbers of a sequence that may become a typed array: a vector, or a
+/// matrix stored row by row.
+#[derive(Debug, Default)]
+struct NumberBuffer {
+    numbers: Numbers,
+    /// The number of elements, numbers or rows
+    len: usize,
+    /// The length of the rows of a matrix
+    columns: Option<usize>,
+}
+
+impl NumberBuffer {
+    /// Append `value`, a number or a row of numbers of the same length as the
+    /// previous rows. Leaves the buffer unchanged if it is anything else.
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Rejected> {
+        let start = self.numbers.len();
+        let row = value.serialize(NumberSink {
+            numbers: &mut self.numbers,
+            in_row: false,
+        });
+        let accepted = match (row, self.columns) {
+            (Ok(None), None) => true,
+            (Ok(Some(columns)), None) if self.len == 0 && columns > 0 => {
+                self.columns = Some(columns);
+                true
+            }
+            (Ok(Some(columns)), Some(expected)) => columns == expected,
+            _ => false,
+        };
+        if !accepted {
+            self.numbers.truncate(start);
+            return Err(Rejected);
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    /// The element at `index`, as it was pushed.
+    fn element(&self, index: usize) -> Buffered<'_> {
+        let columns = self.columns.unwrap_or(1);
+        Buffered {
+            numbers: &self.numbers,
+            range: index * columns..(index + 1) * columns,
+            row: self.columns.is_some(),
+        }
+    }
+
+    fn typed_array(&self) -> TypedArray {
+        let array = match &self.numbers {
+            Numbers::Integers(values) => TypedArray::from_integers(values),
+            Numbers::Floats {
+                values,
+                single: true,
+            } => {
+                let bytes: Vec<u8> = values
+                    .iter()
+                    .flat_map(|&v| (v as f32).to_le_bytes())
+                    .collect();
+                TypedArray::from_le_bytes(DType::F4, &bytes)
+            }
+            Numbers::Floats { values, .. } => TypedArray::from_f64(values),
+        };
+        match self.columns {
+            Some(columns) => array.shape(self.len, columns),
+            None => array,
+        }
+    }
+}
+
+/// Buffered numbers, kept as integers until the first float.
+#[derive(Debug)]
+enum Numbers {
+    Integers(Vec<i64>),
+    Floats {
+        values: Vec<f64>,
+        /// Whether all values were `f32`
+        single: bool,
+    },
+}
+
+impl Default for Numbers {
+    fn default() -> Self {
+        Numbers::Integers(Vec::new())
+    }
+}
+
+impl Numbers {
+    fn len(&self) -> usize {
+        match self {
+            Numbers::Integers(values) => values.len(),
+            Numbers::Floats { values, .. } => values.len(),
+        }
+    }
+
+    fn truncate(&mut self, len: usize) {
+        match self {
+            Numbers::Integers(values) => values.truncate(len),
+            Numbers::Floats { values, .. } => values.truncate(len),
+        }
+    }
+
+    fn push_integer(&mut self, value: i64) {
+        match self {
+            Numbers::Integers(values) => values.push(value),
+            Numbers::Floats { values, .. } => values.push(value as f64),
+        }
+    }
+
+    /// Append a finite float, rejecting `NaN` and infinities, which JSON
+    /// writes as `null`.
+    fn push_float(&mut self, value: f64, is_f32: bool) -> Result<(), Rejected> {
+        if !value.is_finite() {
+            return Err(Rejected);
+        }
+        if let Numbers::Integers(integers) = self {
+            let values = integers.iter().map(|&v| v as f64).collect();
+            *self = Numbers::Floats {
+                values,
+                single: is_f32,
+            };
+        }
+        if let Numbers::Floats { values, single } = self {
+            values.push(value);
+            *single &= is_f32;
+        }
+        Ok(())
+    }
+}
+
+/// A buffered number, or row of numbers, serialized again when its sequence
+/// turns out not to be a typed array.
+struct Buffered<'a> {
+    numbers: &'a Numbers,
+    range: std::ops::Range<usize>,
+    row: bool,
+}
+
+impl Serialize for Buffered<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        fn number<S: Serializer>(
+            numbers: &Numbers,
+            index: usize,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match numbers {
+                Numbers::Integers(values) => serializer.serialize_i64(values[index]),
+                Numbers::Floats {
+                    values,
+                    single: true,
+                } => serializer.serialize_f32(values[index] as f32),
+                Numbers::Floats { values, .. } => serializer.serialize_f64(values[index]),
+            }
+        }
+
+        if !self.row {
+            return number(self.numbers, self.range.start, serializer);
+        }
+        let mut seq = serializer.serialize_seq(Some(self.range.len()))?;
+        for index in self.range.clone() {
+            seq.serialize_element(&Buffered {
+                numbers: self.numbers,
+                range: index..index + 1,
+                row: false,
+            })?;
+        }
+        seq.end()
+    }
+}
+
+/// Tuples and structs, whose elements and fields are encoded in turn.
+struct Compound<C> {
+    inner: C,
+    scope: Scope,
+}
+
+macro_rules! compound_elements {
+    ($($trait:ident::$method:ident;)*) => {
+        $(
+            impl<C: $trait> $trait for Compound<C> {
+                type Ok = C::Ok;
+                type Error = C::Error;
+
+                fn $method<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), C::Error> {
+                    self.inner.$method(&Encoded::new(value, self.scope))
+                }
+
+                fn end(self) -> Result<C::Ok, C::Error> {
+                    self.inner.end()
+                }
+            }
+        )*
+    };
+}
+
+compound_elements! {
+    SerializeTuple::serialize_element;
+    SerializeTupleStruct::serialize_field;
+    SerializeTupleVariant::serialize_field;
+}
+
+macro_rules! compound_fields {
+    ($($trait:ident;)*) => {
+        $(
+            impl<C: $trait> $trait for Compound<C> {
+                type Ok = C::Ok;
+                type Error = C::Error;
+
+                fn serialize_field<T: Serialize + ?Sized>(
+                    &mut self,
+                    key: &'static str,
+                    value: &T,
+                ) -> Result<(), C::Error> {
+                    match self.scope.field(key) {
+                        Some(scope) => self.inner.serialize_field(key, &Encoded::new(value, scope)),
+                        None => self.inner.serialize_field(key, value),
+                    }
+                }
+
+                fn skip_field(&mut self, key: &'static str) -> Result<(), C::Error> {
+                    self.inner.skip_field(key)
+                }
+
+                fn end(self) -> Result<C::Ok, C::Error> {
+                    self.inner.end()
+                }
+            }
+        )*
+    };
+}
+
+compound_fields! {
+    SerializeStruct;
+    SerializeStructVariant;
+}
+
+/// Maps, whose values are encoded depending on their key.
+struct MapEncoder<M> {
+    inner: M,
+    scope: Scope,
+    /// The scope of the value of the last key
+    value_scope: Option<Scope>,
+}
+
+impl<M: SerializeMap> SerializeMap for MapEncoder<M> {
+    type Ok = M::Ok;
+    type Error = M::Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), M::Error> {
+        let name = key.serialize(KeyName).unwrap_or_default();
+        self.value_scope = self.scope.field(&name);
+        self.inner.serialize_key(key)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), M::Error> {
+        match self.value_scope {
+            Some(scope) => self.inner.serialize_value(&Encoded::new(value, scope)),
+            None => self.inner.serialize_value(value),
+        }
+    }
+
+    fn end(self) -> Result<M::Ok, M::Error> {
+        self.inner.end()
+    }
+}
+
+/// The error of the probing serializers, for values they do not accept.
+#[derive(Debug)]
+struct Rejected;
+
+impl std::fmt::Display for Rejected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("rejected")
+    }
+}
+
+impl std::error::Error for Rejected {}
+
+impl ser::Error for Rejected {
+    fn custom<T: std::fmt::Display>(_msg: T) -> Self {
+        Rejected
+    }
+}
+
+macro_rules! reject {
+    ($($method:ident($($ty:ty),*);)*) => {
+        $(
+            fn $method(self, $(_: $ty),*) -> Result<Self::Ok, Rejected> {
+                Err(Rejected)
+            }
+        )*
+    };
+}
+
+macro_rules! reject_compound {
+    () => {
+        type SerializeTuple = Impossible<Self::Ok, Rejected>;
+        type SerializeTupleStruct = Impossible<Self::Ok, Rejected>;
+        type SerializeTupleVariant = Impossible<Self::Ok, Rejected>;
+        type SerializeMap = Impossible<Self::Ok, Rejected>;
+        type SerializeStruct = Impossible<Self::Ok, Rejected>;
+        type SerializeStructVariant = Impossible<Self::Ok, Rejected>;
+
+        reject! {
+            serialize_bool(bool);
+            serialize_char(char);
+            serialize_bytes(&[u8]);
+            serialize_unit_struct(&'static str);
+            serialize_unit_variant(&'static str, u32, &'static str);
+        }
+
+        fn serialize_newtype_variant<T: Serialize + ?Sized>(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: &T,
+        ) -> Result<Self::Ok, Rejected> {
+            Err(Rejected)
+        }
+
+        fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Rejected> {
+            Err(Rejected)
+        }
+
+        fn serialize_tuple_struct(
+            self,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeTupleStruct, Rejected> {
+            Err(Rejected)
+        }
+
+        fn serialize_tuple_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeTupleVariant, Rejected> {
+            Err(Rejected)
+        }
+
+        fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Rejected> {
+            Err(Rejected)
+        }
+
+        fn serialize_struct(
+            self,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeStruct, Rejected> {
+            Err(Rejected)
+        }
+
+        fn serialize_struct_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeStructVariant, Rejected> {
+            Err(Rejected)
+        }
+    };
+}
+
+/// Appends numbers, and sequences of numbers outside of a row, to a
+/// [`NumberBuffer`], rejecting everything else. Returns the length of a row,
+/// `None` for a number.
+struct NumberSink<'a> {
+    numbers: &'a mut Numbers,
+    in_row: bool,
+}
+
+macro_rules! integer {
+    ($($method:ident($ty:ty);)*) => {
+        $(
+            fn $method(self, v: $ty) -> Result<Option<usize>, Rejected> {
+                self.numbers.push_integer(v.into());
+                Ok(None)
+            }
+        )*
+    };
+}
+
+impl<'a> Serializer for NumberSink<'a> {
+    type Ok = Option<usize>;
+    type Error = Rejected;
+    type SerializeSeq = RowSink<'a>;
+
+    reject_compound!();
+
+    reject! {
+        serialize_str(&str);
+        serialize_none();
+        serialize_unit();
+    }
+
+    integer! {
+        serialize_i8(i8);
+        serialize_i16(i16);
+        serialize_i32(i32);
+        serialize_i64(i64);
+        serialize_u8(u8);
+        serialize_u16(u16);
+        serialize_u32(u32);
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Option<usize>, Rejected> {
+        match i64::try_from(v) {
+            Ok(v) => self.numbers.push_integer(v),
+            Err(_) => self.numbers.push_float(v as f64, false)?,
+        }
+        Ok(None)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Option<usize>, Rejected> {
+        self.numbers.push_float(v.into(), true)?;
+        Ok(None)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Option<usize>, Rejected> {
+        self.numbers.push_float(v, false)?;
+        Ok(None)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Option<usize>, Rejected> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Option<usize>, Rejected> {
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<RowSink<'a>, Rejected> {
+        if self.in_row {
+            return Err(Rejected);
+        }
+        Ok(RowSink {
+            numbers: self.numbers,
+            len: 0,
+        })
+    }
+}
+
+/// A row of a matrix, appended by [`NumberSink`].
+struct RowSink<'a> {
+    numbers: &'a mut Numbers,
+    len: usize,
+}
+
+impl SerializeSeq for RowSink<'_> {
+    type Ok = Option<usize>;
+    type Error = Rejected;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Rejected> {
+        value.serialize(NumberSink {
+            numbers: self.numbers,
+            in_row: true,
+        })?;
+        self.len += 1;
+        Ok(())
+    }
+
+    fn end(self) -> Result<Option<usize>, Rejected> {
+        Ok(Some(self.len))
+    }
+}
+
+/// Serializes string map keys to their name, rejecting everything else.
+struct KeyName;
+
+impl Serializer for KeyName {
+    type Ok = String;
+    type Error = Rejected;
+    type SerializeSeq = Impossible<String, Rejected>;
+
+    reject_compound!();
+
+    reject! {
+        serialize_i8(i8);
+        serialize_i16(i16);
+        serialize_i32(i32);
+        serialize_i64(i64);
+        serialize_u8(u8);
+        serialize_u16(u16);
+        serialize_u32(u32);
+        serialize_u64(u64);
+        serialize_f32(f32);
+        serialize_f64(f64);
+    }
+
+    fn serialize_none(self) -> Result<String, Rejected> {
+        Err(Rejected)
+    }
+
+    fn serialize_unit(self) -> Result<String, Rejected> {
+        Err(Rejected)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _: &T) -> Result<String, Rejected> {
+        Err(Rejected)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<String, Rejected> {
+        value.serialize(self)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<String, Rejected> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Rejected> {
+        Err(Rejected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, to_value};
+
+    use super::*;
+
+    fn decode(array: &TypedArray) -> Vec<u8> {
+        general_purpose::STANDARD.decode(&array.bdata).unwrap()
+    }
+
+    #[test]
+    fn serialize_typed_array() {
+        let array = TypedArray::from_i32(&[1, -1]).shape(1, 2);
+        let expected = json!({"dtype": "i4", "bdata": "AQAAAP////8=", "shape": "1,2"});
+        assert_eq!(to_value(array).unwrap(), expected);
+    }
+
+    #[test]
+    fn from_value_picks_smallest_dtype() {
+        let u1 = TypedArray::from_value(&json!([0, 1, 255])).unwrap();
+        assert_eq!(u1.dtype, DType::U1);
+        assert_eq!(decode(&u1), vec![0, 1, 255]);
+
+        let i2 = TypedArray::from_value(&json!([-1, 300])).unwrap();
+        assert_eq!(i2.dtype, DType::I2);
+
+        let u4 = TypedArray::from_value(&json!([70000])).unwrap();
+        assert_eq!(u4.dtype, DType::U4);
+
+        let f8 = TypedArray::from_value(&json!([1, 2.5])).unwrap();
+        assert_eq!(f8.dtype, DType::F8);
+        assert_eq!(
+            decode(&f8),
+            [1.0f64.to_le_bytes(), 2.5f64.to_le_bytes()].concat()
+        );
+
+        let large = TypedArray::from_value(&json!([-1, 1_i64 << 40])).unwrap();
+        assert_eq!(large.dtype, DType::F8);
+    }
+
+    #[test]
+    fn from_value_matrix() {
+        let matrix = TypedArray::from_value(&json!([[1, 2, 3], [4, 5, 6]])).unwrap();
+        assert_eq!(matrix.shape.as_deref(), Some("2,3"));
+        assert_eq!(decode(&matrix), vec![1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn from_value_rejects_non_numeric() {
+        assert!(TypedArray::from_value(&json!([])).is_none());
+        assert!(TypedArray::from_value(&json!(["a", 1])).is_none());
+        assert!(TypedArray::from_value(&json!([1, null])).is_none());
+        assert!(TypedArray::from_value(&json!([[1, 2], [3]])).is_none());
+        assert!(TypedArray::from_value(&json!(1)).is_none());
+    }
+
+    #[test]
+    fn encode_traces_skips_short_and_info_arrays() {
+        let x: Vec<f64> = (0..20).map(f64::from).collect();
+        let traces = json!([{
+            "type": "scatter",
+            "x": x,
+            "y": [1, 2, 3],
+            "text": vec!["a"; 20],
+            "domain": {"x": x},
+            "marker": {"size": vec![4; 20]}
+        }]);
+        let traces = to_value(Encoded::traces(&traces)).unwrap();
+
+        let trace = &traces[0];
+        assert_eq!(trace["x"], to_value(TypedArray::from_f64(&x)).unwrap());
+        assert_eq!(trace["y"], json!([1, 2, 3]));
+        assert_eq!(trace["text"], json!(vec!["a"; 20]));
+        assert_eq!(trace["domain"]["x"], json!(x));
+        assert_eq!(trace["marker"]["size"]["dtype"], json!("u1"));
+    }
+
+    #[derive(Serialize)]
+    struct Trace {
+        x: Vec<f64>,
+        y: Vec<Option<i32>>,
+        z: Vec<Vec<u8>>,
+        range: Vec<i32>,
+    }
+
+    #[test]
+    fn encode_while_serializing() {
+        let mut y: Vec<Option<i32>> = (0..20).map(Some).collect();
+        let trace = Trace {
+            x: (0..20).map(f64::from).collect(),
+            y: y.clone(),
+            z: vec![(0..20).collect(); 2],
+            range: (0..20).collect(),
+        };
+        let encoded = to_value(Encoded::traces(&[&trace])).unwrap();
+        assert_eq!(encoded[0]["x"]["dtype"], json!("f8"));
+        assert_eq!(encoded[0]["y"]["dtype"], json!("u1"));
+        // A short matrix is kept, but its long rows are encoded
+        assert_eq!(encoded[0]["z"][1]["dtype"], json!("u1"));
+        assert_eq!(encoded[0]["range"], json!(trace.range));
+
+        // Missing values and NaN are written as JSON
+        y[3] = None;
+        let trace = Trace {
+            x: vec![f64::NAN; 20],
+            y,
+            ..trace
+        };
+        let encoded = to_value(Encoded::traces(&[&trace])).unwrap();
+        let plain = to_value([&trace]).unwrap();
+        assert_eq!(encoded[0]["x"], plain[0]["x"]);
+        assert_eq!(encoded[0]["y"], plain[0]["y"]);
+    }
+
+    #[test]
+    fn encode_f32_and_replay_rejected_sequences() {
+        let values: Vec<f32> = (0..20).map(|v| v as f32 / 10.0).collect();
+        let encoded = to_value(Encoded::traces(&values)).unwrap();
+        assert_eq!(encoded, to_value(TypedArray::from_f32(&values)).unwrap());
+
+        // Buffered numbers are written as they were serialized
+        let mut gaps: Vec<Option<f32>> = values.iter().copied().map(Some).collect();
+        gaps[19] = None;
+        let encoded = serde_json::to_string(&Encoded::traces(&gaps)).unwrap();
+        assert_eq!(encoded, serde_json::to_string(&gaps).unwrap());
+
+        // Rows of different lengths are written one by one
+        let rows = vec![values.clone(), values[..2].to_vec()];
+        let encoded = to_value(Encoded::traces(&rows)).unwrap();
+        assert_eq!(encoded[0]["dtype"], json!("f4"));
+        assert_eq!(encoded[1], to_value(&rows[1]).unwrap());
+    }
+
+    #[test]
+    fn encode_frames_data_only() {
+        let x: Vec<u8> = (0..20).collect();
+        let frames = json!([{
+            "name": "first",
+            "data": [{"x": x}],
+            "layout": {"xaxis": {"tickvals": x}}
+        }]);
+        let encoded = to_value(Encoded::frames(&frames)).unwrap();
+        assert_eq!(encoded[0]["data"][0]["x"]["dtype"], json!("u1"));
+        assert_eq!(encoded[0]["layout"], frames[0]["layout"]);
+        assert_eq!(encoded[0]["name"], json!("first"));
+    }
+}
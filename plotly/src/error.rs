@@ -0,0 +1,186 @@
+//! Error type returned by the fallible `Plot` output and display methods.
+
+use std::{fmt, io};
+
+/// Errors that can occur while writing, rendering, displaying or exporting a
+/// [`Plot`](crate::Plot).
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// Creating or writing an output file failed, e.g. because the parent
+    /// directory does not exist.
+    Io(io::Error),
+    /// The plot could not be rendered through its HTML template.
+    Render(RenderError),
+    /// The default application for HTML files could not be launched.
+    Launch(io::Error),
+    /// The plot could not be exported to a static image.
+    #[cfg(feature = "plotly_static")]
+    Export(plotly_static::ExportError),
+    /// Points could not be streamed into the traces of a plot, e.g. because a
+    /// trace index is out of range or a value failed to serialize.
+    Stream(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "failed to write plot output: {e}"),
+            Error::Render(e) => write!(f, "failed to render plot: {e}"),
+            Error::Launch(e) => write!(f, "failed to open the default HTML application: {e}"),
+            #[cfg(feature = "plotly_static")]
+            Error::Export(e) => write!(f, "failed to export plot: {e}"),
+            Error::Stream(msg) => write!(f, "failed to stream points: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) | Error::Launch(e) => Some(e),
+            Error::Render(e) => Some(e),
+            #[cfg(feature = "plotly_static")]
+            Error::Export(e) => Some(e),
+            Error::Stream(_) => None,
+        }
+    }
+}
+
+/// A failure of the HTML template engine, e.g. because a value written into
+/// the page failed to format.
+#[derive(Debug)]
+pub struct RenderError(askama::Error);
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for RenderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        std::error::Error::source(&self.0)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+#[cfg(feature = "plotly_static")]
+impl From<plotly_static::ExportError> for Error {
+    fn from(e: plotly_static::ExportError) -> Self {
+        Error::Export(e)
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Io(e) => e,
+            e => io::Error::other(e),
+        }
+    }
+}
+
+impl Error {
+    /// Classify an error of askama's `Template::write_into`, which reports a
+    /// failure to render the template as an `io::Error` wrapping
+    /// `fmt::Error`.
+    pub(crate) fn from_template_write(e: io::Error) -> Self {
+        if e.get_ref().is_some_and(|inner| inner.is::<fmt::Error>()) {
+            Error::Render(RenderError(askama::Error::Fmt))
+        } else {
+            Error::Io(e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error as _;
+
+    use super::*;
+
+    use askama::Template;
+
+    struct FailingWriter;
+
+    impl io::Write for FailingWriter {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    struct FailingDisplay;
+
+    impl fmt::Display for FailingDisplay {
+        fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
+            Err(fmt::Error)
+        }
+    }
+
+    #[derive(Template)]
+    #[template(source = "{{ value }}", ext = "txt")]
+    struct ValueTemplate<T: fmt::Display> {
+        value: T,
+    }
+
+    #[test]
+    fn template_write_keeps_io_error() {
+        let result = ValueTemplate { value: "data" }.write_into(&mut FailingWriter);
+        match result.map_err(Error::from_template_write) {
+            Err(Error::Io(e)) => assert_eq!(e.kind(), io::ErrorKind::BrokenPipe),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn template_write_reports_render_error() {
+        let result = ValueTemplate {
+            value: FailingDisplay,
+        }
+        .write_into(&mut Vec::new());
+        assert!(matches!(
+            result.map_err(Error::from_template_write),
+            Err(Error::Render(_))
+        ));
+    }
+
+    #[test]
+    fn convert_to_io_error() {
+        let e: io::Error = Error::Io(io::Error::new(io::ErrorKind::NotFound, "missing")).into();
+        assert_eq!(e.kind(), io::ErrorKind::NotFound);
+
+        let e: io::Error = Error::Stream("no trace 3".into()).into();
+        assert_eq!(e.kind(), io::ErrorKind::Other);
+        assert_eq!(e.to_string(), "failed to stream points: no trace 3");
+    }
+
+    #[test]
+    fn error_source() {
+        let e = Error::Launch(io::Error::new(io::ErrorKind::NotFound, "xdg-open"));
+        assert!(e.source().is_some());
+        assert!(Error::Stream("no trace 3".into()).source().is_none());
+    }
+
+    #[cfg(feature = "plotly_static")]
+    #[test]
+    fn wrap_export_error() {
+        use plotly_static::ExportError;
+
+        let e = Error::from(ExportError::Timeout("waiting for the page".into()));
+        assert_eq!(
+            e.to_string(),
+            "failed to export plot: timed out waiting for the page"
+        );
+        assert!(e.source().is_some_and(|source| source.is::<ExportError>()));
+    }
+}
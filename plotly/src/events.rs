@@ -0,0 +1,324 @@
+//! Payloads of the plotly.js [events](https://plotly.com/javascript/plotlyjs-events/)
+//! emitted by a plot `<div>`.
+//!
+//! The structs deserialize from the event data plotly.js passes to its
+//! listeners. Fields use `camelCase` names to match the plotly.js API, and
+//! fields missing from an event, e.g. `z` in a 2D plot, are `None`.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// A data value carried by an event: a number, a string for category and
+/// date axes, or any other JSON value, e.g. the `[lon, lat]` pairs of map
+/// selections.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum DataValue {
+    Number(f64),
+    String(String),
+    Other(Value),
+}
+
+impl DataValue {
+    /// The value as a number, if it is one.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            DataValue::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    /// The value as a string, if it is one.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            DataValue::String(string) => Some(string),
+            _ => None,
+        }
+    }
+}
+
+/// A single point of a click, hover or selection event, mirroring plotly.js'
+/// `event.points[i]`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Point {
+    /// The zero-based index of the trace the point belongs to.
+    pub curve_number: usize,
+    /// The index of the point in the trace data.
+    pub point_number: Option<usize>,
+    /// The indices of the points aggregated in this point, e.g. for
+    /// histograms.
+    pub point_numbers: Option<Vec<usize>>,
+    /// The index of the point in the trace data, for traces with
+    /// multi-dimensional data such as heatmaps.
+    pub point_index: Option<usize>,
+    pub x: Option<DataValue>,
+    pub y: Option<DataValue>,
+    pub z: Option<DataValue>,
+    pub lat: Option<f64>,
+    pub lon: Option<f64>,
+    /// The label of the point in pie, sunburst, treemap and funnel area
+    /// traces.
+    pub label: Option<DataValue>,
+    /// The value of the point in pie, sunburst, treemap and funnel area
+    /// traces.
+    pub value: Option<DataValue>,
+    /// The location of the point in choropleth traces.
+    pub location: Option<String>,
+    pub id: Option<DataValue>,
+    pub text: Option<DataValue>,
+    #[serde(rename = "hovertext")]
+    pub hover_text: Option<DataValue>,
+    #[serde(rename = "customdata")]
+    pub custom_data: Option<Value>,
+}
+
+/// The payload of `plotly_click`, `plotly_hover` and `plotly_unhover`
+/// events.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PointsEvent {
+    #[serde(default)]
+    pub points: Vec<Point>,
+}
+
+/// The payload of a `plotly_click` event.
+pub type ClickEvent = PointsEvent;
+/// A point of a `plotly_click` event.
+pub type ClickPoint = Point;
+
+/// The payload of `plotly_selected` and `plotly_selecting` events.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelectionEvent {
+    /// The selected points.
+    #[serde(default)]
+    pub points: Vec<Point>,
+    /// The extent of a box selection by axis id, e.g. `"x"` and `"y2"`, as
+    /// `[start, end]`. Map and geo subplots give `[[lon, lat], [lon, lat]]`.
+    pub range: Option<BTreeMap<String, Vec<DataValue>>>,
+    /// The vertices of a lasso selection by axis id.
+    pub lasso_points: Option<BTreeMap<String, Vec<DataValue>>>,
+}
+
+/// The payload of a `plotly_relayout` event: the layout attributes that
+/// changed, keyed by their attribute path, e.g. `"xaxis.range[0]"` or
+/// `"xaxis.autorange"`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct RelayoutEvent(pub Map<String, Value>);
+
+impl RelayoutEvent {
+    /// The new value of the layout attribute at `path`.
+    pub fn get(&self, path: &str) -> Option<&Value> {
+        self.0.get(path)
+    }
+
+    /// The new range of the axis `axis`, e.g. `"xaxis"` or `"yaxis2"`, after
+    /// a zoom or pan, given either as `"xaxis.range[0]"`/`"xaxis.range[1]"`
+    /// or as `"xaxis.range"`.
+    pub fn axis_range(&self, axis: &str) -> Option<[DataValue; 2]> {
+        let bound = |value: &Value| serde_json::from_value(value.clone()).ok();
+        if let (Some(start), Some(end)) = (
+            self.get(&format!("{axis}.range[0]")),
+            self.get(&format!("{axis}.range[1]")),
+        ) {
+            return Some([bound(start)?, bound(end)?]);
+        }
+        match self.get(&format!("{axis}.range"))?.as_array()?.as_slice() {
+            [start, end] => Some([bound(start)?, bound(end)?]),
+            _ => None,
+        }
+    }
+
+    /// Whether the axis `axis` was reset to autorange, e.g. by a double
+    /// click.
+    pub fn is_autorange(&self, axis: &str) -> bool {
+        self.get(&format!("{axis}.autorange")) == Some(&Value::Bool(true))
+    }
+}
+
+/// The payload of a `plotly_restyle` event.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(from = "(Map<String, Value>, Vec<usize>)")]
+pub struct RestyleEvent {
+    /// The trace attributes that changed, keyed by their attribute path.
+    /// Array values hold one element per entry of `trace_indices`.
+    pub update: Map<String, Value>,
+    /// The indices of the restyled traces.
+    pub trace_indices: Vec<usize>,
+}
+
+impl From<(Map<String, Value>, Vec<usize>)> for RestyleEvent {
+    fn from((update, trace_indices): (Map<String, Value>, Vec<usize>)) -> Self {
+        Self {
+            update,
+            trace_indices,
+        }
+    }
+}
+
+/// The payload of `plotly_legendclick` and `plotly_legenddoubleclick`
+/// events.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LegendClickEvent {
+    /// The index of the trace whose legend item was clicked.
+    pub curve_number: usize,
+    /// The index of the trace in the expanded list of traces, which differs
+    /// from `curve_number` for traces with several legend items.
+    pub expanded_index: Option<usize>,
+}
+
+/// The slider step activated in a `plotly_sliderchange` event.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct SliderStep {
+    /// The index of the step in the slider.
+    #[serde(rename = "_index")]
+    pub index: usize,
+    pub label: Option<String>,
+    pub value: Option<String>,
+    pub method: Option<String>,
+}
+
+/// The payload of a `plotly_sliderchange` event.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SliderChangeEvent {
+    pub step: SliderStep,
+    /// Whether the change was made by the user, rather than by an animation.
+    #[serde(default)]
+    pub interaction: bool,
+    /// The index of the previously active step.
+    pub previous_active: Option<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{from_value, json};
+
+    use super::*;
+
+    #[test]
+    fn cleared_selection_does_not_deserialize() {
+        // plotly.js sends `plotly_selected` with `undefined` when a selection
+        // is cleared; the callbacks skip such payloads.
+        assert!(from_value::<SelectionEvent>(Value::Null).is_err());
+        assert!(from_value::<PointsEvent>(Value::Null).is_err());
+    }
+
+    #[test]
+    fn deserialize_points_event() {
+        let event: PointsEvent = from_value(json!({
+            "points": [{
+                "curveNumber": 1,
+                "pointNumber": 2,
+                "x": "2024-03-09",
+                "y": 4.5,
+                "text": "label",
+                "hovertext": 3,
+                "customdata": {"id": 7},
+                "data": {"type": "scatter"}
+            }],
+            "event": {}
+        }))
+        .unwrap();
+
+        let point = &event.points[0];
+        assert_eq!(point.curve_number, 1);
+        assert_eq!(point.point_number, Some(2));
+        assert_eq!(
+            point.x.as_ref().and_then(DataValue::as_str),
+            Some("2024-03-09")
+        );
+        assert_eq!(point.y.as_ref().and_then(DataValue::as_f64), Some(4.5));
+        assert_eq!(point.z, None);
+        assert_eq!(point.text, Some(DataValue::String("label".to_string())));
+        assert_eq!(point.hover_text, Some(DataValue::Number(3.0)));
+        assert_eq!(point.custom_data, Some(json!({"id": 7})));
+
+        let event: PointsEvent = from_value(json!({})).unwrap();
+        assert!(event.points.is_empty());
+    }
+
+    #[test]
+    fn deserialize_selection_event() {
+        let event: SelectionEvent = from_value(json!({
+            "points": [{"curveNumber": 0, "pointIndex": 3, "x": "a", "y": 1}],
+            "range": {"x": [0.5, 2.5], "y2": ["b", "d"]}
+        }))
+        .unwrap();
+        assert_eq!(event.points[0].point_index, Some(3));
+        let range = event.range.unwrap();
+        assert_eq!(
+            range["x"],
+            vec![DataValue::Number(0.5), DataValue::Number(2.5)]
+        );
+        assert_eq!(range["y2"][1], DataValue::String("d".to_string()));
+        assert!(event.lasso_points.is_none());
+
+        let event: SelectionEvent = from_value(json!({
+            "points": [],
+            "lassoPoints": {"mapbox": [[1.0, 2.0], [3.0, 4.0]]}
+        }))
+        .unwrap();
+        assert_eq!(
+            event.lasso_points.unwrap()["mapbox"][0],
+            DataValue::Other(json!([1.0, 2.0]))
+        );
+    }
+
+    #[test]
+    fn relayout_event_axis_range() {
+        let event: RelayoutEvent = from_value(json!({
+            "xaxis.range[0]": 1.5,
+            "xaxis.range[1]": 3,
+            "yaxis.range": ["a", "c"],
+            "yaxis2.autorange": true
+        }))
+        .unwrap();
+        assert_eq!(
+            event.axis_range("xaxis"),
+            Some([DataValue::Number(1.5), DataValue::Number(3.0)])
+        );
+        assert_eq!(
+            event.axis_range("yaxis"),
+            Some([
+                DataValue::String("a".to_string()),
+                DataValue::String("c".to_string())
+            ])
+        );
+        assert_eq!(event.axis_range("yaxis2"), None);
+        assert!(event.is_autorange("yaxis2"));
+        assert!(!event.is_autorange("xaxis"));
+    }
+
+    #[test]
+    fn deserialize_restyle_event() {
+        let event: RestyleEvent = from_value(json!([{"visible": [false]}, [2]])).unwrap();
+        assert_eq!(event.update["visible"], json!([false]));
+        assert_eq!(event.trace_indices, vec![2]);
+    }
+
+    #[test]
+    fn deserialize_legend_and_slider_events() {
+        let event: LegendClickEvent =
+            from_value(json!({"curveNumber": 1, "expandedIndex": 1, "node": {}})).unwrap();
+        assert_eq!(event.curve_number, 1);
+        assert_eq!(event.expanded_index, Some(1));
+
+        let event: SliderChangeEvent = from_value(json!({
+            "step": {"_index": 2, "label": "2020", "value": "2020", "method": "animate"},
+            "interaction": true,
+            "previousActive": 1,
+            "slider": {}
+        }))
+        .unwrap();
+        assert_eq!(event.step.index, 2);
+        assert_eq!(event.step.label.as_deref(), Some("2020"));
+        assert!(event.interaction);
+        assert_eq!(event.previous_active, Some(1));
+    }
+}
@@ -2,7 +2,10 @@
 pub mod sync {
     use std::path::Path;
 
-    use crate::{plot::Plot, ImageFormat};
+    use plotly_static::{AnimationFormat, ExportError, ExportJob, JobResult};
+    use serde_json::Value;
+
+    use crate::{plot::Plot, Error, ImageFormat};
 
     /// Extension methods for exporting plots using a synchronous exporter.
     pub trait ExporterSyncExt {
@@ -53,7 +56,7 @@ pub mod sync {
             width: usize,
             height: usize,
             scale: f64,
-        ) -> Result<(), Box<dyn std::error::Error>>;
+        ) -> Result<(), Error>;
 
         /// Convert the `Plot` to a static image and return the image as a
         /// `base64` string. Supported formats are [ImageFormat::JPEG],
@@ -98,7 +101,7 @@ pub mod sync {
             width: usize,
             height: usize,
             scale: f64,
-        ) -> Result<String, Box<dyn std::error::Error>>;
+        ) -> Result<String, Error>;
 
         /// Convert the `Plot` to SVG and return it as a String.
         ///
@@ -139,7 +142,99 @@ pub mod sync {
             width: usize,
             height: usize,
             scale: f64,
-        ) -> Result<String, Box<dyn std::error::Error>>;
+        ) -> Result<String, Error>;
+
+        /// Convert several `Plot`s to static images in one browser session.
+        ///
+        /// Each job is a `(plot, filename, format, width, height, scale)`
+        /// tuple with the same meaning as the arguments of `write_image`. The
+        /// export page is loaded once and all plots are rendered into it,
+        /// which is faster than calling `write_image` for each of them.
+        ///
+        /// A failing job does not abort the others: the returned
+        /// [JobResult]s hold the outcome and the duration of each job, in
+        /// the order of `jobs`. An error is only returned if no browser
+        /// session could be opened.
+        ///
+        /// # Examples
+        ///
+        /// ```no_run
+        /// use plotly::{Plot, Scatter};
+        /// use plotly::export::sync::ExporterSyncExt as _;
+        /// use plotly::plotly_static::{StaticExporterBuilder, ImageFormat};
+        ///
+        /// let mut plot = Plot::new();
+        /// plot.add_trace(Scatter::new(vec![1, 2, 3], vec![4, 5, 6]));
+        ///
+        /// let mut exporter = StaticExporterBuilder::default()
+        ///     .build()
+        ///     .expect("Failed to create StaticExporter");
+        ///
+        /// let results = exporter
+        ///     .write_images(&[
+        ///         (&plot, "plot", ImageFormat::PNG, 800, 600, 2.0),
+        ///         (&plot, "plot", ImageFormat::SVG, 800, 600, 1.0),
+        ///     ])
+        ///     .expect("Failed to open a browser session");
+        /// for job in results {
+        ///     if let Err(e) = job.result {
+        ///         eprintln!("Failed to export {}: {e}", job.dst.display());
+        ///     }
+        /// }
+        ///
+        /// exporter.close();
+        /// ```
+        fn write_images<P: AsRef<Path>>(
+            &mut self,
+            jobs: &[(&Plot, P, ImageFormat, usize, usize, f64)],
+        ) -> Result<Vec<JobResult>, Error>;
+
+        /// Convert every frame of an animated `Plot` to a static image and
+        /// save them at the given location as an animated GIF or APNG, or as
+        /// an image sequence.
+        ///
+        /// The frames of the plot are applied one after the other with
+        /// `Plotly.animate` in a headless browser. See
+        /// [AnimationFormat](plotly_static::AnimationFormat) for the output
+        /// formats and the frame duration.
+        ///
+        /// # Examples
+        ///
+        /// ```no_run
+        /// use std::time::Duration;
+        ///
+        /// use plotly::{Bar, Plot};
+        /// use plotly::export::sync::ExporterSyncExt as _;
+        /// use plotly::layout::Frame;
+        /// use plotly::plotly_static::{AnimationFormat, StaticExporterBuilder};
+        ///
+        /// let mut plot = Plot::new();
+        /// plot.add_trace(Bar::new(vec!["a", "b"], vec![1, 2]));
+        /// for (name, y) in [("2024", vec![2, 3]), ("2025", vec![4, 1])] {
+        ///     let mut traces = plotly::Traces::new();
+        ///     traces.push(Bar::new(vec!["a", "b"], y));
+        ///     plot.add_frame(Frame::new().name(name).data(traces));
+        /// }
+        ///
+        /// let mut exporter = StaticExporterBuilder::default()
+        ///     .build()
+        ///     .expect("Failed to create StaticExporter");
+        ///
+        /// let format = AnimationFormat::GIF { frame_duration: Duration::from_secs(1) };
+        /// exporter.write_animated_image(&plot, "sales", format, 800, 600, 1.0)
+        ///     .expect("Failed to export animation");
+        ///
+        /// exporter.close();
+        /// ```
+        fn write_animated_image<P: AsRef<Path>>(
+            &mut self,
+            plot: &Plot,
+            filename: P,
+            format: AnimationFormat,
+            width: usize,
+            height: usize,
+            scale: f64,
+        ) -> Result<(), Error>;
     }
 
     impl ExporterSyncExt for plotly_static::StaticExporter {
@@ -151,15 +246,15 @@ pub mod sync {
             width: usize,
             height: usize,
             scale: f64,
-        ) -> Result<(), Box<dyn std::error::Error>> {
-            self.write_fig(
+        ) -> Result<(), Error> {
+            Ok(self.write_fig(
                 filename.as_ref(),
-                &serde_json::to_value(plot)?,
+                &figure(plot)?,
                 format,
                 width,
                 height,
                 scale,
-            )
+            )?)
         }
 
         fn to_base64(
@@ -169,20 +264,14 @@ pub mod sync {
             width: usize,
             height: usize,
             scale: f64,
-        ) -> Result<String, Box<dyn std::error::Error>> {
-            match format {
-                ImageFormat::JPEG | ImageFormat::PNG | ImageFormat::WEBP => self.write_to_string(
-                    &serde_json::to_value(plot)?,
-                    format,
-                    width,
-                    height,
-                    scale,
-                ),
-                _ => Err(format!(
-                    "Cannot generate base64 string for ImageFormat:{format}. Allowed formats are JPEG, PNG, WEBP"
-                )
-                .into()),
-            }
+        ) -> Result<String, Error> {
+            Ok(self.write_to_string(
+                &figure(plot)?,
+                base64_format(format)?,
+                width,
+                height,
+                scale,
+            )?)
         }
 
         fn to_svg(
@@ -191,16 +280,73 @@ pub mod sync {
             width: usize,
             height: usize,
             scale: f64,
-        ) -> Result<String, Box<dyn std::error::Error>> {
-            self.write_to_string(
-                &serde_json::to_value(plot)?,
-                ImageFormat::SVG,
+        ) -> Result<String, Error> {
+            Ok(self.write_to_string(&figure(plot)?, ImageFormat::SVG, width, height, scale)?)
+        }
+
+        fn write_images<P: AsRef<Path>>(
+            &mut self,
+            jobs: &[(&Plot, P, ImageFormat, usize, usize, f64)],
+        ) -> Result<Vec<JobResult>, Error> {
+            Ok(self.write_batch(&export_jobs(jobs)?)?)
+        }
+
+        fn write_animated_image<P: AsRef<Path>>(
+            &mut self,
+            plot: &Plot,
+            filename: P,
+            format: AnimationFormat,
+            width: usize,
+            height: usize,
+            scale: f64,
+        ) -> Result<(), Error> {
+            Ok(self.write_animation(
+                filename.as_ref(),
+                &figure(plot)?,
+                format,
                 width,
                 height,
                 scale,
-            )
+            )?)
+        }
+    }
+
+    /// Serialize `plot` as the figure passed to the exporter.
+    pub(crate) fn figure(plot: &Plot) -> Result<Value, Error> {
+        serde_json::to_value(plot).map_err(|e| {
+            Error::Export(ExportError::InvalidInput(format!(
+                "failed to serialize the plot: {e}"
+            )))
+        })
+    }
+
+    /// `format` if images of it can be returned as a `base64` string.
+    pub(crate) fn base64_format(format: ImageFormat) -> Result<ImageFormat, Error> {
+        match format {
+            ImageFormat::JPEG | ImageFormat::PNG | ImageFormat::WEBP => Ok(format),
+            _ => Err(Error::Export(ExportError::InvalidInput(format!(
+                "Cannot generate base64 string for ImageFormat:{format}. Allowed formats are JPEG, PNG, WEBP"
+            )))),
         }
     }
+
+    /// Convert `write_images` jobs to the jobs of a `plotly_static` batch.
+    pub(crate) fn export_jobs<P: AsRef<Path>>(
+        jobs: &[(&Plot, P, ImageFormat, usize, usize, f64)],
+    ) -> Result<Vec<ExportJob>, Error> {
+        jobs.iter()
+            .map(|(plot, filename, format, width, height, scale)| {
+                Ok(ExportJob::new(
+                    filename.as_ref(),
+                    figure(plot)?,
+                    format.clone(),
+                    *width,
+                    *height,
+                    *scale,
+                ))
+            })
+            .collect()
+    }
 }
 
 #[cfg(feature = "plotly_static")]
@@ -208,8 +354,10 @@ pub mod r#async {
     use std::path::Path;
 
     use async_trait::async_trait;
+    use plotly_static::{AnimationFormat, JobResult};
 
-    use crate::{plot::Plot, ImageFormat};
+    use super::sync::{base64_format, export_jobs, figure};
+    use crate::{plot::Plot, Error, ImageFormat};
 
     /// Extension methods for exporting plots using an asynchronous exporter.
     #[async_trait(?Send)]
@@ -233,7 +381,7 @@ pub mod r#async {
             width: usize,
             height: usize,
             scale: f64,
-        ) -> Result<(), Box<dyn std::error::Error>>;
+        ) -> Result<(), Error>;
 
         /// Convert the `Plot` to a static image and return the image as a
         /// `base64` string using the asynchronous exporter.
@@ -253,7 +401,7 @@ pub mod r#async {
             width: usize,
             height: usize,
             scale: f64,
-        ) -> Result<String, Box<dyn std::error::Error>>;
+        ) -> Result<String, Error>;
 
         /// Convert the `Plot` to SVG and return it as a String using the
         /// asynchronous exporter.
@@ -269,7 +417,30 @@ pub mod r#async {
             width: usize,
             height: usize,
             scale: f64,
-        ) -> Result<String, Box<dyn std::error::Error>>;
+        ) -> Result<String, Error>;
+
+        /// Convert several `Plot`s to static images in one browser session.
+        ///
+        /// Same as [`ExporterSyncExt::write_images`](super::sync::ExporterSyncExt::write_images)
+        /// but async.
+        async fn write_images<P: AsRef<Path>>(
+            &mut self,
+            jobs: &[(&Plot, P, ImageFormat, usize, usize, f64)],
+        ) -> Result<Vec<JobResult>, Error>;
+
+        /// Convert every frame of an animated `Plot` to a static image.
+        ///
+        /// Same as [`ExporterSyncExt::write_animated_image`](super::sync::ExporterSyncExt::write_animated_image)
+        /// but async.
+        async fn write_animated_image<P: AsRef<Path>>(
+            &mut self,
+            plot: &Plot,
+            filename: P,
+            format: AnimationFormat,
+            width: usize,
+            height: usize,
+            scale: f64,
+        ) -> Result<(), Error>;
     }
 
     #[async_trait(?Send)]
@@ -282,16 +453,17 @@ pub mod r#async {
             width: usize,
             height: usize,
             scale: f64,
-        ) -> Result<(), Box<dyn std::error::Error>> {
-            self.write_fig(
-                filename.as_ref(),
-                &serde_json::to_value(plot)?,
-                format,
-                width,
-                height,
-                scale,
-            )
-            .await
+        ) -> Result<(), Error> {
+            Ok(self
+                .write_fig(
+                    filename.as_ref(),
+                    &figure(plot)?,
+                    format,
+                    width,
+                    height,
+                    scale,
+                )
+                .await?)
         }
 
         async fn to_base64(
@@ -301,22 +473,10 @@ pub mod r#async {
             width: usize,
             height: usize,
             scale: f64,
-        ) -> Result<String, Box<dyn std::error::Error>> {
-            match format {
-                ImageFormat::JPEG | ImageFormat::PNG | ImageFormat::WEBP => self
-                    .write_to_string(
-                        &serde_json::to_value(plot)?,
-                        format,
-                        width,
-                        height,
-                        scale,
-                    )
-                    .await,
-                _ => Err(format!(
-                    "Cannot generate base64 string for ImageFormat:{format}. Allowed formats are JPEG, PNG, WEBP"
-                )
-                .into()),
-            }
+        ) -> Result<String, Error> {
+            Ok(self
+                .write_to_string(&figure(plot)?, base64_format(format)?, width, height, scale)
+                .await?)
         }
 
         async fn to_svg(
@@ -325,15 +485,38 @@ pub mod r#async {
             width: usize,
             height: usize,
             scale: f64,
-        ) -> Result<String, Box<dyn std::error::Error>> {
-            self.write_to_string(
-                &serde_json::to_value(plot)?,
-                ImageFormat::SVG,
-                width,
-                height,
-                scale,
-            )
-            .await
+        ) -> Result<String, Error> {
+            Ok(self
+                .write_to_string(&figure(plot)?, ImageFormat::SVG, width, height, scale)
+                .await?)
+        }
+
+        async fn write_images<P: AsRef<Path>>(
+            &mut self,
+            jobs: &[(&Plot, P, ImageFormat, usize, usize, f64)],
+        ) -> Result<Vec<JobResult>, Error> {
+            Ok(self.write_batch(&export_jobs(jobs)?).await?)
+        }
+
+        async fn write_animated_image<P: AsRef<Path>>(
+            &mut self,
+            plot: &Plot,
+            filename: P,
+            format: AnimationFormat,
+            width: usize,
+            height: usize,
+            scale: f64,
+        ) -> Result<(), Error> {
+            Ok(self
+                .write_animation(
+                    filename.as_ref(),
+                    &figure(plot)?,
+                    format,
+                    width,
+                    height,
+                    scale,
+                )
+                .await?)
         }
     }
 }
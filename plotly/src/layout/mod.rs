@@ -7,6 +7,7 @@ use update_menu::UpdateMenu;
 
 use crate::color::Color;
 use crate::common::{Calendar, ColorScale, Font, Label, Orientation, Title};
+use crate::private::NumOrString;
 
 pub mod themes;
 pub mod update_menu;
@@ -395,6 +396,12 @@ pub struct LayoutFields {
     #[serde(rename = "updatemenus")]
     update_menus: Option<Vec<UpdateMenu>>,
     sliders: Option<Vec<Slider>>,
+    /// Used to allow user interactions with the plot to persist after
+    /// `Plotly.react` calls that are unaware of these interactions. If
+    /// `uirevision` is unchanged between calls, zoom, pan and legend state
+    /// set by the user are kept.
+    #[serde(rename = "uirevision")]
+    ui_revision: Option<NumOrString>,
 }
 
 #[cfg(test)]
@@ -815,4 +822,13 @@ mod tests {
 
         assert_eq!(to_value(layout).unwrap(), expected);
     }
+
+    #[test]
+    fn serialize_layout_ui_revision() {
+        let layout = Layout::new().ui_revision("rev");
+        assert_eq!(to_value(layout).unwrap(), json!({"uirevision": "rev"}));
+
+        let layout = Layout::new().ui_revision(2);
+        assert_eq!(to_value(layout).unwrap(), json!({"uirevision": 2}));
+    }
 }
@@ -133,7 +133,7 @@ impl ButtonBuilder {
         Ok(())
     }
 
-    pub fn push_relayout(mut self, relayout: impl Relayout + Serialize) -> Self {
+    pub fn push_relayout(mut self, relayout: impl Relayout) -> Self {
         if self.error.is_none() {
             if let Err(e) = self.try_push_relayout(relayout) {
                 self.error = Some(e);
@@ -142,10 +142,7 @@ impl ButtonBuilder {
         self
     }
 
-    fn try_push_relayout(
-        &mut self,
-        relayout: impl Relayout + Serialize,
-    ) -> Result<(), ControlBuilderError> {
+    fn try_push_relayout(&mut self, relayout: impl Relayout) -> Result<(), ControlBuilderError> {
         let relayout_value = serde_json::to_value(&relayout)
             .map_err(|e| ControlBuilderError::RelayoutSerializationError(e.to_string()))?;
         let relayout_obj = relayout_value.as_object().ok_or_else(|| {
@@ -39,7 +39,20 @@ compile_error!(
 #[cfg(feature = "plotly_ndarray")]
 pub mod ndarray;
 #[cfg(feature = "plotly_ndarray")]
-pub use crate::ndarray::ArrayTraces;
+pub use crate::ndarray::{ArrayTraces, GridRow};
+
+#[cfg(feature = "plotly_polars")]
+pub mod polars;
+
+#[cfg(feature = "plotly_arrow")]
+pub mod arrow;
+
+#[cfg(all(
+    feature = "serve",
+    not(target_family = "wasm"),
+    not(target_os = "android")
+))]
+pub mod serve;
 
 #[cfg(target_family = "wasm")]
 pub mod bindings;
@@ -49,13 +62,17 @@ pub mod callbacks;
 
 pub mod common;
 pub mod configuration;
+pub mod error;
+pub mod events;
 pub mod export;
 pub mod layout;
 pub mod plot;
+pub mod stream;
 pub mod traces;
 
 pub use common::color;
 pub use configuration::Configuration;
+pub use error::Error;
 pub use layout::Layout;
 pub use plot::{Plot, Trace, Traces};
 // Also provide easy access to modules which contain additional trace-specific types
@@ -71,7 +88,7 @@ pub use traces::{
 };
 
 pub trait Restyle: serde::Serialize {}
-pub trait Relayout {}
+pub trait Relayout: serde::Serialize {}
 
 #[cfg(feature = "kaleido")]
 pub use plotly_kaleido::ImageFormat;
@@ -91,3 +108,10 @@ pub mod prelude {
 // Not public API.
 #[doc(hidden)]
 mod private;
+
+// The output of `plotly_codegen` for its test schema, compiled with the tests
+// to check the generated code against this crate.
+#[cfg(test)]
+#[allow(dead_code)]
+#[rustfmt::skip]
+mod codegen_fixture;
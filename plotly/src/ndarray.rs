@@ -1,5 +1,62 @@
+use ndarray::{ArcArray2, Array, Ix2};
+use serde::{Serialize, Serializer};
+
 #[derive(PartialOrd, PartialEq, Eq)]
 pub enum ArrayTraces {
     OverColumns,
     OverRows,
 }
+
+/// A single row of a two dimensional array shared between all rows of a
+/// z-grid.
+///
+/// Gridded traces built with `from_array` hold one `GridRow` per row of the
+/// grid. Each row is serialized straight from the shared array, so the grid
+/// is never copied into nested `Vec`s.
+#[derive(Clone, Debug)]
+pub struct GridRow<T> {
+    grid: ArcArray2<T>,
+    row: usize,
+}
+
+impl<T: Clone> GridRow<T> {
+    /// Split `grid` into its rows without copying the data.
+    pub fn rows(grid: Array<T, Ix2>) -> Vec<Self> {
+        let grid = grid.into_shared();
+        (0..grid.nrows())
+            .map(|row| Self {
+                grid: grid.clone(),
+                row,
+            })
+            .collect()
+    }
+}
+
+impl<T: Serialize> Serialize for GridRow<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.grid.row(self.row))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+    use serde_json::{json, to_value};
+
+    use super::*;
+
+    #[test]
+    fn serialize_grid_rows() {
+        let rows = GridRow::rows(array![[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(to_value(rows).unwrap(), json!([[1, 2, 3], [4, 5, 6]]));
+    }
+
+    #[test]
+    fn serialize_grid_rows_of_transposed_array() {
+        let rows = GridRow::rows(array![[1, 2, 3], [4, 5, 6]].reversed_axes());
+        assert_eq!(to_value(rows).unwrap(), json!([[1, 4], [2, 5], [3, 6]]));
+    }
+}
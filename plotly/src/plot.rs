@@ -1,6 +1,11 @@
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
-use std::{fs::File, io::Write, path::Path};
+use std::{
+    ffi::OsStr,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
 
 use askama::Template;
 use dyn_clone::DynClone;
@@ -14,9 +19,19 @@ use rand::{
     rngs::SmallRng,
     SeedableRng,
 };
-use serde::Serialize;
-
-use crate::{layout::Frame, Configuration, Layout};
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+
+#[cfg(all(
+    feature = "serve",
+    not(target_family = "wasm"),
+    not(target_os = "android")
+))]
+use crate::serve::PlotServer;
+use crate::{
+    common::{typed_array::Encoded, DataEncoding},
+    layout::Frame,
+    Configuration, Error, Layout,
+};
 
 static SEED_COUNTER: AtomicU64 = AtomicU64::new(0);
 
@@ -156,19 +171,41 @@ impl Traces {
 ///     Ok(())
 /// }
 /// ```
-#[derive(Serialize, Clone)]
+#[derive(Clone)]
 pub struct Plot {
-    #[serde(rename = "data")]
     traces: Traces,
     layout: Layout,
-    #[serde(rename = "config")]
     configuration: Configuration,
     /// Animation frames
     frames: Option<Vec<Frame>>,
-    #[serde(skip)]
+    data_encoding: DataEncoding,
     js_scripts: String,
 }
 
+impl Serialize for Plot {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Plot", 4)?;
+        match self.data_encoding {
+            DataEncoding::Json => {
+                state.serialize_field("data", &self.traces)?;
+                state.serialize_field("layout", &self.layout)?;
+                state.serialize_field("config", &self.configuration)?;
+                state.serialize_field("frames", &self.frames)?;
+            }
+            DataEncoding::TypedArray => {
+                state.serialize_field("data", &Encoded::traces(&self.traces))?;
+                state.serialize_field("layout", &self.layout)?;
+                state.serialize_field("config", &self.configuration)?;
+                state.serialize_field("frames", &Encoded::frames(&self.frames))?;
+            }
+        }
+        state.end()
+    }
+}
+
 impl Default for Plot {
     fn default() -> Self {
         Self::new()
@@ -183,6 +220,7 @@ impl Plot {
             layout: Layout::default(),
             configuration: Configuration::default(),
             frames: None,
+            data_encoding: DataEncoding::default(),
             js_scripts: Self::js_scripts(),
         }
     }
@@ -219,6 +257,21 @@ impl Plot {
         self.configuration = configuration;
     }
 
+    /// Set how the numeric data of the traces is serialized.
+    ///
+    /// With [`DataEncoding::TypedArray`] numeric vectors and matrices are
+    /// written as base64 encoded typed arrays (`{dtype, bdata, shape}`), which
+    /// greatly reduces the size of the JSON and HTML output for large traces.
+    /// Arrays containing strings or missing values are left untouched.
+    pub fn set_data_encoding(&mut self, data_encoding: DataEncoding) {
+        self.data_encoding = data_encoding;
+    }
+
+    /// Get the encoding used for the numeric data of the traces.
+    pub fn data_encoding(&self) -> DataEncoding {
+        self.data_encoding
+    }
+
     /// Get the contained data elements.
     pub fn data(&self) -> &Traces {
         &self.traces
@@ -275,31 +328,26 @@ impl Plot {
     ///
     /// The HTML file is saved in a temp file, from which it is read and
     /// displayed by the browser.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the temp file cannot be written or the browser cannot be
+    /// launched. Use `Plot::try_show()` to handle these errors instead.
     #[cfg(all(not(target_family = "wasm"), not(target_os = "android")))]
     pub fn show(&self) {
-        use std::env;
-        let rendered = self.render();
+        Self::expect_shown(self.try_show());
+    }
 
-        // Set up the temp file with a unique filename.
-        let mut temp = env::temp_dir();
-        let mut plot_name =
-            Alphanumeric.sample_string(&mut SmallRng::seed_from_u64(Self::generate_seed()), 22);
-        plot_name.push_str(".html");
-        plot_name = format!("plotly_{plot_name}");
-        temp.push(plot_name);
-
-        // Save the rendered plot to the temp file.
-        let temp_path = temp.to_str().unwrap();
-
-        {
-            let mut file = File::create(temp_path).unwrap();
-            file.write_all(rendered.as_bytes())
-                .expect("failed to write html output");
-            file.flush().unwrap();
-        }
+    /// Display the fully rendered HTML `Plot` in the default system browser,
+    /// returning an error instead of panicking if the temp file cannot be
+    /// written or the browser cannot be launched.
+    #[cfg(all(not(target_family = "wasm"), not(target_os = "android")))]
+    pub fn try_show(&self) -> Result<(), Error> {
+        let temp_path = Self::temp_html_path();
+        self.try_write_html(&temp_path)?;
 
         // Hand off the job of opening the browser to an OS-specific implementation.
-        Plot::show_with_default_app(temp_path);
+        Plot::show_with_default_app(&temp_path)
     }
 
     /// Display the fully rendered HTML `Plot` in the default system browser.
@@ -307,57 +355,117 @@ impl Plot {
     /// The HTML file is generated and saved in the provided filename as long as
     /// the path already exists, after the file is saved, it is read and
     /// displayed by the browser.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the file cannot be written or the browser cannot be launched.
+    /// Use `Plot::try_show_html()` to handle these errors instead.
     #[cfg(all(not(target_family = "wasm"), not(target_os = "android")))]
     pub fn show_html<P: AsRef<Path> + std::clone::Clone>(&self, filename: P) {
-        let path = filename.as_ref().to_str().unwrap();
-        self.write_html(filename.clone());
+        Self::expect_shown(self.try_show_html(filename));
+    }
+
+    /// Save the rendered HTML `Plot` to the provided filename and display it in
+    /// the default system browser, returning an error instead of panicking if
+    /// the file cannot be written or the browser cannot be launched.
+    #[cfg(all(not(target_family = "wasm"), not(target_os = "android")))]
+    pub fn try_show_html<P: AsRef<Path>>(&self, filename: P) -> Result<(), Error> {
+        self.try_write_html(filename.as_ref())?;
         // Hand off the job of opening the browser to an OS-specific implementation.
-        Plot::show_with_default_app(path);
+        Plot::show_with_default_app(filename.as_ref())
+    }
+
+    /// Serve the `Plot` from a local live-reload server and open it once in
+    /// the default system browser.
+    ///
+    /// Later changes are pushed to the open page with `PlotServer::update`,
+    /// instead of opening a new tab for each call as `Plot::show()` does. The
+    /// server stops when the returned `PlotServer` is dropped. This method
+    /// requires the `serve` feature.
+    #[cfg(all(not(target_family = "wasm"), not(target_os = "android")))]
+    #[cfg(feature = "serve")]
+    pub fn serve(&self) -> Result<PlotServer, Error> {
+        let server = PlotServer::bind("127.0.0.1:0", self)?;
+        server.open()?;
+        Ok(server)
     }
 
     /// Display the fully rendered `Plot` as a static image of the given format
     /// in the default system browser.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the temp file cannot be written or the browser cannot be
+    /// launched. Use `Plot::try_show_image()` to handle these errors instead.
     #[cfg(all(not(target_family = "wasm"), not(target_os = "android")))]
     #[cfg(any(feature = "kaleido", feature = "plotly_static"))]
     pub fn show_image(&self, format: ImageFormat, width: usize, height: usize) {
-        use std::env;
-
-        let rendered = self.render_static(&format, width, height);
+        Self::expect_shown(self.try_show_image(format, width, height));
+    }
 
-        // Set up the temp file with a unique filename.
-        let mut temp = env::temp_dir();
-        let mut plot_name =
-            Alphanumeric.sample_string(&mut SmallRng::seed_from_u64(Self::generate_seed()), 22);
-        plot_name.push_str(".html");
-        plot_name = format!("plotly_{plot_name}");
-        temp.push(plot_name);
-
-        // Save the rendered plot to the temp file.
-        let temp_path = temp.to_str().unwrap();
-
-        {
-            let mut file = File::create(temp_path).unwrap();
-            file.write_all(rendered.as_bytes())
-                .expect("failed to write html output");
-            file.flush().unwrap();
-        }
+    /// Display the fully rendered `Plot` as a static image of the given format
+    /// in the default system browser, returning an error instead of panicking
+    /// if the temp file cannot be written or the browser cannot be launched.
+    #[cfg(all(not(target_family = "wasm"), not(target_os = "android")))]
+    #[cfg(any(feature = "kaleido", feature = "plotly_static"))]
+    pub fn try_show_image(
+        &self,
+        format: ImageFormat,
+        width: usize,
+        height: usize,
+    ) -> Result<(), Error> {
+        let temp_path = Self::temp_html_path();
+        let tmpl = StaticPlotTemplate {
+            plot: self,
+            format,
+            js_scripts: &self.js_scripts,
+            width,
+            height,
+        };
+        Self::write_template(&tmpl, &temp_path)?;
 
         // Hand off the job of opening the browser to an OS-specific implementation.
-        Plot::show_with_default_app(temp_path);
+        Plot::show_with_default_app(&temp_path)
     }
 
     /// Save the rendered `Plot` to a file at the given location.
     ///
     /// This method will render the plot to a full, standalone HTML document,
     /// before saving it to the given location.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the file cannot be created or written. Use
+    /// `Plot::try_write_html()` to handle these errors instead.
     pub fn write_html<P: AsRef<Path>>(&self, filename: P) {
-        let rendered = self.to_html();
+        if let Err(e) = self.try_write_html(filename) {
+            panic!("{e}");
+        }
+    }
 
-        let mut file =
-            File::create(filename).expect("Provided filepath does not exist or is not accessible");
-        file.write_all(rendered.as_bytes())
-            .expect("failed to write html output");
-        file.flush().unwrap();
+    /// Save the rendered `Plot` to a file at the given location, returning an
+    /// error if the file cannot be created or written.
+    pub fn try_write_html<P: AsRef<Path>>(&self, filename: P) -> Result<(), Error> {
+        let tmpl = PlotTemplate {
+            plot: self,
+            js_scripts: &self.js_scripts,
+        };
+        Self::write_template(&tmpl, filename.as_ref())
+    }
+
+    /// Render the `Plot` as a full, standalone HTML document directly into
+    /// `writer`.
+    ///
+    /// Unlike `Plot::to_html()`, the document is streamed through the template
+    /// without first being collected into a `String`, so it can be written
+    /// straight to a file, an HTTP response body or a compressor. Wrap
+    /// unbuffered writers such as `File` in a `BufWriter`.
+    pub fn write_html_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let tmpl = PlotTemplate {
+            plot: self,
+            js_scripts: &self.js_scripts,
+        };
+        tmpl.write_into(&mut writer)
     }
 
     /// Convert a `Plot` to an HTML string representation.
@@ -547,11 +655,9 @@ impl Plot {
         width: usize,
         height: usize,
         scale: f64,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<(), Error> {
         use crate::prelude::*;
-        let mut exporter = plotly_static::StaticExporterBuilder::default()
-            .build()
-            .map_err(|e| format!("Failed to create StaticExporter: {e}"))?;
+        let mut exporter = plotly_static::StaticExporterBuilder::default().build()?;
         let result = exporter.write_image(self, filename, format, width, height, scale);
         exporter.close();
         result
@@ -579,11 +685,9 @@ impl Plot {
         width: usize,
         height: usize,
         scale: f64,
-    ) -> Result<String, Box<dyn std::error::Error>> {
+    ) -> Result<String, Error> {
         use crate::prelude::*;
-        let mut exporter = plotly_static::StaticExporterBuilder::default()
-            .build()
-            .map_err(|e| format!("Failed to create StaticExporter: {e}"))?;
+        let mut exporter = plotly_static::StaticExporterBuilder::default().build()?;
         let result = exporter.to_base64(self, format, width, height, scale);
         exporter.close();
         result
@@ -601,16 +705,9 @@ impl Plot {
     /// methods to reuse a single `StaticExporter` instance across multiple
     /// operations.
     #[cfg(feature = "plotly_static")]
-    pub fn to_svg(
-        &self,
-        width: usize,
-        height: usize,
-        scale: f64,
-    ) -> Result<String, Box<dyn std::error::Error>> {
+    pub fn to_svg(&self, width: usize, height: usize, scale: f64) -> Result<String, Error> {
         use crate::prelude::*;
-        let mut exporter = plotly_static::StaticExporterBuilder::default()
-            .build()
-            .map_err(|e| format!("Failed to create StaticExporter: {e}"))?;
+        let mut exporter = plotly_static::StaticExporterBuilder::default().build()?;
         let result = exporter.to_svg(self, width, height, scale);
         exporter.close();
         result
@@ -630,14 +727,14 @@ impl Plot {
         height: usize,
         scale: f64,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        exporter.write_fig(
+        Ok(exporter.write_fig(
             filename.as_ref(),
             &serde_json::to_value(self)?,
             format,
             width,
             height,
             scale,
-        )
+        )?)
     }
 
     /// Deprecated: use [crate::export::sync::ExporterSyncExt::to_base64].
@@ -655,13 +752,13 @@ impl Plot {
     ) -> Result<String, Box<dyn std::error::Error>> {
         match format {
             ImageFormat::JPEG | ImageFormat::PNG | ImageFormat::WEBP => {
-                exporter.write_to_string(
+                Ok(exporter.write_to_string(
                     &serde_json::to_value(self)?,
                     format,
                     width,
                     height,
                     scale,
-                )
+                )?)
             }
             _ => {
                 Err(format!("Cannot generate base64 string for ImageFormat:{format}. Allowed formats are JPEG, PNG, WEBP").into())
@@ -681,13 +778,21 @@ impl Plot {
         height: usize,
         scale: f64,
     ) -> Result<String, Box<dyn std::error::Error>> {
-        exporter.write_to_string(
+        Ok(exporter.write_to_string(
             &serde_json::to_value(self)?,
             ImageFormat::SVG,
             width,
             height,
             scale,
-        )
+        )?)
+    }
+
+    /// Stream a rendered template into a newly created file.
+    fn write_template<T: Template>(tmpl: &T, path: &Path) -> Result<(), Error> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        Template::write_into(tmpl, &mut writer).map_err(Error::from_template_write)?;
+        writer.flush()?;
+        Ok(())
     }
 
     fn render(&self) -> String {
@@ -776,6 +881,12 @@ impl Plot {
         serde_json::to_string(self).unwrap()
     }
 
+    /// Serialize the `Plot` as JSON directly into `writer`, without first
+    /// building the full string in memory.
+    pub fn write_json_to<W: Write>(&self, writer: W) -> io::Result<()> {
+        serde_json::to_writer(writer, self).map_err(io::Error::from)
+    }
+
     #[cfg(target_family = "wasm")]
     /// Convert a `Plot` to a native JavaScript `js_sys::Object`.
     pub fn to_js_object(&self) -> wasm_bindgen_futures::js_sys::Object {
@@ -791,30 +902,51 @@ impl Plot {
     }
 
     #[cfg(all(unix, not(target_os = "android"), not(target_os = "macos")))]
-    fn show_with_default_app(temp_path: &str) {
+    pub(crate) fn show_with_default_app<T: AsRef<OsStr>>(target: T) -> Result<(), Error> {
         use std::process::Command;
         Command::new("xdg-open")
-            .args([temp_path])
+            .arg(target)
             .output()
-            .expect(DEFAULT_HTML_APP_NOT_FOUND);
+            .map(|_| ())
+            .map_err(Error::Launch)
     }
 
     #[cfg(target_os = "macos")]
-    fn show_with_default_app(temp_path: &str) {
+    pub(crate) fn show_with_default_app<T: AsRef<OsStr>>(target: T) -> Result<(), Error> {
         use std::process::Command;
         Command::new("open")
-            .args([temp_path])
+            .arg(target)
             .output()
-            .expect(DEFAULT_HTML_APP_NOT_FOUND);
+            .map(|_| ())
+            .map_err(Error::Launch)
     }
 
     #[cfg(target_os = "windows")]
-    fn show_with_default_app(temp_path: &str) {
+    pub(crate) fn show_with_default_app<T: AsRef<OsStr>>(target: T) -> Result<(), Error> {
         use std::process::Command;
         Command::new("explorer")
-            .arg(temp_path)
+            .arg(target)
             .spawn()
-            .expect(DEFAULT_HTML_APP_NOT_FOUND);
+            .map(|_| ())
+            .map_err(Error::Launch)
+    }
+
+    /// A unique path in the system temp directory for displaying a plot.
+    #[cfg(all(not(target_family = "wasm"), not(target_os = "android")))]
+    fn temp_html_path() -> std::path::PathBuf {
+        let plot_name =
+            Alphanumeric.sample_string(&mut SmallRng::seed_from_u64(Self::generate_seed()), 22);
+        std::env::temp_dir().join(format!("plotly_{plot_name}.html"))
+    }
+
+    /// Panic with a helpful message if a plot could not be displayed.
+    #[cfg(all(not(target_family = "wasm"), not(target_os = "android")))]
+    fn expect_shown(result: Result<(), Error>) {
+        match result {
+            Ok(()) => {}
+            Err(e @ Error::Launch(_)) => panic!("{DEFAULT_HTML_APP_NOT_FOUND}: {e}"),
+            Err(e) => panic!("{e}"),
+        }
     }
 
     /// Generate unique seeds for SmallRng such that file names and div names
@@ -939,6 +1071,24 @@ mod tests {
         assert_eq!(to_value(plot).unwrap(), expected);
     }
 
+    #[test]
+    fn plot_serialize_typed_array() {
+        let x: Vec<f64> = (0..32).map(f64::from).collect();
+        let mut plot = Plot::new();
+        plot.add_trace(Scatter::new(x.clone(), vec!["a"; 32]));
+        plot.add_frame(Frame::new().data(plot.data().clone()));
+        plot.set_data_encoding(DataEncoding::TypedArray);
+
+        let encoded = to_value(crate::common::TypedArray::from_f64(&x)).unwrap();
+        let value = to_value(&plot).unwrap();
+        assert_eq!(value["data"][0]["x"], encoded);
+        assert_eq!(value["data"][0]["y"], json!(vec!["a"; 32]));
+        assert_eq!(value["frames"][0]["data"][0]["x"], encoded);
+
+        plot.set_data_encoding(DataEncoding::Json);
+        assert_eq!(to_value(&plot).unwrap()["data"][0]["x"], json!(x));
+    }
+
     #[test]
     fn plot_serialize_with_layout() {
         let mut plot = create_test_plot();
@@ -1038,6 +1188,42 @@ mod tests {
         assert!(std::fs::remove_file(&dst).is_ok());
     }
 
+    #[test]
+    fn try_write_html_missing_directory() {
+        let plot = create_test_plot();
+        let dst = PathBuf::from("missing_directory").join("plotly_example.html");
+        match plot.try_write_html(&dst) {
+            Err(Error::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::NotFound),
+            other => panic!("unexpected result: {other:?}"),
+        }
+        assert!(!dst.exists());
+    }
+
+    #[test]
+    fn try_write_html_matches_to_html() {
+        let plot = create_test_plot();
+        let dst = std::env::temp_dir().join("plotly_try_write_html.html");
+        plot.try_write_html(&dst).unwrap();
+        assert_eq!(std::fs::read_to_string(&dst).unwrap(), plot.to_html());
+        std::fs::remove_file(&dst).unwrap();
+    }
+
+    #[test]
+    fn write_html_to_writer() {
+        let plot = create_test_plot();
+        let mut buf = Vec::new();
+        plot.write_html_to(&mut buf).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), plot.to_html());
+    }
+
+    #[test]
+    fn write_json_to_writer() {
+        let plot = create_test_plot();
+        let mut buf = Vec::new();
+        plot.write_json_to(&mut buf).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), plot.to_json());
+    }
+
     // Helper to generate unique ports for parallel tests
     #[cfg(feature = "plotly_static")]
     fn get_unique_port() -> u32 {
@@ -1246,4 +1432,56 @@ mod tests {
         assert!(std::fs::remove_file(&dst).is_ok());
         exporter.close();
     }
+
+    #[test]
+    fn plot_with_generated_traces() {
+        use crate::codegen_fixture::{
+            layout::{Layout as GeneratedLayout, LayoutBarmode, LayoutTitle, LayoutXaxis},
+            traces::{
+                scatter::{ScatterFill, ScatterMarker},
+                Bar, Scatter as GeneratedScatter,
+            },
+        };
+        use crate::common::ColorScalePalette;
+        use crate::private::NumOrString;
+
+        let scatter = GeneratedScatter::new()
+            .x(vec![1, 2])
+            .y(vec![3.5, 4.5])
+            .fill(ScatterFill::Tozeroy)
+            .hoverinfo("x+y")
+            .marker(
+                ScatterMarker::new()
+                    .color("red")
+                    .size_array(vec![4.0, 8.0])
+                    .colorscale(ColorScalePalette::Viridis.into()),
+            );
+        let mut plot = Plot::new();
+        plot.add_trace(scatter);
+        plot.add_trace(Bar::new().y(vec!["a", "b"]).width(0.5));
+
+        assert_eq!(
+            to_value(&plot).unwrap()["data"],
+            json!([
+                {
+                    "type": "scatter",
+                    "x": [1, 2],
+                    "y": [3.5, 4.5],
+                    "fill": "tozeroy",
+                    "hoverinfo": "x+y",
+                    "marker": {"color": "red", "size": [4.0, 8.0], "colorscale": "Viridis"}
+                },
+                {"type": "bar", "y": ["a", "b"], "width": 0.5}
+            ])
+        );
+
+        let layout = GeneratedLayout::new()
+            .title(LayoutTitle::new().text("Title"))
+            .barmode(LayoutBarmode::Stack)
+            .xaxis(LayoutXaxis::new().range(vec![NumOrString::I(0), NumOrString::F(1.5)]));
+        assert_eq!(
+            to_value(layout).unwrap(),
+            json!({"title": {"text": "Title"}, "barmode": "stack", "xaxis": {"range": [0, 1.5]}})
+        );
+    }
 }
@@ -0,0 +1,274 @@
+//! Building traces from [Polars](https://pola.rs) `DataFrame` columns.
+//!
+//! Columns are converted to JSON values that `plotly.js` understands: dates
+//! and datetimes become date strings in the column's time zone, categorical
+//! and enum columns become their string labels and nulls become `null`, which
+//! `plotly.js` renders as gaps.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use plotly::common::{Marker, Mode};
+//! use plotly::polars::DataFrameExt;
+//! use plotly::{Plot, Scatter};
+//! use polars::prelude::*;
+//!
+//! # fn main() -> PolarsResult<()> {
+//! let df = df!(
+//!     "species" => ["setosa", "setosa", "virginica", "virginica"],
+//!     "sepal_length" => [5.1, 4.9, 6.3, 5.8],
+//!     "sepal_width" => [3.5, 3.0, 3.3, 2.7],
+//! )?;
+//!
+//! let traces = df.group_traces("species", |group| {
+//!     Ok(Scatter::new(
+//!         group.column_values("sepal_length")?,
+//!         group.column_values("sepal_width")?,
+//!     )
+//!     .mode(Mode::Markers)
+//!     .name(group.name())
+//!     .marker(Marker::new().color(group.color())))
+//! })?;
+//!
+//! let mut plot = Plot::new();
+//! plot.add_traces(traces);
+//! # Ok(())
+//! # }
+//! ```
+
+use polars::prelude::*;
+use serde_json::{Number, Value};
+
+use crate::Trace;
+
+/// The `plotly.js` default colour sequence, cycled through by
+/// [`DataFrameExt::group_traces`].
+pub const GROUP_COLORS: [&str; 10] = [
+    "#636efa", "#EF553B", "#00cc96", "#ab63fa", "#FFA15A", "#19d3f3", "#FF6692", "#B6E880",
+    "#FF97FF", "#FECB52",
+];
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f";
+
+/// Extension methods for building traces from a `DataFrame`.
+pub trait DataFrameExt {
+    /// Convert the column `name` into JSON values that can be passed to any
+    /// trace constructor or data attribute, e.g. `Scatter::new(x, y)`.
+    fn column_values(&self, name: &str) -> PolarsResult<Vec<Value>>;
+
+    /// Split the frame by the distinct values of the column `by`, in order of
+    /// first appearance, and build one trace per group with `build`.
+    ///
+    /// Each [`Group`] carries a name derived from its key and a colour taken
+    /// from [`GROUP_COLORS`], so that traces can be labelled and coloured
+    /// consistently across plots.
+    fn group_traces<T, F>(&self, by: &str, build: F) -> PolarsResult<Vec<Box<dyn Trace>>>
+    where
+        T: Trace + 'static,
+        F: FnMut(&Group) -> PolarsResult<Box<T>>;
+}
+
+impl DataFrameExt for DataFrame {
+    fn column_values(&self, name: &str) -> PolarsResult<Vec<Value>> {
+        series_to_values(self.column(name)?.as_materialized_series())
+    }
+
+    fn group_traces<T, F>(&self, by: &str, mut build: F) -> PolarsResult<Vec<Box<dyn Trace>>>
+    where
+        T: Trace + 'static,
+        F: FnMut(&Group) -> PolarsResult<Box<T>>,
+    {
+        self.partition_by_stable([by], true)?
+            .into_iter()
+            .enumerate()
+            .map(|(index, frame)| {
+                let key = frame.column_values(by)?.into_iter().next();
+                let group = Group {
+                    name: match key {
+                        Some(Value::String(name)) => name,
+                        Some(key) => key.to_string(),
+                        None => String::new(),
+                    },
+                    color: GROUP_COLORS[index % GROUP_COLORS.len()],
+                    frame,
+                };
+                build(&group).map(|trace| trace as Box<dyn Trace>)
+            })
+            .collect()
+    }
+}
+
+/// One group of rows produced by [`DataFrameExt::group_traces`].
+#[derive(Debug, Clone)]
+pub struct Group {
+    name: String,
+    color: &'static str,
+    frame: DataFrame,
+}
+
+impl Group {
+    /// The group key formatted as a trace name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The colour assigned to this group.
+    pub fn color(&self) -> &'static str {
+        self.color
+    }
+
+    /// The rows belonging to this group.
+    pub fn frame(&self) -> &DataFrame {
+        &self.frame
+    }
+
+    /// Convert the column `name` of this group into JSON values.
+    pub fn column_values(&self, name: &str) -> PolarsResult<Vec<Value>> {
+        self.frame.column_values(name)
+    }
+}
+
+/// Convert a `Series` into JSON values, mapping temporal and categorical data
+/// types to the string representations `plotly.js` expects.
+pub fn series_to_values(series: &Series) -> PolarsResult<Vec<Value>> {
+    let series = match series.dtype() {
+        DataType::Date => series.date()?.to_string(DATE_FORMAT)?.into_series(),
+        DataType::Datetime(..) => series.datetime()?.to_string(DATETIME_FORMAT)?.into_series(),
+        DataType::Categorical(..) | DataType::Enum(..) => series.cast(&DataType::String)?,
+        _ => series.clone(),
+    }
+    .rechunk();
+
+    Ok(series.iter().map(any_value_to_json).collect())
+}
+
+fn any_value_to_json(value: AnyValue) -> Value {
+    match value {
+        AnyValue::Null => Value::Null,
+        AnyValue::Boolean(v) => Value::Bool(v),
+        AnyValue::String(v) => Value::String(v.to_string()),
+        AnyValue::StringOwned(v) => Value::String(v.to_string()),
+        AnyValue::UInt8(v) => Value::from(v),
+        AnyValue::UInt16(v) => Value::from(v),
+        AnyValue::UInt32(v) => Value::from(v),
+        AnyValue::UInt64(v) => Value::from(v),
+        AnyValue::Int8(v) => Value::from(v),
+        AnyValue::Int16(v) => Value::from(v),
+        AnyValue::Int32(v) => Value::from(v),
+        AnyValue::Int64(v) => Value::from(v),
+        AnyValue::Float32(v) => float_to_json(v as f64),
+        AnyValue::Float64(v) => float_to_json(v),
+        other => Value::String(other.to_string()),
+    }
+}
+
+/// `NaN` and infinities have no JSON representation and are shown as gaps.
+fn float_to_json(v: f64) -> Value {
+    Number::from_f64(v).map_or(Value::Null, Value::Number)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::Scatter;
+
+    #[test]
+    fn numeric_and_null_values() {
+        let df = df!(
+            "a" => [Some(1i32), None, Some(3)],
+            "b" => [Some(1.5f64), Some(f64::NAN), None],
+            "c" => [Some("x"), None, Some("z")],
+        )
+        .unwrap();
+
+        assert_eq!(
+            Value::Array(df.column_values("a").unwrap()),
+            json!([1, null, 3])
+        );
+        assert_eq!(
+            Value::Array(df.column_values("b").unwrap()),
+            json!([1.5, null, null])
+        );
+        assert_eq!(
+            Value::Array(df.column_values("c").unwrap()),
+            json!(["x", null, "z"])
+        );
+        assert!(df.column_values("missing").is_err());
+    }
+
+    #[test]
+    fn temporal_values() {
+        let df = df!("t" => [0i64, 90_061_500])
+            .unwrap()
+            .lazy()
+            .with_columns([
+                col("t")
+                    .cast(DataType::Datetime(TimeUnit::Milliseconds, None))
+                    .alias("datetime"),
+                col("t")
+                    .cast(DataType::Datetime(TimeUnit::Milliseconds, None))
+                    .cast(DataType::Date)
+                    .alias("date"),
+            ])
+            .collect()
+            .unwrap();
+
+        assert_eq!(
+            Value::Array(df.column_values("datetime").unwrap()),
+            json!(["1970-01-01 00:00:00", "1970-01-02 01:01:01.500"])
+        );
+        assert_eq!(
+            Value::Array(df.column_values("date").unwrap()),
+            json!(["1970-01-01", "1970-01-02"])
+        );
+    }
+
+    #[test]
+    fn categorical_values() {
+        let df = df!("c" => ["a", "b", "a"])
+            .unwrap()
+            .lazy()
+            .with_column(col("c").cast(DataType::from_categories(Categories::global())))
+            .collect()
+            .unwrap();
+
+        assert_eq!(
+            Value::Array(df.column_values("c").unwrap()),
+            json!(["a", "b", "a"])
+        );
+    }
+
+    #[test]
+    fn group_traces_by_column() {
+        let df = df!(
+            "g" => ["b", "a", "b", "a", "c"],
+            "x" => [1, 2, 3, 4, 5],
+        )
+        .unwrap();
+
+        let traces = df
+            .group_traces("g", |group| {
+                Ok(
+                    Scatter::new(group.column_values("x")?, group.column_values("x")?)
+                        .name(group.name())
+                        .marker(crate::common::Marker::new().color(group.color())),
+                )
+            })
+            .unwrap();
+
+        let traces: Vec<Value> = traces
+            .iter()
+            .map(|trace| serde_json::from_str(&trace.to_json()).unwrap())
+            .collect();
+        assert_eq!(traces.len(), 3);
+        assert_eq!(traces[0]["name"], json!("b"));
+        assert_eq!(traces[0]["x"], json!([1, 3]));
+        assert_eq!(traces[0]["marker"]["color"], json!(GROUP_COLORS[0]));
+        assert_eq!(traces[1]["name"], json!("a"));
+        assert_eq!(traces[1]["x"], json!([2, 4]));
+        assert_eq!(traces[2]["marker"]["color"], json!(GROUP_COLORS[2]));
+    }
+}
@@ -1,5 +1,5 @@
 #[cfg(feature = "plotly_ndarray")]
-use ndarray::{Array, Ix2};
+use ndarray::{Array, Ix1, Ix2};
 use serde::Serialize;
 
 #[cfg(feature = "plotly_ndarray")]
@@ -125,6 +125,24 @@ where
     traces
 }
 
+/// Convert a one dimensional array into a `Vec`, reusing its allocation when
+/// the elements are contiguous.
+#[cfg(feature = "plotly_ndarray")]
+pub fn vector_from<T>(array: Array<T, Ix1>) -> Vec<T>
+where
+    T: Clone,
+{
+    if !array.is_standard_layout() {
+        return array.to_vec();
+    }
+    let len = array.len();
+    let (mut data, offset) = array.into_raw_vec_and_offset();
+    let offset = offset.unwrap_or(0);
+    data.truncate(offset + len);
+    data.drain(..offset);
+    data
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::{json, to_value};
@@ -203,4 +221,20 @@ mod tests {
         assert_eq!(to_value(NumOrStringCollection(vec![NumOrString::I(-50)])).unwrap(), json!([-50]));
         assert_eq!(to_value(NumOrStringCollection(vec![NumOrString::U(50)])).unwrap(), json!([50]));
     }
+
+    #[test]
+    #[cfg(feature = "plotly_ndarray")]
+    fn vector_from_array() {
+        use ndarray::{array, s};
+
+        assert_eq!(vector_from(array![1, 2, 3]), vec![1, 2, 3]);
+        assert_eq!(
+            vector_from(array![1, 2, 3, 4].slice_move(s![1..3])),
+            vec![2, 3]
+        );
+        assert_eq!(
+            vector_from(array![1, 2, 3, 4].slice_move(s![..;2])),
+            vec![1, 3]
+        );
+    }
 }
@@ -0,0 +1,921 @@
+//! A local live-reload preview server, enabled with the `serve` feature.
+//!
+//! [`Plot::serve`] starts a [`PlotServer`] and opens its page in the default
+//! system browser once. Each later call to [`PlotServer::update`] pushes the
+//! new figure to every open page over a WebSocket, where it is redrawn with
+//! `Plotly.react`. Unless the layout sets its own `uirevision`, a fixed one is
+//! added so that zoom, pan and legend state survive updates.
+//!
+//! For live dashboards, a [`PlotStream`] obtained from [`PlotServer::stream`]
+//! appends or prepends points to individual traces with `Plotly.extendTraces`
+//! and `Plotly.prependTraces`, without re-sending the whole figure.
+//!
+//! Closures registered with [`PlotServer::on_click`],
+//! [`PlotServer::on_selected`], [`PlotServer::on_relayout`] and the other
+//! `on_*` methods are called with the typed payloads of [`crate::events`]
+//! when the corresponding plotly.js event fires on an open page. They run on
+//! the server's background threads.
+//!
+//! The page embeds `plotly.js` from [`Plot::offline_js_sources`], so the
+//! server works without an internet connection.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use plotly::serve::StreamUpdate;
+//! use plotly::{Plot, Scatter};
+//!
+//! # fn main() -> Result<(), plotly::Error> {
+//! let mut plot = Plot::new();
+//! plot.add_trace(Scatter::new(vec![0, 1, 2], vec![2, 1, 0]));
+//! let server = plot.serve()?;
+//!
+//! plot.add_trace(Scatter::new(vec![0, 1, 2], vec![0, 1, 2]));
+//! server.update(&plot)?;
+//!
+//! // Append a point to the second trace, keeping at most 100 points.
+//! let stream = server.stream();
+//! stream.extend(&StreamUpdate::new().xy(1, &[3], &[3]).max_points(100))?;
+//!
+//! // Print the points clicked in the browser.
+//! server.on_click(|event| println!("{:?}", event.points));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    io::{self, Read, Write},
+    net::{IpAddr, SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Sender},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use askama::Template;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+use tungstenite::{
+    handshake::server::{ErrorResponse, Request, Response},
+    http::StatusCode,
+    Message, WebSocket,
+};
+
+pub use crate::stream::StreamUpdate;
+use crate::{
+    common::{typed_array::Encoded, DataEncoding},
+    events::{ClickEvent, PointsEvent, RelayoutEvent, RestyleEvent, SelectionEvent},
+    layout::Frame,
+    Configuration, Error, Layout, Plot, Traces,
+};
+
+/// The `uirevision` added to layouts that do not set one.
+const UI_REVISION: &str = "plotly-serve";
+
+/// The WebSocket path on which pages send their plotly.js events.
+const EVENTS_PATH: &str = "/events";
+
+/// How often the threads reading events check whether the server was dropped.
+const EVENTS_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// The largest HTTP request line and headers read from a client.
+const MAX_REQUEST_HEAD: usize = 16 * 1024;
+
+/// The time a client has to send the request line and headers.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Template)]
+#[template(path = "serve_plot.html", escape = "none")]
+struct ServePlotTemplate<'a> {
+    figure: &'a Value,
+    js_scripts: &'a str,
+    ws_port: u16,
+}
+
+/// The figure passed to `Plotly.newPlot` and `Plotly.react`.
+#[derive(Serialize)]
+struct Figure<'a> {
+    data: &'a Traces,
+    layout: &'a Layout,
+    config: &'a Configuration,
+    frames: Option<&'a [Frame]>,
+}
+
+struct State {
+    /// The current figure, always with JSON encoded data so that streamed
+    /// points can be added to it.
+    figure: Value,
+    encoding: DataEncoding,
+    /// The message queues of the open pages, each drained by the page's own
+    /// writer thread so that a stalled page does not block the others.
+    clients: Vec<Sender<String>>,
+}
+
+impl State {
+    fn new(plot: &Plot) -> Result<Self, Error> {
+        Ok(Self {
+            figure: figure(plot)?,
+            encoding: plot.data_encoding(),
+            clients: Vec::new(),
+        })
+    }
+
+    /// The current figure, encoded as requested by the plot.
+    fn encoded_figure(&self) -> Result<Value, Error> {
+        match self.encoding {
+            DataEncoding::Json => Ok(self.figure.clone()),
+            DataEncoding::TypedArray => {
+                Ok(serde_json::to_value(Encoded::figure(&self.figure)).map_err(io::Error::from)?)
+            }
+        }
+    }
+
+    /// Queue a `Plotly.<method>(graph_div, ...args)` call for every open page,
+    /// dropping pages that have been closed.
+    fn broadcast(&mut self, method: &str, args: Value) {
+        let message = json!({ "method": method, "args": args }).to_string();
+        self.clients
+            .retain(|client| client.send(message.clone()).is_ok());
+    }
+}
+
+/// A plotly.js event sent by a page.
+#[derive(Deserialize)]
+struct EventMessage {
+    event: String,
+    #[serde(default)]
+    data: Value,
+}
+
+type Handler = Arc<Mutex<dyn FnMut(Value) + Send>>;
+
+/// The closures registered for plotly.js events, by event name.
+#[derive(Default)]
+struct Handlers(Vec<(&'static str, Handler)>);
+
+impl Handlers {
+    /// The handlers registered for `event`. They are called after releasing
+    /// the lock on the registry, so that they can register handlers too.
+    fn matching(&self, event: &str) -> Vec<Handler> {
+        self.0
+            .iter()
+            .filter(|(e, _)| *e == event)
+            .map(|(_, handler)| handler.clone())
+            .collect()
+    }
+}
+
+/// A local HTTP and WebSocket server showing a live-updating plot.
+///
+/// The server runs on background threads until the `PlotServer` is dropped.
+pub struct PlotServer {
+    http_addr: SocketAddr,
+    ws_addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    handlers: Arc<Mutex<Handlers>>,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl PlotServer {
+    /// Start a server showing `plot` on `addr`, without opening a browser.
+    /// The WebSocket used for updates listens on a free port of the same
+    /// interface.
+    ///
+    /// The page is only served to requests addressing the server by IP
+    /// address or as `localhost`, as a defense against DNS rebinding.
+    pub fn bind<A: ToSocketAddrs>(addr: A, plot: &Plot) -> Result<Self, Error> {
+        let http = TcpListener::bind(addr)?;
+        let http_addr = http.local_addr()?;
+        let ws = TcpListener::bind((http_addr.ip(), 0))?;
+        let ws_addr = ws.local_addr()?;
+
+        let state = Arc::new(Mutex::new(State::new(plot)?));
+        let handlers = Arc::new(Mutex::new(Handlers::default()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let js_scripts = Arc::new(Plot::offline_js_sources());
+
+        Self::spawn_listener(http, &shutdown, {
+            let state = state.clone();
+            move |stream| {
+                let _ = serve_page(stream, &state, &js_scripts, http_addr, ws_addr.port());
+            }
+        });
+        Self::spawn_listener(ws, &shutdown, {
+            let state = state.clone();
+            let handlers = handlers.clone();
+            let shutdown = shutdown.clone();
+            move |stream| {
+                let mut path = String::new();
+                // The error type is fixed by `tungstenite`'s `Callback` trait.
+                #[allow(clippy::result_large_err)]
+                let callback = |request: &Request, response: Response| {
+                    // Refuse pages of other sites, which could otherwise read
+                    // the figure or send fake events.
+                    if !is_page_origin(request, http_addr, ws_addr) {
+                        let mut refusal = ErrorResponse::new(Some("Forbidden origin".into()));
+                        *refusal.status_mut() = StatusCode::FORBIDDEN;
+                        return Err(refusal);
+                    }
+                    path = request.uri().path().to_string();
+                    Ok(response)
+                };
+                let Ok(mut client) = tungstenite::accept_hdr(stream, callback) else {
+                    return;
+                };
+                if path == EVENTS_PATH {
+                    receive_events(client, &handlers, &shutdown);
+                    return;
+                }
+                // Queue the current figure first, so that updates made between
+                // loading the page and connecting are not lost.
+                let (sender, receiver) = mpsc::channel();
+                {
+                    let mut state = state.lock().unwrap();
+                    // The server was dropped while accepting this page.
+                    if shutdown.load(Ordering::Relaxed) {
+                        return;
+                    }
+                    let Ok(figure) = state.encoded_figure() else {
+                        return;
+                    };
+                    let message = json!({ "method": "react", "args": [figure] });
+                    let _ = sender.send(message.to_string());
+                    state.clients.push(sender);
+                }
+                // Ends when the page disconnects or the server is dropped,
+                // which drops the sender.
+                for message in receiver {
+                    if client.send(Message::text(message)).is_err() {
+                        return;
+                    }
+                }
+                let _ = client.close(None);
+            }
+        });
+
+        Ok(Self {
+            http_addr,
+            ws_addr,
+            state,
+            handlers,
+            shutdown,
+        })
+    }
+
+    /// Accept connections on `listener`, handling each on its own thread,
+    /// until `shutdown` is set.
+    fn spawn_listener<F>(listener: TcpListener, shutdown: &Arc<AtomicBool>, handle: F)
+    where
+        F: Fn(TcpStream) + Send + Sync + 'static,
+    {
+        let shutdown = shutdown.clone();
+        let handle = Arc::new(handle);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if shutdown.load(Ordering::Relaxed) {
+                    break;
+                }
+                if let Ok(stream) = stream {
+                    let handle = handle.clone();
+                    thread::spawn(move || handle(stream));
+                }
+            }
+        });
+    }
+
+    /// The address of the page showing the plot.
+    pub fn url(&self) -> String {
+        format!("http://{}/", self.http_addr)
+    }
+
+    /// Open the page showing the plot in the default system browser.
+    pub fn open(&self) -> Result<(), Error> {
+        Plot::show_with_default_app(self.url())
+    }
+
+    /// Replace the figure shown on every open page with `plot`.
+    ///
+    /// Pages opened later show the latest figure as well. The figure is sent
+    /// in the background, so a slow page does not delay the caller.
+    pub fn update(&self, plot: &Plot) -> Result<(), Error> {
+        let new_state = State::new(plot)?;
+        let mut state = self.state.lock().unwrap();
+        state.figure = new_state.figure;
+        state.encoding = new_state.encoding;
+        let figure = state.encoded_figure()?;
+        state.broadcast("react", json!([figure]));
+        Ok(())
+    }
+
+    /// Call `handler` whenever a point is clicked on an open page
+    /// (`plotly_click`).
+    pub fn on_click<F>(&self, handler: F)
+    where
+        F: FnMut(ClickEvent) + Send + 'static,
+    {
+        self.on("plotly_click", handler);
+    }
+
+    /// Call `handler` whenever the mouse moves onto a point on an open page
+    /// (`plotly_hover`).
+    pub fn on_hover<F>(&self, handler: F)
+    where
+        F: FnMut(PointsEvent) + Send + 'static,
+    {
+        self.on("plotly_hover", handler);
+    }
+
+    /// Call `handler` whenever a box or lasso selection is completed on an
+    /// open page (`plotly_selected`).
+    pub fn on_selected<F>(&self, handler: F)
+    where
+        F: FnMut(SelectionEvent) + Send + 'static,
+    {
+        self.on("plotly_selected", handler);
+    }
+
+    /// Call `handler` whenever a selection is cleared on an open page
+    /// (`plotly_deselect`).
+    pub fn on_deselect<F>(&self, mut handler: F)
+    where
+        F: FnMut() + Send + 'static,
+    {
+        self.on("plotly_deselect", move |_: Value| handler());
+    }
+
+    /// Call `handler` whenever the layout changes on an open page, e.g. after
+    /// zooming or panning (`plotly_relayout`).
+    pub fn on_relayout<F>(&self, handler: F)
+    where
+        F: FnMut(RelayoutEvent) + Send + 'static,
+    {
+        self.on("plotly_relayout", handler);
+    }
+
+    /// Call `handler` whenever trace attributes change on an open page, e.g.
+    /// when toggling a trace from the legend (`plotly_restyle`).
+    pub fn on_restyle<F>(&self, handler: F)
+    where
+        F: FnMut(RestyleEvent) + Send + 'static,
+    {
+        self.on("plotly_restyle", handler);
+    }
+
+    /// Register `handler` for `event`, skipping payloads that do not
+    /// deserialize into `E`.
+    fn on<E, F>(&self, event: &'static str, mut handler: F)
+    where
+        E: DeserializeOwned,
+        F: FnMut(E) + Send + 'static,
+    {
+        let handler = move |data: Value| {
+            if let Ok(data) = serde_json::from_value(data) {
+                handler(data);
+            }
+        };
+        self.handlers
+            .lock()
+            .unwrap()
+            .0
+            .push((event, Arc::new(Mutex::new(handler))));
+    }
+
+    /// A handle for streaming points into the traces of the served plot. The
+    /// handle can be cloned and sent to other threads.
+    pub fn stream(&self) -> PlotStream {
+        PlotStream {
+            state: self.state.clone(),
+        }
+    }
+}
+
+/// A handle for streaming points into the traces of a plot served by a
+/// [`PlotServer`], obtained from [`PlotServer::stream`].
+///
+/// Only the new points are sent to the open pages. They are also added to the
+/// server's copy of the figure, so pages opened later show them as well.
+#[derive(Clone)]
+pub struct PlotStream {
+    state: Arc<Mutex<State>>,
+}
+
+impl PlotStream {
+    /// Append points to the end of traces, like `Plotly.extendTraces`.
+    ///
+    /// Returns [`Error::Stream`] without changing the plot if an index in
+    /// `update` is out of range of the plot's traces, an attribute path
+    /// crosses a value that is not an object, or a value failed to serialize.
+    pub fn extend(&self, update: &StreamUpdate) -> Result<(), Error> {
+        self.send("extendTraces", update, false)
+    }
+
+    /// Insert points at the start of traces, like `Plotly.prependTraces`.
+    ///
+    /// Fails like [`PlotStream::extend`].
+    pub fn prepend(&self, update: &StreamUpdate) -> Result<(), Error> {
+        self.send("prependTraces", update, true)
+    }
+
+    fn send(&self, method: &str, update: &StreamUpdate, prepend: bool) -> Result<(), Error> {
+        if update.is_empty() {
+            return Ok(());
+        }
+        let args = update.args()?;
+        let mut state = self.state.lock().unwrap();
+        update.apply(&mut state.figure, prepend)?;
+        state.broadcast(method, args);
+        Ok(())
+    }
+}
+
+impl Drop for PlotServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        // End the writer threads of the open pages, which close their sockets.
+        // `PlotStream` handles may keep the state alive.
+        if let Ok(mut state) = self.state.lock() {
+            state.clients.clear();
+        }
+        // Wake the listener threads blocked in `accept` so they can exit.
+        let _ = TcpStream::connect(self.http_addr);
+        let _ = TcpStream::connect(self.ws_addr);
+    }
+}
+
+/// Read the events sent by a page and call the matching handlers, until the
+/// page disconnects or the server is dropped.
+fn receive_events(
+    mut client: WebSocket<TcpStream>,
+    handlers: &Mutex<Handlers>,
+    shutdown: &AtomicBool,
+) {
+    if client
+        .get_ref()
+        .set_read_timeout(Some(EVENTS_POLL_INTERVAL))
+        .is_err()
+    {
+        return;
+    }
+    while !shutdown.load(Ordering::Relaxed) {
+        let text = match client.read() {
+            Ok(Message::Text(text)) => text,
+            Ok(_) => continue,
+            Err(tungstenite::Error::Io(e))
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) =>
+            {
+                continue
+            }
+            Err(_) => break,
+        };
+        let Ok(message) = serde_json::from_str::<EventMessage>(&text) else {
+            continue;
+        };
+        let matching = handlers.lock().unwrap().matching(&message.event);
+        for handler in matching {
+            // Skip handlers that panicked before.
+            if let Ok(mut handler) = handler.lock() {
+                (*handler)(message.data.clone());
+            }
+        }
+    }
+}
+
+/// Whether the WebSocket handshake `request` comes from the page served on
+/// `http_addr` and is addressed to `ws_addr`. Browsers always send the
+/// `Origin` of the page opening a WebSocket.
+fn is_page_origin(request: &Request, http_addr: SocketAddr, ws_addr: SocketAddr) -> bool {
+    let header = |name| {
+        request
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+    };
+    let origin = header("Origin").and_then(|origin| origin.strip_prefix("http://"));
+    origin.is_some_and(|origin| is_server_host(origin, http_addr))
+        && header("Host").is_some_and(|host| is_server_host(host, ws_addr))
+}
+
+/// Whether `host`, the `host:port` of a `Host` header or an origin, names the
+/// server listening on `addr`.
+///
+/// Only IP addresses and `localhost` are accepted, which keeps out pages of
+/// other sites whose domain was rebound to the server's address. A server
+/// bound to all interfaces accepts the address of any of them.
+fn is_server_host(host: &str, addr: SocketAddr) -> bool {
+    let Some((name, port)) = host.rsplit_once(':') else {
+        return false;
+    };
+    if port.parse() != Ok(addr.port()) {
+        return false;
+    }
+    let name = name.trim_start_matches('[').trim_end_matches(']');
+    let ip = addr.ip();
+    if name.eq_ignore_ascii_case("localhost") {
+        return ip.is_loopback() || ip.is_unspecified();
+    }
+    name.parse::<IpAddr>()
+        .is_ok_and(|name| ip.is_unspecified() || name == ip)
+}
+
+/// Serialize `plot` as a figure for `Plotly.react`, adding a `uirevision` to
+/// the layout unless it has one.
+fn figure(plot: &Plot) -> Result<Value, Error> {
+    let figure = Figure {
+        data: plot.data(),
+        layout: plot.layout(),
+        config: plot.configuration(),
+        frames: plot.frames(),
+    };
+    let mut figure = serde_json::to_value(figure).map_err(io::Error::from)?;
+    if let Some(layout) = figure["layout"].as_object_mut() {
+        layout
+            .entry("uirevision")
+            .or_insert_with(|| json!(UI_REVISION));
+    }
+    Ok(figure)
+}
+
+/// Read the request line and headers sent on `stream`, failing if they
+/// exceed [`MAX_REQUEST_HEAD`] or take longer than [`REQUEST_TIMEOUT`].
+fn read_request_head(mut stream: &TcpStream) -> io::Result<String> {
+    let deadline = Instant::now() + REQUEST_TIMEOUT;
+    let mut head = Vec::new();
+    let mut chunk = [0; 1024];
+    loop {
+        if let Some(end) = head.windows(4).position(|w| w == b"\r\n\r\n") {
+            head.truncate(end);
+            return Ok(String::from_utf8_lossy(&head).into_owned());
+        }
+        if head.len() > MAX_REQUEST_HEAD {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "request headers are too large",
+            ));
+        }
+        let remaining = deadline
+            .checked_duration_since(Instant::now())
+            .filter(|remaining| !remaining.is_zero())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::TimedOut, "request timed out"))?;
+        stream.set_read_timeout(Some(remaining))?;
+        let read = stream.read(&mut chunk)?;
+        if read == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        head.extend_from_slice(&chunk[..read]);
+    }
+}
+
+/// Answer a single HTTP request, serving the plot page at `/` to requests
+/// addressed to `http_addr`.
+fn serve_page(
+    stream: TcpStream,
+    state: &Mutex<State>,
+    js_scripts: &str,
+    http_addr: SocketAddr,
+    ws_port: u16,
+) -> io::Result<()> {
+    let head = read_request_head(&stream)?;
+    let mut lines = head.lines();
+    let request_line = lines.next().unwrap_or_default();
+    let host = lines.find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim()
+            .eq_ignore_ascii_case("host")
+            .then(|| value.trim())
+    });
+
+    let mut stream = &stream;
+    // Refuse pages of other sites whose domain was rebound to this address.
+    if !host.is_some_and(|host| is_server_host(host, http_addr)) {
+        return stream.write_all(
+            b"HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        );
+    }
+    if request_line.split_whitespace().nth(1) != Some("/") {
+        return stream.write_all(
+            b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        );
+    }
+
+    let figure = state
+        .lock()
+        .unwrap()
+        .encoded_figure()
+        .map_err(io::Error::other)?;
+    let tmpl = ServePlotTemplate {
+        figure: &figure,
+        js_scripts,
+        ws_port,
+    };
+    let body = tmpl.render().map_err(|e| io::Error::other(e.to_string()))?;
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+        body.len()
+    )?;
+    stream.write_all(body.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use tungstenite::{client::IntoClientRequest, HandshakeError};
+
+    use super::*;
+    use crate::{Layout, Scatter};
+
+    fn plot(name: &str) -> Plot {
+        let mut plot = Plot::new();
+        plot.add_trace(Scatter::new(vec![0, 1], vec![1, 0]).name(name));
+        plot
+    }
+
+    /// Open the WebSocket `path` of `server` from a page of `origin`.
+    fn connect(
+        server: &PlotServer,
+        path: &str,
+        origin: &str,
+    ) -> Result<WebSocket<TcpStream>, tungstenite::Error> {
+        let mut request = format!("ws://{}{path}", server.ws_addr)
+            .into_client_request()
+            .unwrap();
+        request
+            .headers_mut()
+            .insert("Origin", origin.parse().unwrap());
+        let stream = TcpStream::connect(server.ws_addr).unwrap();
+        match tungstenite::client(request, stream) {
+            Ok((client, _)) => Ok(client),
+            Err(HandshakeError::Failure(e)) => Err(e),
+            Err(HandshakeError::Interrupted(_)) => unreachable!("blocking handshake"),
+        }
+    }
+
+    fn page_origin(server: &PlotServer) -> String {
+        format!("http://{}", server.http_addr)
+    }
+
+    /// Request `path` from the page server of `server` with the `Host` header
+    /// `host`, returning the whole response.
+    fn get(server: &PlotServer, path: &str, host: &str) -> String {
+        let mut response = String::new();
+        let mut stream = TcpStream::connect(server.http_addr).unwrap();
+        write!(stream, "GET {path} HTTP/1.1\r\nHost: {host}\r\n\r\n").unwrap();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    fn read_message(client: &mut WebSocket<TcpStream>) -> Value {
+        let message = client.read().unwrap();
+        serde_json::from_str(message.to_text().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn figure_adds_ui_revision() {
+        let value = figure(&plot("a")).unwrap();
+        assert_eq!(value["layout"]["uirevision"], json!(UI_REVISION));
+
+        let mut custom = plot("a");
+        custom.set_layout(Layout::new().ui_revision("mine"));
+        let value = figure(&custom).unwrap();
+        assert_eq!(value["layout"]["uirevision"], json!("mine"));
+    }
+
+    #[test]
+    fn serve_page_and_push_updates() {
+        let server = PlotServer::bind("127.0.0.1:0", &plot("first")).unwrap();
+
+        let localhost = format!("localhost:{}", server.http_addr.port());
+        let page = get(&server, "/", &localhost);
+        assert!(page.starts_with("HTTP/1.1 200 OK"));
+        assert!(page.contains("\"name\":\"first\""));
+        assert!(page.contains(&format!(":{}\"", server.ws_addr.port())));
+
+        let response = get(&server, "/missing", &server.http_addr.to_string());
+        assert!(response.starts_with("HTTP/1.1 404 Not Found"));
+
+        let mut client = connect(&server, "/", &page_origin(&server)).unwrap();
+        let message = read_message(&mut client);
+        assert_eq!(message["method"], json!("react"));
+        assert_eq!(message["args"][0]["data"][0]["name"], json!("first"));
+
+        server.update(&plot("second")).unwrap();
+        let message = read_message(&mut client);
+        assert_eq!(message["method"], json!("react"));
+        assert_eq!(message["args"][0]["data"][0]["name"], json!("second"));
+        assert_eq!(
+            message["args"][0]["layout"]["uirevision"],
+            json!(UI_REVISION)
+        );
+
+        let stream = server.stream();
+        stream
+            .extend(&StreamUpdate::new().xy(0, &[2], &[2]).max_points(2))
+            .unwrap();
+        let message = read_message(&mut client);
+        assert_eq!(message["method"], json!("extendTraces"));
+        assert_eq!(message["args"], json!([{"x": [[2]], "y": [[2]]}, [0], 2]));
+
+        stream
+            .prepend(&StreamUpdate::new().values(0, "marker.color", &["red"]))
+            .unwrap();
+        assert!(stream
+            .extend(&StreamUpdate::new().values(3, "x", &[1]))
+            .is_err());
+        let message = read_message(&mut client);
+        assert_eq!(message["method"], json!("prependTraces"));
+        assert_eq!(message["args"], json!([{"marker.color": [["red"]]}, [0]]));
+
+        let trace = &server.state.lock().unwrap().figure["data"][0];
+        assert_eq!(trace["x"], json!([1, 2]));
+        assert_eq!(trace["y"], json!([0, 2]));
+        assert_eq!(trace["marker"]["color"], json!(["red"]));
+    }
+
+    #[test]
+    fn refuse_foreign_origins() {
+        let server = PlotServer::bind("127.0.0.1:0", &plot("secret")).unwrap();
+        for origin in ["http://evil.example", "null"] {
+            match connect(&server, "/", origin) {
+                Err(tungstenite::Error::Http(response)) => {
+                    assert_eq!(response.status(), StatusCode::FORBIDDEN)
+                }
+                other => panic!("unexpected handshake result: {:?}", other.map(|_| ())),
+            }
+        }
+
+        let localhost = format!("http://localhost:{}", server.http_addr.port());
+        let mut client = connect(&server, "/", &localhost).unwrap();
+        assert_eq!(read_message(&mut client)["method"], json!("react"));
+    }
+
+    #[test]
+    fn refuse_foreign_hosts() {
+        let server = PlotServer::bind("127.0.0.1:0", &plot("secret")).unwrap();
+        let port = server.http_addr.port();
+        for host in [
+            format!("evil.example:{port}"),
+            format!("127.0.0.2:{port}"),
+            "localhost".to_string(),
+        ] {
+            let response = get(&server, "/", &host);
+            assert!(response.starts_with("HTTP/1.1 403 Forbidden"), "{host}");
+            assert!(!response.contains("secret"));
+        }
+
+        let mut response = String::new();
+        let mut stream = TcpStream::connect(server.http_addr).unwrap();
+        stream.write_all(b"GET / HTTP/1.1\r\n\r\n").unwrap();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 403 Forbidden"));
+    }
+
+    #[test]
+    fn refuse_oversized_request_headers() {
+        let server = PlotServer::bind("127.0.0.1:0", &plot("a")).unwrap();
+        let mut stream = TcpStream::connect(server.http_addr).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        stream.write_all(b"GET / HTTP/1.1\r\nX-Padding: ").unwrap();
+        let padding = vec![b'a'; 1024];
+        // The server closes the connection once the limit is exceeded.
+        let written = (0..4 * MAX_REQUEST_HEAD / padding.len())
+            .take_while(|_| stream.write_all(&padding).is_ok())
+            .count();
+        assert!(written > MAX_REQUEST_HEAD / padding.len());
+        let mut response = Vec::new();
+        let _ = stream.read_to_end(&mut response);
+        assert!(response.is_empty());
+    }
+
+    #[test]
+    fn serve_on_all_interfaces() {
+        let server = PlotServer::bind("0.0.0.0:0", &plot("a")).unwrap();
+        let http_addr = SocketAddr::from(([127, 0, 0, 1], server.http_addr.port()));
+        let ws_addr = SocketAddr::from(([127, 0, 0, 1], server.ws_addr.port()));
+
+        let page = get(&server, "/", &http_addr.to_string());
+        assert!(page.starts_with("HTTP/1.1 200 OK"));
+
+        let mut request = format!("ws://{ws_addr}/").into_client_request().unwrap();
+        request
+            .headers_mut()
+            .insert("Origin", format!("http://{http_addr}").parse().unwrap());
+        let stream = TcpStream::connect(ws_addr).unwrap();
+        let (mut client, _) = tungstenite::client(request, stream).unwrap();
+        assert_eq!(read_message(&mut client)["method"], json!("react"));
+    }
+
+    #[test]
+    fn dispatch_events_to_handlers() {
+        let server = PlotServer::bind("127.0.0.1:0", &plot("a")).unwrap();
+        let (sender, receiver) = std::sync::mpsc::channel();
+        server.on_click({
+            let sender = sender.clone();
+            move |event| {
+                sender
+                    .send(format!("click {:?}", event.points[0].x))
+                    .unwrap()
+            }
+        });
+        server.on_relayout({
+            let sender = sender.clone();
+            move |event| {
+                sender
+                    .send(format!("relayout {:?}", event.axis_range("xaxis")))
+                    .unwrap()
+            }
+        });
+        server.on_deselect(move || sender.send("deselect".to_string()).unwrap());
+
+        let mut client = connect(&server, EVENTS_PATH, &page_origin(&server)).unwrap();
+        for message in [
+            json!({"event": "plotly_hover", "data": {"points": []}}),
+            json!({"event": "plotly_click", "data": {"points": [{"curveNumber": 0, "x": 1}]}}),
+            json!({"event": "plotly_click", "data": "not a click event"}),
+            json!({"event": "plotly_relayout", "data": {"xaxis.range": [0, 2]}}),
+            json!({"event": "plotly_deselect"}),
+        ] {
+            client.send(Message::text(message.to_string())).unwrap();
+        }
+
+        let received: Vec<String> = receiver.iter().take(3).collect();
+        assert_eq!(
+            received,
+            [
+                "click Some(Number(1.0))",
+                "relayout Some([Number(0.0), Number(2.0)])",
+                "deselect",
+            ]
+        );
+    }
+
+    #[test]
+    fn register_handlers_while_one_runs() {
+        let server = PlotServer::bind("127.0.0.1:0", &plot("a")).unwrap();
+        let (started_sender, started) = std::sync::mpsc::channel();
+        let (release_sender, release) = std::sync::mpsc::channel::<()>();
+        server.on_hover(move |_| {
+            started_sender.send(()).unwrap();
+            release.recv().unwrap();
+        });
+
+        let mut client = connect(&server, EVENTS_PATH, &page_origin(&server)).unwrap();
+        let hover = json!({"event": "plotly_hover", "data": {"points": []}});
+        client.send(Message::text(hover.to_string())).unwrap();
+        started.recv().unwrap();
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        server.on_click(move |_| sender.send(()).unwrap());
+        release_sender.send(()).unwrap();
+        let click = json!({"event": "plotly_click", "data": {"points": []}});
+        client.send(Message::text(click.to_string())).unwrap();
+        receiver.recv().unwrap();
+    }
+
+    #[test]
+    fn close_sockets_on_drop() {
+        let server = PlotServer::bind("127.0.0.1:0", &plot("a")).unwrap();
+        let mut events = connect(&server, EVENTS_PATH, &page_origin(&server)).unwrap();
+        let mut page = connect(&server, "/", &page_origin(&server)).unwrap();
+        assert_eq!(read_message(&mut page)["method"], json!("react"));
+        assert!(matches!(
+            connect(&server, EVENTS_PATH, "http://evil.example"),
+            Err(tungstenite::Error::Http(_))
+        ));
+        // Keeps the state, and so the page's sender, alive.
+        let _stream = server.stream();
+
+        drop(server);
+        for client in [&mut events, &mut page] {
+            client
+                .get_ref()
+                .set_read_timeout(Some(Duration::from_secs(5)))
+                .unwrap();
+            loop {
+                match client.read() {
+                    Err(tungstenite::Error::Io(e)) if e.kind() == io::ErrorKind::WouldBlock => {
+                        panic!("the socket was not closed")
+                    }
+                    Ok(Message::Close(_)) | Err(_) => break,
+                    Ok(_) => {}
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn encoded_figure_uses_plot_encoding() {
+        let mut plot = Plot::new();
+        plot.add_trace(Scatter::new((0..20).collect(), (0..20).collect()));
+        plot.set_data_encoding(DataEncoding::TypedArray);
+        let state = State::new(&plot).unwrap();
+        assert!(state.figure["data"][0]["x"].is_array());
+        assert!(state.encoded_figure().unwrap()["data"][0]["x"]["bdata"].is_string());
+    }
+}
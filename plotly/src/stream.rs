@@ -0,0 +1,295 @@
+//! Points streamed into the traces of a displayed plot.
+//!
+//! A [`StreamUpdate`] is sent with `PlotStream` from the `serve` feature, or
+//! with `bindings::extend_traces` and `bindings::prepend_traces` in a WASM
+//! context.
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+#[cfg(any(feature = "serve", target_family = "wasm"))]
+use crate::Error;
+#[cfg(doc)]
+use crate::Plot;
+
+/// Points to add to the traces of a plot, with the semantics of
+/// `Plotly.extendTraces` and `Plotly.prependTraces`.
+///
+/// Traces are addressed by their index in [`Plot::data`], in the order they
+/// were added to the plot.
+///
+/// A value that fails to serialize is reported when the update is sent.
+#[derive(Debug, Clone, Default)]
+pub struct StreamUpdate {
+    traces: Vec<(usize, Map<String, Value>)>,
+    max_points: Option<usize>,
+    /// The first serialization failure of the added values.
+    error: Option<String>,
+}
+
+impl StreamUpdate {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Add `values` to the data attribute `attribute` of the trace at
+    /// `index`, e.g. `"x"`, `"y"`, `"text"` or the dotted path
+    /// `"marker.color"`.
+    pub fn values<T: Serialize>(mut self, index: usize, attribute: &str, values: &[T]) -> Self {
+        let values = match values.iter().map(serde_json::to_value).collect() {
+            Ok(values) => values,
+            Err(e) => {
+                self.error
+                    .get_or_insert_with(|| format!("invalid value for '{attribute}': {e}"));
+                return self;
+            }
+        };
+        let position = match self.traces.iter().position(|(i, _)| *i == index) {
+            Some(position) => position,
+            None => {
+                self.traces.push((index, Map::new()));
+                self.traces.len() - 1
+            }
+        };
+        let attributes = &mut self.traces[position].1;
+        if let Value::Array(items) = attributes.entry(attribute).or_insert_with(|| json!([])) {
+            items.extend::<Vec<Value>>(values);
+        }
+        self
+    }
+
+    /// Add points with coordinates `x` and `y` to the trace at `index`.
+    pub fn xy<X: Serialize, Y: Serialize>(self, index: usize, x: &[X], y: &[Y]) -> Self {
+        self.values(index, "x", x).values(index, "y", y)
+    }
+
+    /// Whether no points have been added.
+    pub fn is_empty(&self) -> bool {
+        self.traces.is_empty() && self.error.is_none()
+    }
+
+    /// Keep at most `max_points` points in each updated attribute, dropping
+    /// the oldest points when extending and the newest when prepending. The
+    /// limit applies to the attribute in every trace of the update, also in
+    /// those that got no points for it.
+    pub fn max_points(mut self, max_points: usize) -> Self {
+        self.max_points = Some(max_points);
+        self
+    }
+
+    /// The `update`, `indices` and `maxPoints` arguments of
+    /// `Plotly.extendTraces` and `Plotly.prependTraces`. Every attribute
+    /// needs an array for each index, so attributes missing from a trace get
+    /// an empty one.
+    #[cfg(any(feature = "serve", target_family = "wasm"))]
+    pub(crate) fn args(&self) -> Result<Value, Error> {
+        if let Some(error) = &self.error {
+            return Err(Error::Stream(error.clone()));
+        }
+        let mut update = Map::new();
+        for attribute in self.attribute_names() {
+            let arrays = self
+                .traces
+                .iter()
+                .map(|(_, attributes)| attributes.get(attribute).cloned().unwrap_or(json!([])))
+                .collect();
+            update.insert(attribute.to_string(), Value::Array(arrays));
+        }
+        let indices: Vec<usize> = self.traces.iter().map(|(index, _)| *index).collect();
+        Ok(match self.max_points {
+            Some(max_points) => json!([update, indices, max_points]),
+            None => json!([update, indices]),
+        })
+    }
+
+    /// Add the points to the traces of `figure`, as `plotly.js` does. Like
+    /// the arguments sent to `plotly.js`, every listed trace takes every
+    /// attribute of the update, so `max_points` also trims attributes of a
+    /// trace that got no new points. The figure is left unchanged if any
+    /// trace or attribute cannot take them.
+    #[cfg(feature = "serve")]
+    pub(crate) fn apply(&self, figure: &mut Value, prepend: bool) -> Result<(), Error> {
+        if let Some(error) = &self.error {
+            return Err(Error::Stream(error.clone()));
+        }
+        let names = self.attribute_names();
+        for (index, _) in &self.traces {
+            let Some(trace) = figure["data"].get(*index) else {
+                return Err(Error::Stream(format!("no trace at index {index}")));
+            };
+            if let Some(attribute) = names.iter().find(|a| !is_settable(trace, a)) {
+                return Err(Error::Stream(format!(
+                    "'{attribute}' of the trace at index {index} is inside a value that is not an object"
+                )));
+            }
+        }
+
+        for (index, attributes) in &self.traces {
+            let trace = &mut figure["data"][*index];
+            for attribute in &names {
+                let target = attribute.split('.').fold(&mut *trace, |t, key| &mut t[key]);
+                if !target.is_array() {
+                    *target = json!([]);
+                }
+                let items = target.as_array_mut().unwrap();
+                let values = attributes
+                    .get(*attribute)
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .cloned();
+                if prepend {
+                    items.splice(0..0, values);
+                } else {
+                    items.extend(values);
+                }
+                if let Some(max_points) = self.max_points.filter(|max| items.len() > *max) {
+                    if prepend {
+                        items.truncate(max_points);
+                    } else {
+                        items.drain(..items.len() - max_points);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The attributes updated in any trace, in the order they were added.
+    #[cfg(any(feature = "serve", target_family = "wasm"))]
+    fn attribute_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        for (_, attributes) in &self.traces {
+            for attribute in attributes.keys() {
+                if !names.contains(&attribute.as_str()) {
+                    names.push(attribute);
+                }
+            }
+        }
+        names
+    }
+}
+
+/// Whether the dotted path `attribute` can be set in `trace`, i.e. all of its
+/// parents are objects or missing.
+#[cfg(feature = "serve")]
+fn is_settable(trace: &Value, attribute: &str) -> bool {
+    let mut value = trace;
+    for key in attribute.split('.') {
+        match value {
+            Value::Object(map) => match map.get(key) {
+                Some(child) => value = child,
+                None => return true,
+            },
+            Value::Null => return true,
+            _ => return false,
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stream_update_is_empty() {
+        assert!(StreamUpdate::new().max_points(1).is_empty());
+        assert!(!StreamUpdate::new().values(0, "x", &[1]).is_empty());
+    }
+
+    #[test]
+    #[cfg(any(feature = "serve", target_family = "wasm"))]
+    fn stream_update_keeps_serialization_error() {
+        use std::collections::HashMap;
+
+        let values = [HashMap::from([((1, 2), 3)])];
+        let update = StreamUpdate::new().values(0, "customdata", &values);
+        assert!(!update.is_empty());
+        assert!(matches!(update.args(), Err(Error::Stream(msg)) if msg.contains("customdata")));
+    }
+
+    #[test]
+    #[cfg(any(feature = "serve", target_family = "wasm"))]
+    fn stream_update_args() {
+        let update = StreamUpdate::new()
+            .xy(2, &[1.0, 2.0], &[3.0, 4.0])
+            .values(0, "y", &[5])
+            .values(2, "x", &[6.0]);
+        assert_eq!(
+            update.args().unwrap(),
+            json!([
+                {"x": [[1.0, 2.0, 6.0], []], "y": [[3.0, 4.0], [5]]},
+                [2, 0]
+            ])
+        );
+        assert_eq!(
+            StreamUpdate::new()
+                .values(0, "x", &[1])
+                .max_points(5)
+                .args()
+                .unwrap(),
+            json!([{"x": [[1]]}, [0], 5])
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serve")]
+    fn stream_update_apply_max_points() {
+        let mut figure = json!({"data": [{"x": [1, 2, 3]}, {}]});
+        let update = StreamUpdate::new()
+            .values(0, "x", &[4, 5])
+            .values(1, "y", &[1, 2, 3])
+            .max_points(3);
+
+        update.apply(&mut figure, false).unwrap();
+        assert_eq!(figure["data"][0]["x"], json!([3, 4, 5]));
+        assert_eq!(figure["data"][1]["y"], json!([1, 2, 3]));
+
+        update.apply(&mut figure, true).unwrap();
+        assert_eq!(figure["data"][0]["x"], json!([4, 5, 3]));
+        assert_eq!(figure["data"][1]["y"], json!([1, 2, 3]));
+    }
+
+    #[test]
+    #[cfg(feature = "serve")]
+    fn stream_update_apply_max_points_to_every_listed_trace() {
+        let mut figure = json!({"data": [{"x": [1, 2, 3, 4]}, {"x": [1, 2, 3, 4], "y": [5]}]});
+        StreamUpdate::new()
+            .values(0, "y", &[1])
+            .values(1, "x", &[5])
+            .max_points(2)
+            .apply(&mut figure, false)
+            .unwrap();
+        assert_eq!(figure["data"][0], json!({"x": [3, 4], "y": [1]}));
+        assert_eq!(figure["data"][1], json!({"x": [4, 5], "y": [5]}));
+    }
+
+    #[test]
+    #[cfg(feature = "serve")]
+    fn stream_update_apply_out_of_range() {
+        let mut figure = json!({"data": [{}]});
+        let result = StreamUpdate::new()
+            .values(1, "x", &[1])
+            .apply(&mut figure, false);
+        assert!(matches!(result, Err(Error::Stream(msg)) if msg == "no trace at index 1"));
+    }
+
+    #[test]
+    #[cfg(feature = "serve")]
+    fn stream_update_apply_invalid_path() {
+        let mut figure = json!({"data": [{"x": [1], "marker": 5}]});
+        let result = StreamUpdate::new()
+            .values(0, "x", &[2])
+            .values(0, "marker.color", &["red"])
+            .apply(&mut figure, false);
+        assert!(matches!(result, Err(Error::Stream(_))));
+        assert_eq!(figure, json!({"data": [{"x": [1], "marker": 5}]}));
+
+        StreamUpdate::new()
+            .values(0, "line.color", &["red"])
+            .apply(&mut figure, false)
+            .unwrap();
+        assert_eq!(figure["data"][0]["line"]["color"], json!(["red"]));
+    }
+}
@@ -1,8 +1,12 @@
 //! Contour trace
 
+#[cfg(feature = "plotly_ndarray")]
+use ndarray::{Array, Ix1, Ix2};
 use plotly_derive::FieldSetter;
 use serde::Serialize;
 
+#[cfg(feature = "plotly_ndarray")]
+use crate::ndarray::GridRow;
 use crate::{
     color::Color,
     common::{
@@ -245,6 +249,43 @@ where
     }
 }
 
+#[cfg(feature = "plotly_ndarray")]
+impl<Z> Contour<GridRow<Z>, f64, f64>
+where
+    Z: Serialize + Clone,
+{
+    /// Create a contour plot from a two dimensional `z` grid indexed by row
+    /// (`y`) and column (`x`). This function requires the `plotly_ndarray`
+    /// feature.
+    pub fn from_array_z(z: Array<Z, Ix2>) -> Box<Self> {
+        Box::new(Contour {
+            z: Some(GridRow::rows(z)),
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(feature = "plotly_ndarray")]
+impl<Z, X, Y> Contour<GridRow<Z>, X, Y>
+where
+    X: Serialize + Clone,
+    Y: Serialize + Clone,
+    Z: Serialize + Clone,
+{
+    /// Create a contour plot from the `x` and `y` axis coordinates and a two
+    /// dimensional `z` grid with one row per `y` value. The grid is serialized
+    /// directly from the array without being copied. This function requires
+    /// the `plotly_ndarray` feature.
+    pub fn from_array(x: Array<X, Ix1>, y: Array<Y, Ix1>, z: Array<Z, Ix2>) -> Box<Self> {
+        Box::new(Contour {
+            x: Some(private::vector_from(x)),
+            y: Some(private::vector_from(y)),
+            z: Some(GridRow::rows(z)),
+            ..Default::default()
+        })
+    }
+}
+
 impl<Z, X, Y> Contour<Z, X, Y>
 where
     X: Serialize + Clone,
@@ -683,4 +724,27 @@ mod tests {
 
         assert_eq!(to_value(trace).unwrap(), expected);
     }
+
+    #[test]
+    #[cfg(feature = "plotly_ndarray")]
+    fn serialize_contour_from_array() {
+        use ndarray::array;
+
+        let trace = Contour::from_array(
+            array![0, 1],
+            array![2.0, 3.0],
+            array![[4.0, 5.0], [6.0, 7.0]],
+        );
+        let expected = json!({
+            "type": "contour",
+            "x": [0, 1],
+            "y": [2.0, 3.0],
+            "z": [[4.0, 5.0], [6.0, 7.0]]
+        });
+        assert_eq!(to_value(trace).unwrap(), expected);
+
+        let trace = Contour::from_array_z(array![[1, 2], [3, 4]]);
+        let expected = json!({"type": "contour", "z": [[1, 2], [3, 4]]});
+        assert_eq!(to_value(trace).unwrap(), expected);
+    }
 }
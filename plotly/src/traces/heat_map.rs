@@ -1,8 +1,12 @@
 //! Heat map trace
 
+#[cfg(feature = "plotly_ndarray")]
+use ndarray::{Array, Ix1, Ix2};
 use plotly_derive::FieldSetter;
 use serde::Serialize;
 
+#[cfg(feature = "plotly_ndarray")]
+use crate::ndarray::GridRow;
 use crate::{
     common::{
         Calendar, ColorBar, ColorScale, Dim, HoverInfo, Label, LegendGroupTitle, PlotType, Visible,
@@ -159,6 +163,42 @@ where
     }
 }
 
+#[cfg(feature = "plotly_ndarray")]
+impl<Z> HeatMap<f64, f64, GridRow<Z>>
+where
+    Z: Serialize + Clone,
+{
+    /// Create a heatmap from a two dimensional `z` grid indexed by row (`y`)
+    /// and column (`x`). This function requires the `plotly_ndarray` feature.
+    pub fn from_array_z(z: Array<Z, Ix2>) -> Box<Self> {
+        Box::new(Self {
+            z: Some(GridRow::rows(z)),
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(feature = "plotly_ndarray")]
+impl<X, Y, Z> HeatMap<X, Y, GridRow<Z>>
+where
+    X: Serialize + Clone,
+    Y: Serialize + Clone,
+    Z: Serialize + Clone,
+{
+    /// Create a heatmap from the `x` and `y` axis coordinates and a two
+    /// dimensional `z` grid with one row per `y` value. The grid is serialized
+    /// directly from the array without being copied. This function requires
+    /// the `plotly_ndarray` feature.
+    pub fn from_array(x: Array<X, Ix1>, y: Array<Y, Ix1>, z: Array<Z, Ix2>) -> Box<Self> {
+        Box::new(Self {
+            x: Some(crate::private::vector_from(x)),
+            y: Some(crate::private::vector_from(y)),
+            z: Some(GridRow::rows(z)),
+            ..Default::default()
+        })
+    }
+}
+
 impl<X, Y, Z> Trace for HeatMap<X, Y, Z>
 where
     X: Serialize + Clone,
@@ -283,4 +323,27 @@ mod tests {
 
         assert_eq!(to_value(trace).unwrap(), expected);
     }
+
+    #[test]
+    #[cfg(feature = "plotly_ndarray")]
+    fn serialize_heat_map_from_array() {
+        use ndarray::array;
+
+        let trace = HeatMap::from_array(
+            array![0.0, 1.0, 2.0],
+            array!["a", "b"],
+            array![[1, 2, 3], [4, 5, 6]],
+        );
+        let expected = json!({
+            "type": "heatmap",
+            "x": [0.0, 1.0, 2.0],
+            "y": ["a", "b"],
+            "z": [[1, 2, 3], [4, 5, 6]]
+        });
+        assert_eq!(to_value(trace).unwrap(), expected);
+
+        let trace = HeatMap::from_array_z(array![[1, 2], [3, 4]]);
+        let expected = json!({"type": "heatmap", "z": [[1, 2], [3, 4]]});
+        assert_eq!(to_value(trace).unwrap(), expected);
+    }
 }
@@ -1,11 +1,14 @@
 //! Mesh plot
 
+#[cfg(feature = "plotly_ndarray")]
+use ndarray::{Array, Ix1, Ix2};
 use plotly_derive::FieldSetter;
 use serde::Serialize;
 
 use crate::common::{
-    color::Color, Calendar, ColorBar, ColorScale, Dim, HoverInfo, Label, LegendGroupTitle,
-    PlotType, Visible,
+    color::Color,
+    point::{split_points, split_triangles, Point3D, TriangleIndex},
+    Calendar, ColorBar, ColorScale, Dim, HoverInfo, Label, LegendGroupTitle, PlotType, Visible,
 };
 use crate::private::{NumOrString, NumOrStringCollection};
 use crate::Trace;
@@ -405,6 +408,81 @@ where
             ..Default::default()
         })
     }
+
+    /// Create a mesh from the vertex coordinates `x`, `y` and `z` and,
+    /// optionally, the vertex indices `i`, `j` and `k` of each triangle. This
+    /// function requires the `plotly_ndarray` feature.
+    #[cfg(feature = "plotly_ndarray")]
+    pub fn from_array(
+        x: Array<X, Ix1>,
+        y: Array<Y, Ix1>,
+        z: Array<Z, Ix1>,
+        i: Option<Array<usize, Ix1>>,
+        j: Option<Array<usize, Ix1>>,
+        k: Option<Array<usize, Ix1>>,
+    ) -> Box<Self> {
+        use crate::private::vector_from;
+
+        Box::new(Self {
+            x: Some(vector_from(x)),
+            y: Some(vector_from(y)),
+            z: Some(vector_from(z)),
+            i: i.map(vector_from),
+            j: j.map(vector_from),
+            k: k.map(vector_from),
+            ..Default::default()
+        })
+    }
+}
+
+impl<T> Mesh3D<T, T, T>
+where
+    T: Serialize + Default + Clone,
+{
+    /// Create a mesh from a slice of vertices without triangle indices, which
+    /// `plotly.js` then triangulates according to `alphahull` and
+    /// `delaunay_axis`.
+    pub fn from_points<P: Point3D<Scalar = T>>(points: &[P]) -> Box<Self> {
+        let [x, y, z] = split_points(points);
+        Self::new(x, y, z, None, None, None)
+    }
+
+    /// Create a mesh from a slice of vertices and the vertex indices of each
+    /// triangle, as found in index buffers of `u16`, `u32` or `usize`.
+    pub fn from_triangles<P, I>(points: &[P], triangles: &[[I; 3]]) -> Box<Self>
+    where
+        P: Point3D<Scalar = T>,
+        I: TriangleIndex,
+    {
+        let [x, y, z] = split_points(points);
+        let [i, j, k] = split_triangles(triangles);
+        Self::new(x, y, z, Some(i), Some(j), Some(k))
+    }
+
+    /// Create a mesh from a two dimensional array of vertices with one `x`,
+    /// `y`, `z` row per vertex and, optionally, an array of triangles with one
+    /// row of three vertex indices per triangle. This function requires the
+    /// `plotly_ndarray` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `vertices` or `triangles` does not have three columns.
+    #[cfg(feature = "plotly_ndarray")]
+    pub fn from_vertex_array(
+        vertices: Array<T, Ix2>,
+        triangles: Option<Array<usize, Ix2>>,
+    ) -> Box<Self> {
+        assert_eq!(vertices.ncols(), 3, "vertices must have x, y, z columns");
+        let [x, y, z] = [0, 1, 2].map(|axis| vertices.column(axis).to_vec());
+        match triangles {
+            Some(triangles) => {
+                assert_eq!(triangles.ncols(), 3, "triangles must have three columns");
+                let [i, j, k] = [0, 1, 2].map(|corner| triangles.column(corner).to_vec());
+                Self::new(x, y, z, Some(i), Some(j), Some(k))
+            }
+            None => Self::new(x, y, z, None, None, None),
+        }
+    }
 }
 
 impl<X, Y, Z> Trace for Mesh3D<X, Y, Z>
@@ -595,4 +673,82 @@ mod tests {
 
         assert_eq!(to_value(mesh3d).unwrap(), expected);
     }
+
+    #[test]
+    #[cfg(feature = "plotly_ndarray")]
+    fn serialize_mesh3d_from_array() {
+        use ndarray::array;
+
+        let trace = Mesh3D::from_array(
+            array![0.0, 1.0, 0.0],
+            array![0.0, 0.0, 1.0],
+            array![0.0, 0.0, 0.0],
+            Some(array![0]),
+            Some(array![1]),
+            Some(array![2]),
+        );
+        let expected = json!({
+            "type": "mesh3d",
+            "x": [0.0, 1.0, 0.0],
+            "y": [0.0, 0.0, 1.0],
+            "z": [0.0, 0.0, 0.0],
+            "i": [0],
+            "j": [1],
+            "k": [2]
+        });
+        assert_eq!(to_value(trace).unwrap(), expected);
+    }
+
+    #[test]
+    #[cfg(feature = "plotly_ndarray")]
+    fn serialize_mesh3d_from_vertex_array() {
+        use ndarray::array;
+
+        let vertices = array![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+        let trace = Mesh3D::from_vertex_array(vertices.clone(), Some(array![[0, 1, 2]]));
+        let expected = json!({
+            "type": "mesh3d",
+            "x": [0.0, 1.0, 0.0],
+            "y": [0.0, 0.0, 1.0],
+            "z": [0.0, 0.0, 0.0],
+            "i": [0],
+            "j": [1],
+            "k": [2]
+        });
+        assert_eq!(to_value(trace).unwrap(), expected);
+
+        let trace = Mesh3D::from_vertex_array(vertices, None);
+        assert_eq!(to_value(trace).unwrap()["x"], json!([0.0, 1.0, 0.0]));
+    }
+
+    #[test]
+    fn serialize_mesh3d_from_triangles() {
+        let points = [
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [1.0, 1.0, 0.0],
+        ];
+        let trace = Mesh3D::from_triangles(&points, &[[0u32, 1, 2], [1, 3, 2]]);
+        let expected = json!({
+            "type": "mesh3d",
+            "x": [0.0, 1.0, 0.0, 1.0],
+            "y": [0.0, 0.0, 1.0, 1.0],
+            "z": [0.0, 0.0, 0.0, 0.0],
+            "i": [0, 1],
+            "j": [1, 3],
+            "k": [2, 2]
+        });
+        assert_eq!(to_value(trace).unwrap(), expected);
+
+        let trace = Mesh3D::from_points(&[(0, 0, 0), (1, 0, 0)]).alpha_hull(0.0);
+        let expected = json!({
+            "type": "mesh3d",
+            "x": [0, 1],
+            "y": [0, 0],
+            "z": [0, 0],
+            "alphahull": 0.0
+        });
+        assert_eq!(to_value(trace).unwrap(), expected);
+    }
 }
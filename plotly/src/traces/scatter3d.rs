@@ -8,6 +8,7 @@ use serde::Serialize;
 use crate::{
     color::Color,
     common::{
+        point::{split_points, Point3D},
         Calendar, Dim, ErrorData, HoverInfo, Label, LegendGroupTitle, Line, Marker, Mode, PlotType,
         Position, Visible,
     },
@@ -292,14 +293,26 @@ where
     pub fn from_array(x: Array<X, Ix1>, y: Array<Y, Ix1>, z: Array<Z, Ix1>) -> Box<Self> {
         Box::new(Scatter3D {
             r#type: PlotType::Scatter3D,
-            x: Some(x.to_vec()),
-            y: Some(y.to_vec()),
-            z: Some(z.to_vec()),
+            x: Some(private::vector_from(x)),
+            y: Some(private::vector_from(y)),
+            z: Some(private::vector_from(z)),
             ..Default::default()
         })
     }
 }
 
+impl<T> Scatter3D<T, T, T>
+where
+    T: Serialize + Default + Clone,
+{
+    /// Create a trace from a slice of points, e.g. `[f64; 3]` arrays or, with
+    /// the `nalgebra` and `glam` features, `Point3`, `Vector3` and `Vec3`.
+    pub fn from_points<P: Point3D<Scalar = T>>(points: &[P]) -> Box<Self> {
+        let [x, y, z] = split_points(points);
+        Self::new(x, y, z)
+    }
+}
+
 impl<X, Y, Z> Trace for Scatter3D<X, Y, Z>
 where
     X: Serialize + Clone,
@@ -439,4 +452,16 @@ mod tests {
 
         assert_eq!(to_value(trace).unwrap(), expected);
     }
+
+    #[test]
+    fn serialize_scatter3d_from_points() {
+        let trace = Scatter3D::from_points(&[[0.0, 1.0, 2.0], [3.0, 4.0, 5.0]]);
+        let expected = json!({
+            "type": "scatter3d",
+            "x": [0.0, 3.0],
+            "y": [1.0, 4.0],
+            "z": [2.0, 5.0]
+        });
+        assert_eq!(to_value(trace).unwrap(), expected);
+    }
 }
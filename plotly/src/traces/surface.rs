@@ -1,8 +1,12 @@
 //! Surface trace
 
+#[cfg(feature = "plotly_ndarray")]
+use ndarray::{Array, Ix1, Ix2};
 use plotly_derive::FieldSetter;
 use serde::Serialize;
 
+#[cfg(feature = "plotly_ndarray")]
+use crate::ndarray::GridRow;
 use crate::{
     color::Color,
     common::{
@@ -93,6 +97,27 @@ impl SurfaceContours {
     }
 }
 
+/// The `z` grid of a [`Surface`], with one row per `y` value.
+///
+/// Nested `Vec`s convert into a grid, so they can be passed to
+/// [`Surface::z`] directly.
+#[derive(Serialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum SurfaceGrid<Z: Clone> {
+    /// Nested rows of values.
+    Rows(Vec<Vec<Z>>),
+    /// The rows of a two dimensional array, serialized without copying. This
+    /// variant requires the `plotly_ndarray` feature.
+    #[cfg(feature = "plotly_ndarray")]
+    Array(Vec<GridRow<Z>>),
+}
+
+impl<Z: Clone> From<Vec<Vec<Z>>> for SurfaceGrid<Z> {
+    fn from(rows: Vec<Vec<Z>>) -> Self {
+        Self::Rows(rows)
+    }
+}
+
 /// Construct a surface trace.
 ///
 /// # Examples
@@ -123,7 +148,8 @@ where
     r#type: PlotType,
     x: Option<Vec<X>>,
     y: Option<Vec<Y>>,
-    z: Option<Vec<Vec<Z>>>,
+    #[field_setter(into)]
+    z: Option<SurfaceGrid<Z>>,
     #[serde(rename = "autocolorscale")]
     auto_color_scale: Option<bool>,
     cauto: Option<bool>,
@@ -184,7 +210,33 @@ where
 {
     pub fn new(z: Vec<Vec<Z>>) -> Box<Self> {
         Box::new(Self {
-            z: Some(z),
+            z: Some(SurfaceGrid::Rows(z)),
+            ..Default::default()
+        })
+    }
+
+    /// Create a surface from a two dimensional `z` grid indexed by row (`y`)
+    /// and column (`x`). The grid is serialized directly from the array
+    /// without being copied. This function requires the `plotly_ndarray`
+    /// feature.
+    #[cfg(feature = "plotly_ndarray")]
+    pub fn from_array_z(z: Array<Z, Ix2>) -> Box<Self> {
+        Box::new(Self {
+            z: Some(SurfaceGrid::Array(GridRow::rows(z))),
+            ..Default::default()
+        })
+    }
+
+    /// Create a surface from the `x` and `y` axis coordinates and a two
+    /// dimensional `z` grid with one row per `y` value. The grid is serialized
+    /// directly from the array without being copied. This function requires
+    /// the `plotly_ndarray` feature.
+    #[cfg(feature = "plotly_ndarray")]
+    pub fn from_array(x: Array<X, Ix1>, y: Array<Y, Ix1>, z: Array<Z, Ix2>) -> Box<Self> {
+        Box::new(Self {
+            x: Some(crate::private::vector_from(x)),
+            y: Some(crate::private::vector_from(y)),
+            z: Some(SurfaceGrid::Array(GridRow::rows(z))),
             ..Default::default()
         })
     }
@@ -384,4 +436,23 @@ mod tests {
 
         assert_eq!(to_value(trace).unwrap(), expected);
     }
+
+    #[test]
+    #[cfg(feature = "plotly_ndarray")]
+    fn serialize_surface_from_array() {
+        use ndarray::array;
+
+        let trace = Surface::from_array(array![0, 1], array![2, 3], array![[4, 5], [6, 7]]);
+        let expected = json!({
+            "type": "surface",
+            "x": [0, 1],
+            "y": [2, 3],
+            "z": [[4, 5], [6, 7]]
+        });
+        assert_eq!(to_value(trace).unwrap(), expected);
+
+        let trace = Surface::<f64, f64, _>::from_array_z(array![[1, 2], [3, 4]]);
+        let expected = json!({"type": "surface", "z": [[1, 2], [3, 4]]});
+        assert_eq!(to_value(trace).unwrap(), expected);
+    }
 }
@@ -0,0 +1,386 @@
+//! Rust source emission for the attribute tree parsed in [`crate::schema`].
+//!
+//! Every attribute container becomes a struct deriving `FieldSetter`, every
+//! enumerated attribute with string values becomes an enum and every leaf
+//! attribute becomes an `Option<_>` field, wrapped in `Dim<_>` when plotly.js
+//! accepts per-point values (`arrayOk`).
+
+use std::collections::HashSet;
+
+use serde_json::Value;
+
+use crate::naming::{doc_comment, field_ident, pascal_case};
+use crate::schema::{Attribute, Node, Object, ValType};
+
+const GENERATED_NOTICE: &str =
+    "//! This file is generated by `plotly_codegen` from plotly.js `plot-schema.json`.\n\
+     //! Do not edit it by hand; re-run the generator instead.\n";
+
+/// The flavour of struct being emitted, mapped onto `#[field_setter(kind)]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum StructKind {
+    Trace,
+    Layout,
+    Nested,
+}
+
+/// Accumulates the items of a single generated Rust module.
+#[derive(Default)]
+pub(crate) struct Module {
+    items: Vec<String>,
+    type_names: HashSet<String>,
+    uses_color: bool,
+    uses_dim: bool,
+    uses_color_scale: bool,
+    uses_num_or_string: bool,
+    uses_collection: bool,
+}
+
+impl Module {
+    /// Render the module, including the generated-file notice and the `use`
+    /// declarations required by its items.
+    pub(crate) fn render(&self, title: &str, is_trace: bool) -> String {
+        let mut out = format!("//! {title}\n//!\n{GENERATED_NOTICE}\n");
+
+        out.push_str("use plotly_derive::FieldSetter;\nuse serde::Serialize;\n\n");
+
+        let mut common = Vec::new();
+        if self.uses_color_scale {
+            common.push("ColorScale");
+        }
+        if self.uses_dim {
+            common.push("Dim");
+        }
+        let mut private = Vec::new();
+        if self.uses_num_or_string {
+            private.push("NumOrString");
+        }
+        if self.uses_collection {
+            private.push("NumOrStringCollection");
+        }
+
+        let mut crate_uses = Vec::new();
+        if self.uses_color {
+            crate_uses.push("color::Color".to_string());
+        }
+        match common.as_slice() {
+            [] => {}
+            [one] => crate_uses.push(format!("common::{one}")),
+            many => crate_uses.push(format!("common::{{{}}}", many.join(", "))),
+        }
+        match private.as_slice() {
+            [] => {}
+            [one] => crate_uses.push(format!("private::{one}")),
+            many => crate_uses.push(format!("private::{{{}}}", many.join(", "))),
+        }
+        if is_trace {
+            crate_uses.push("Trace".to_string());
+        }
+        match crate_uses.as_slice() {
+            [] => {}
+            [one] => out.push_str(&format!("use crate::{one};\n")),
+            many => out.push_str(&format!("use crate::{{{}}};\n", many.join(", "))),
+        }
+
+        for item in &self.items {
+            out.push('\n');
+            out.push_str(item);
+        }
+        out
+    }
+
+    /// Reserve a unique type name within the module.
+    fn unique_type_name(&mut self, name: String) -> String {
+        let mut candidate = name.clone();
+        let mut suffix = 2;
+        while !self.type_names.insert(candidate.clone()) {
+            candidate = format!("{name}{suffix}");
+            suffix += 1;
+        }
+        candidate
+    }
+
+    /// Emit a struct for `object` and, recursively, for all nested containers.
+    /// Returns the name of the emitted struct.
+    pub(crate) fn emit_struct(
+        &mut self,
+        type_name: &str,
+        object: &Object,
+        kind: StructKind,
+        trace_type: Option<&str>,
+    ) -> String {
+        let type_name = self.unique_type_name(type_name.to_owned());
+        let mut fields = String::new();
+
+        if let Some(trace_type) = trace_type {
+            fields.push_str(&format!(
+                "    #[field_setter(default = \"\\\"{trace_type}\\\".to_string()\")]\n    r#type: String,\n"
+            ));
+        }
+
+        for node in &object.children {
+            let (name, description, ty) = match node {
+                Node::Attribute(attribute) => (
+                    &attribute.name,
+                    &attribute.description,
+                    self.attribute_type(&type_name, attribute),
+                ),
+                Node::Object(child) => {
+                    let child_ty = self.emit_struct(
+                        &format!("{type_name}{}", pascal_case(&child.name)),
+                        child,
+                        StructKind::Nested,
+                        None,
+                    );
+                    (&child.name, &child.description, child_ty)
+                }
+                Node::Array(child) => {
+                    let child_ty = self.emit_struct(
+                        &format!("{type_name}{}", pascal_case(&child.name)),
+                        child,
+                        StructKind::Nested,
+                        None,
+                    );
+                    (&child.name, &child.description, format!("Vec<{child_ty}>"))
+                }
+            };
+
+            if let Some(description) = description {
+                fields.push_str(&doc_comment(description, 4));
+            }
+            let ident = field_ident(name);
+            if ident.trim_start_matches("r#") != name {
+                fields.push_str(&format!("    #[serde(rename = \"{name}\")]\n"));
+            }
+            fields.push_str(&format!("    {ident}: Option<{ty}>,\n"));
+        }
+
+        let mut item = String::new();
+        if let Some(description) = &object.description {
+            item.push_str(&doc_comment(description, 0));
+        }
+        item.push_str("#[serde_with::skip_serializing_none]\n");
+        item.push_str("#[derive(Serialize, Clone, Debug, FieldSetter)]\n");
+        match kind {
+            StructKind::Trace => item.push_str("#[field_setter(box_self, kind = \"trace\")]\n"),
+            StructKind::Layout => item.push_str("#[field_setter(kind = \"layout\")]\n"),
+            StructKind::Nested => {}
+        }
+        item.push_str(&format!("pub struct {type_name} {{\n{fields}}}\n"));
+
+        match kind {
+            StructKind::Trace => item.push_str(&format!(
+                "\nimpl {type_name} {{\n    pub fn new() -> Box<Self> {{\n        Box::default()\n    }}\n}}\n\
+                 \nimpl Trace for {type_name} {{\n    fn to_json(&self) -> String {{\n        serde_json::to_string(self).unwrap()\n    }}\n}}\n"
+            )),
+            StructKind::Layout | StructKind::Nested => item.push_str(&format!(
+                "\nimpl {type_name} {{\n    pub fn new() -> Self {{\n        Default::default()\n    }}\n}}\n"
+            )),
+        }
+
+        self.items.push(item);
+        type_name
+    }
+
+    /// The Rust type used for a leaf attribute, without the outer `Option`.
+    fn attribute_type(&mut self, owner: &str, attribute: &Attribute) -> String {
+        let inner = match &attribute.val_type {
+            ValType::DataArray => {
+                self.uses_collection = true;
+                return "NumOrStringCollection".to_string();
+            }
+            ValType::Number | ValType::Angle => "f64".to_string(),
+            ValType::Integer => "i64".to_string(),
+            ValType::Boolean => "bool".to_string(),
+            ValType::String | ValType::SubplotId | ValType::FlagList => "String".to_string(),
+            ValType::Color => {
+                self.uses_color = true;
+                "Box<dyn Color>".to_string()
+            }
+            ValType::ColorList => {
+                self.uses_color = true;
+                return "Vec<Box<dyn Color>>".to_string();
+            }
+            ValType::ColorScale => {
+                self.uses_color_scale = true;
+                "ColorScale".to_string()
+            }
+            ValType::InfoArray => {
+                self.uses_num_or_string = true;
+                "Vec<NumOrString>".to_string()
+            }
+            ValType::Enumerated(values) => self.emit_enum(owner, attribute, values),
+            ValType::Any => "serde_json::Value".to_string(),
+        };
+
+        if attribute.array_ok {
+            self.uses_dim = true;
+            format!("Dim<{inner}>")
+        } else {
+            inner
+        }
+    }
+
+    /// Emit an enum for an enumerated attribute whose values are all strings.
+    /// Enumerations mixing booleans or numbers fall back to
+    /// `serde_json::Value`.
+    ///
+    /// Values written as `/regex/` accept any string matching the regex, such
+    /// as the axis ids `x2`, `x3`, ... They become an untagged `Other(String)`
+    /// variant, or a plain `String` when all values are regexes.
+    fn emit_enum(&mut self, owner: &str, attribute: &Attribute, values: &[Value]) -> String {
+        let strings: Option<Vec<&str>> = values.iter().map(Value::as_str).collect();
+        let strings = match strings {
+            Some(strings) if !strings.is_empty() => strings,
+            _ => return "serde_json::Value".to_string(),
+        };
+        let (patterns, strings): (Vec<&str>, Vec<&str>) =
+            strings.into_iter().partition(|value| is_regex(value));
+        if strings.is_empty() {
+            return "String".to_string();
+        }
+
+        let type_name = self.unique_type_name(format!("{owner}{}", pascal_case(&attribute.name)));
+        let mut variants = String::new();
+        let mut seen = HashSet::new();
+        let mut unique_variant = |name: String| {
+            let mut variant = name.clone();
+            let mut suffix = 2;
+            while !seen.insert(variant.clone()) {
+                variant = format!("{name}{suffix}");
+                suffix += 1;
+            }
+            variant
+        };
+        for value in strings {
+            let variant = unique_variant(pascal_case(value));
+            let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+            variants.push_str(&format!(
+                "    #[serde(rename = \"{value}\")]\n    {variant},\n"
+            ));
+        }
+        if !patterns.is_empty() {
+            let variant = unique_variant("Other".to_string());
+            for pattern in patterns {
+                let pattern = &pattern[1..pattern.len() - 1];
+                variants.push_str(&format!("    /// A value matching `{pattern}`.\n"));
+            }
+            variants.push_str(&format!("    #[serde(untagged)]\n    {variant}(String),\n"));
+        }
+
+        let mut item = String::new();
+        if let Some(description) = &attribute.description {
+            item.push_str(&doc_comment(description, 0));
+        }
+        item.push_str("#[derive(Serialize, Clone, Debug, PartialEq, Eq)]\n");
+        item.push_str(&format!("pub enum {type_name} {{\n{variants}}}\n"));
+        self.items.push(item);
+        type_name
+    }
+}
+
+/// Whether the enumerated `value` is a `/regex/` rather than a literal.
+fn is_regex(value: &str) -> bool {
+    value.len() > 1 && value.starts_with('/') && value.ends_with('/')
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::schema::Schema;
+
+    fn scatter_module() -> String {
+        let schema = Schema::parse(&json!({
+            "traces": {
+                "scatter": {
+                    "attributes": {
+                        "type": "scatter",
+                        "hoverinfo": {"valType": "flaglist", "arrayOk": true},
+                        "x": {"valType": "data_array"},
+                        "fill": {
+                            "valType": "enumerated",
+                            "values": ["none", "tozeroy", "toself"],
+                            "description": "Sets the area to fill."
+                        },
+                        "visible": {"valType": "enumerated", "values": [true, false, "legendonly"]},
+                        "xaxis": {"valType": "enumerated", "values": ["/^x([2-9]|[1-9][0-9]+)?$/"]},
+                        "yaxis": {"valType": "enumerated", "values": ["other", "/^y([2-9]|[1-9][0-9]+)?$/"]},
+                        "marker": {
+                            "role": "object",
+                            "color": {"valType": "color", "arrayOk": true},
+                            "line": {"width": {"valType": "number"}}
+                        }
+                    }
+                }
+            },
+            "layout": {"layoutAttributes": {}}
+        }))
+        .unwrap();
+
+        let trace = &schema.traces[0];
+        let mut module = Module::default();
+        module.emit_struct(
+            "Scatter",
+            &trace.attributes,
+            StructKind::Trace,
+            Some("scatter"),
+        );
+        module.render("Scatter trace", true)
+    }
+
+    #[test]
+    fn emit_trace_struct() {
+        let code = scatter_module();
+        assert!(code.contains("#[field_setter(box_self, kind = \"trace\")]\npub struct Scatter {"));
+        assert!(code.contains(
+            "#[field_setter(default = \"\\\"scatter\\\".to_string()\")]\n    r#type: String,"
+        ));
+        assert!(code.contains("hoverinfo: Option<Dim<String>>,"));
+        assert!(code.contains("x: Option<NumOrStringCollection>,"));
+        assert!(code.contains("visible: Option<serde_json::Value>,"));
+        assert!(code.contains("marker: Option<ScatterMarker>,"));
+        assert!(code.contains("impl Trace for Scatter {"));
+    }
+
+    #[test]
+    fn emit_nested_structs_and_enums() {
+        let code = scatter_module();
+        assert!(code.contains("pub struct ScatterMarker {"));
+        assert!(code.contains("color: Option<Dim<Box<dyn Color>>>,"));
+        assert!(code.contains("line: Option<ScatterMarkerLine>,"));
+        assert!(code.contains("pub struct ScatterMarkerLine {\n    width: Option<f64>,\n}"));
+        assert!(code.contains(
+            "/// Sets the area to fill.\n#[derive(Serialize, Clone, Debug, PartialEq, Eq)]\npub enum ScatterFill {"
+        ));
+        assert!(code.contains("    #[serde(rename = \"tozeroy\")]\n    Tozeroy,"));
+    }
+
+    #[test]
+    fn regex_enum_values_accept_strings() {
+        let code = scatter_module();
+        assert!(code.contains("xaxis: Option<String>,"));
+        assert!(code.contains("yaxis: Option<ScatterYaxis>,"));
+        assert!(code.contains(concat!(
+            "    #[serde(rename = \"other\")]\n    Other,\n",
+            "    /// A value matching `^y([2-9]|[1-9][0-9]+)?$`.\n",
+            "    #[serde(untagged)]\n    Other2(String),\n"
+        )));
+    }
+
+    #[test]
+    fn emit_imports() {
+        let code = scatter_module();
+        assert!(code.contains(
+            "use crate::{color::Color, common::Dim, private::NumOrStringCollection, Trace};"
+        ));
+    }
+
+    #[test]
+    fn duplicate_type_names_are_suffixed() {
+        let mut module = Module::default();
+        assert_eq!(module.unique_type_name("Title".into()), "Title");
+        assert_eq!(module.unique_type_name("Title".into()), "Title2");
+    }
+}
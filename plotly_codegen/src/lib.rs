@@ -0,0 +1,228 @@
+//! # Plotly Codegen
+//!
+//! Generates `plotly` trace and layout structs from the plotly.js
+//! `plot-schema.json`, so that attribute coverage tracks the bundled plotly.js
+//! version instead of lagging behind it.
+//!
+//! The generator reads a `plot-schema.json`, which plotly.js publishes in the
+//! `dist` directory of every release, and emits one module per trace type
+//! plus a `layout` module. Every attribute container becomes a struct deriving
+//! `plotly_derive::FieldSetter`, every enumerated attribute becomes an enum and
+//! the plotly.js attribute descriptions are carried over as doc comments.
+//!
+//! ## Usage
+//!
+//! Use the schema of the plotly.js version bundled with `plotly`:
+//!
+//! ```bash
+//! curl -o plot-schema.json https://raw.githubusercontent.com/plotly/plotly.js/v3.6.0/dist/plot-schema.json
+//! cargo run -p plotly_codegen -- plot-schema.json plotly/src/generated
+//! ```
+//!
+//! The generated sources expect to live inside the `plotly` crate, as they
+//! refer to `crate::common`, `crate::color` and `crate::private`.
+//!
+//! ## Testing
+//!
+//! The output for the small schema in `tests/fixtures/plot-schema.json` is
+//! committed as `plotly/src/codegen_fixture` and compiled with the `plotly`
+//! tests, so that the emitted code is checked against the real `FieldSetter`
+//! derive and `plotly` types. A test of this crate fails when that output is
+//! stale; regenerate it with
+//!
+//! ```bash
+//! PLOTLY_CODEGEN_UPDATE_FIXTURE=1 cargo test -p plotly_codegen
+//! ```
+
+use std::path::PathBuf;
+
+use serde_json::Value;
+
+mod emit;
+mod naming;
+mod schema;
+
+use emit::{Module, StructKind};
+use naming::pascal_case;
+use schema::{Node, Schema};
+
+const ROOT_MODULE: &str = "\
+//! Trace and layout types generated from the plotly.js `plot-schema.json`
+//!
+//! This file is generated by `plotly_codegen`. Do not edit it by hand.
+
+pub mod layout;
+pub mod traces;
+";
+
+/// A generated Rust source file, relative to the output directory.
+#[derive(Debug, Clone)]
+pub struct GeneratedFile {
+    pub path: PathBuf,
+    pub contents: String,
+}
+
+/// Generate the Rust sources for all traces and the layout described by a
+/// parsed `plot-schema.json`.
+///
+/// Returns an error if the schema does not have the expected `traces` and
+/// `layout.layoutAttributes` sections.
+pub fn generate(schema: &Value) -> Result<Vec<GeneratedFile>, String> {
+    let schema = Schema::parse(schema)?;
+    let mut files = Vec::new();
+    let mut trace_modules = Vec::new();
+
+    for trace in &schema.traces {
+        let type_name = pascal_case(&trace.name);
+        let mut attributes = trace.attributes.clone();
+        attributes.description = trace.description.clone();
+
+        let mut module = Module::default();
+        module.emit_struct(
+            &type_name,
+            &attributes,
+            StructKind::Trace,
+            Some(&trace.name),
+        );
+        files.push(GeneratedFile {
+            path: PathBuf::from("traces").join(format!("{}.rs", trace.name)),
+            contents: module.render(&format!("{type_name} trace"), true),
+        });
+        trace_modules.push((trace.name.clone(), type_name));
+    }
+
+    let mut traces_mod = String::from(
+        "//! Generated trace types\n//!\n//! This file is generated by `plotly_codegen`. Do not edit it by hand.\n\n",
+    );
+    for (module, _) in &trace_modules {
+        traces_mod.push_str(&format!("pub mod {module};\n"));
+    }
+    traces_mod.push('\n');
+    for (module, type_name) in &trace_modules {
+        traces_mod.push_str(&format!("pub use {module}::{type_name};\n"));
+    }
+    files.push(GeneratedFile {
+        path: PathBuf::from("traces").join("mod.rs"),
+        contents: traces_mod,
+    });
+
+    // Trace specific layout attributes, e.g. `barmode`, live on the layout.
+    let mut layout = schema.layout.clone();
+    for trace in &schema.traces {
+        if let Some(extra) = &trace.layout_attributes {
+            for node in &extra.children {
+                if !layout
+                    .children
+                    .iter()
+                    .any(|n| node_name(n) == node_name(node))
+                {
+                    layout.children.push(node.clone());
+                }
+            }
+        }
+    }
+    let mut module = Module::default();
+    module.emit_struct("Layout", &layout, StructKind::Layout, None);
+    files.push(GeneratedFile {
+        path: PathBuf::from("layout.rs"),
+        contents: module.render("Layout", false),
+    });
+
+    files.push(GeneratedFile {
+        path: PathBuf::from("mod.rs"),
+        contents: ROOT_MODULE.to_string(),
+    });
+
+    Ok(files)
+}
+
+fn node_name(node: &Node) -> &str {
+    match node {
+        Node::Attribute(attribute) => &attribute.name,
+        Node::Object(object) | Node::Array(object) => &object.name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn generate_files() {
+        let schema = json!({
+            "traces": {
+                "bar": {
+                    "attributes": {"type": "bar", "width": {"valType": "number", "arrayOk": true}},
+                    "layoutAttributes": {
+                        "barmode": {"valType": "enumerated", "values": ["stack", "group"]}
+                    }
+                }
+            },
+            "layout": {
+                "layoutAttributes": {
+                    "title": {"text": {"valType": "string"}},
+                    "barmode": {"valType": "enumerated", "values": ["stack", "group"]}
+                }
+            }
+        });
+
+        let files = generate(&schema).unwrap();
+        let paths: Vec<_> = files.iter().map(|f| f.path.clone()).collect();
+        assert_eq!(
+            paths,
+            [
+                PathBuf::from("traces/bar.rs"),
+                PathBuf::from("traces/mod.rs"),
+                PathBuf::from("layout.rs"),
+                PathBuf::from("mod.rs"),
+            ]
+        );
+
+        assert!(files[0].contents.contains("pub struct Bar {"));
+        assert!(files[1]
+            .contents
+            .contains("pub mod bar;\n\npub use bar::Bar;\n"));
+
+        let layout = &files[2].contents;
+        assert!(layout.contains("#[field_setter(kind = \"layout\")]\npub struct Layout {"));
+        assert!(layout.contains("title: Option<LayoutTitle>,"));
+        assert_eq!(layout.matches("barmode: Option<LayoutBarmode>,").count(), 1);
+    }
+
+    #[test]
+    fn generate_rejects_invalid_schema() {
+        assert!(generate(&json!({})).is_err());
+    }
+
+    /// The generated code is only compiled as part of `plotly`, so check that
+    /// the copy there matches the current generator.
+    #[test]
+    fn fixture_output_is_up_to_date() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let schema = fs::read_to_string(root.join("tests/fixtures/plot-schema.json")).unwrap();
+        let files = generate(&serde_json::from_str(&schema).unwrap()).unwrap();
+        let out_dir = root.join("../plotly/src/codegen_fixture");
+
+        let update = std::env::var_os("PLOTLY_CODEGEN_UPDATE_FIXTURE").is_some();
+        for file in &files {
+            let path = out_dir.join(&file.path);
+            if update {
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(&path, &file.contents).unwrap();
+            } else {
+                let committed = fs::read_to_string(&path).unwrap_or_default();
+                assert!(
+                    committed == file.contents,
+                    "{} is stale, regenerate it with \
+                     `PLOTLY_CODEGEN_UPDATE_FIXTURE=1 cargo test -p plotly_codegen`",
+                    path.display()
+                );
+            }
+        }
+    }
+}
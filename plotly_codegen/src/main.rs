@@ -0,0 +1,63 @@
+//! Command line front-end for the `plotly_codegen` generator.
+//!
+//! ```bash
+//! cargo run -p plotly_codegen -- SCHEMA OUT_DIR
+//! ```
+//!
+//! `SCHEMA` is a plotly.js `plot-schema.json` and `OUT_DIR` the directory the
+//! modules are written to. The generated files are formatted with `rustfmt`
+//! when it is available.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+
+fn main() -> ExitCode {
+    let args: Vec<_> = std::env::args().skip(1).collect();
+    let [schema_path, out_dir] = args.as_slice() else {
+        eprintln!("usage: plotly_codegen SCHEMA OUT_DIR");
+        return ExitCode::FAILURE;
+    };
+    let (schema_path, out_dir) = (PathBuf::from(schema_path), PathBuf::from(out_dir));
+
+    match run(&schema_path, &out_dir) {
+        Ok(count) => {
+            println!("Generated {count} files in {}", out_dir.display());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("plotly_codegen: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(schema_path: &Path, out_dir: &Path) -> Result<usize, String> {
+    let schema = fs::read_to_string(schema_path)
+        .map_err(|e| format!("failed to read {}: {e}", schema_path.display()))?;
+    let schema: serde_json::Value = serde_json::from_str(&schema)
+        .map_err(|e| format!("failed to parse {}: {e}", schema_path.display()))?;
+
+    let files = plotly_codegen::generate(&schema)?;
+    for file in &files {
+        let path = out_dir.join(&file.path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("failed to create {}: {e}", parent.display()))?;
+        }
+        fs::write(&path, &file.contents)
+            .map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+    }
+
+    let paths: Vec<_> = files.iter().map(|f| out_dir.join(&f.path)).collect();
+    match Command::new("rustfmt")
+        .args(["--edition", "2021"])
+        .args(&paths)
+        .status()
+    {
+        Ok(status) if status.success() => {}
+        _ => eprintln!("plotly_codegen: rustfmt not available or failed, output left unformatted"),
+    }
+
+    Ok(files.len())
+}
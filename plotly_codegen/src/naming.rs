@@ -0,0 +1,141 @@
+//! Helpers for turning plotly.js attribute names and enumerated values into
+//! valid Rust identifiers.
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else", "enum",
+    "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+    "mut", "pub", "ref", "return", "self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while", "yield",
+];
+
+/// Convert an attribute name (e.g. `hoverinfo`, `line_width`, `x0`) into a
+/// snake case field identifier. Rust keywords are emitted as raw identifiers.
+pub(crate) fn field_ident(name: &str) -> String {
+    let mut ident = String::with_capacity(name.len());
+    let mut prev_lower = false;
+    for ch in name.chars() {
+        if ch.is_ascii_uppercase() {
+            if prev_lower {
+                ident.push('_');
+            }
+            ident.push(ch.to_ascii_lowercase());
+            prev_lower = false;
+        } else if ch.is_ascii_alphanumeric() {
+            ident.push(ch);
+            prev_lower = true;
+        } else {
+            ident.push('_');
+            prev_lower = false;
+        }
+    }
+    if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    if RUST_KEYWORDS.contains(&ident.as_str()) {
+        ident.insert_str(0, "r#");
+    }
+    ident
+}
+
+/// Convert an arbitrary string (attribute name, trace type or enumerated
+/// value) into an upper camel case type or variant identifier.
+pub(crate) fn pascal_case(name: &str) -> String {
+    let mut ident = String::with_capacity(name.len());
+    let mut capitalize = true;
+    for ch in name.chars() {
+        match ch {
+            'a'..='z' | 'A'..='Z' | '0'..='9' => {
+                if capitalize {
+                    ident.push(ch.to_ascii_uppercase());
+                    capitalize = false;
+                } else {
+                    ident.push(ch);
+                }
+            }
+            '-' if ident.is_empty() => {
+                ident.push_str("Minus");
+                capitalize = true;
+            }
+            '.' => {
+                ident.push('_');
+                capitalize = true;
+            }
+            _ => capitalize = true,
+        }
+    }
+    if ident.is_empty() {
+        ident.push_str("Empty");
+    }
+    if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, 'V');
+    }
+    ident
+}
+
+/// Escape and wrap a plotly.js description as a block of `///` doc comments,
+/// indented by `indent` spaces.
+pub(crate) fn doc_comment(description: &str, indent: usize) -> String {
+    const WIDTH: usize = 80;
+    let pad = " ".repeat(indent);
+    let escaped = description
+        .replace('\\', "\\\\")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('[', "\\[")
+        .replace(']', "\\]");
+
+    let mut out = String::new();
+    let mut line = String::new();
+    for word in escaped.split_whitespace() {
+        if !line.is_empty() && pad.len() + 4 + line.len() + 1 + word.len() > WIDTH {
+            out.push_str(&format!("{pad}/// {line}\n"));
+            line.clear();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        out.push_str(&format!("{pad}/// {line}\n"));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn field_idents() {
+        assert_eq!(field_ident("hoverinfo"), "hoverinfo");
+        assert_eq!(field_ident("type"), "r#type");
+        assert_eq!(field_ident("xaxis"), "xaxis");
+        assert_eq!(field_ident("dragMode"), "drag_mode");
+        assert_eq!(field_ident("3d"), "_3d");
+    }
+
+    #[test]
+    fn pascal_case_names() {
+        assert_eq!(pascal_case("scatter3d"), "Scatter3d");
+        assert_eq!(pascal_case("lines+markers"), "LinesMarkers");
+        assert_eq!(pascal_case("top left"), "TopLeft");
+        assert_eq!(pascal_case(""), "Empty");
+        assert_eq!(pascal_case("-1"), "Minus1");
+        assert_eq!(pascal_case("1:110m"), "V1110m");
+    }
+
+    #[test]
+    fn doc_comment_wraps_and_escapes() {
+        let doc = doc_comment("Sets the <b>bold</b> range [0, 1].", 4);
+        assert_eq!(
+            doc,
+            "    /// Sets the &lt;b&gt;bold&lt;/b&gt; range \\[0, 1\\].\n"
+        );
+
+        let long = "word ".repeat(40);
+        for line in doc_comment(&long, 4).lines() {
+            assert!(line.len() <= 80);
+        }
+    }
+}
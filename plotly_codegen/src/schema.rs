@@ -0,0 +1,260 @@
+//! A minimal model of the attribute tree found in plotly.js
+//! `plot-schema.json`.
+//!
+//! Only the information needed to emit Rust code is retained: value types,
+//! `arrayOk` flags, enumerated values, descriptions and the nesting of
+//! attribute containers.
+
+use serde_json::{Map, Value};
+
+/// Keys of an attribute container which describe the container itself rather
+/// than one of its children.
+const META_KEYS: &[&str] = &["role", "editType", "description", "impliedEdits", "items"];
+
+/// A single leaf attribute, e.g. `scatter.opacity`.
+#[derive(Debug, Clone)]
+pub(crate) struct Attribute {
+    pub(crate) name: String,
+    pub(crate) val_type: ValType,
+    pub(crate) description: Option<String>,
+    pub(crate) array_ok: bool,
+}
+
+/// The `valType` of a leaf attribute.
+#[derive(Debug, Clone)]
+pub(crate) enum ValType {
+    DataArray,
+    Number,
+    Integer,
+    Boolean,
+    String,
+    Color,
+    ColorList,
+    ColorScale,
+    Angle,
+    SubplotId,
+    FlagList,
+    InfoArray,
+    Enumerated(Vec<Value>),
+    Any,
+}
+
+/// An attribute container, e.g. `scatter.marker`.
+#[derive(Debug, Clone)]
+pub(crate) struct Object {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) children: Vec<Node>,
+}
+
+/// A child of an attribute container.
+#[derive(Debug, Clone)]
+pub(crate) enum Node {
+    Attribute(Attribute),
+    Object(Object),
+    /// A container declared with `items`, such as `layout.annotations`, which
+    /// is serialized as an array of objects.
+    Array(Object),
+}
+
+/// A trace type declared under `traces` in the schema.
+#[derive(Debug, Clone)]
+pub(crate) struct TraceSchema {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) attributes: Object,
+    pub(crate) layout_attributes: Option<Object>,
+}
+
+/// The parts of the schema that are turned into Rust code.
+#[derive(Debug, Clone)]
+pub(crate) struct Schema {
+    pub(crate) traces: Vec<TraceSchema>,
+    pub(crate) layout: Object,
+}
+
+impl Schema {
+    pub(crate) fn parse(schema: &Value) -> Result<Self, String> {
+        let traces = schema
+            .get("traces")
+            .and_then(Value::as_object)
+            .ok_or("schema has no `traces` object")?;
+        let layout = schema
+            .pointer("/layout/layoutAttributes")
+            .and_then(Value::as_object)
+            .ok_or("schema has no `layout.layoutAttributes` object")?;
+
+        let traces = traces
+            .iter()
+            .map(|(name, trace)| {
+                let attributes = trace
+                    .get("attributes")
+                    .and_then(Value::as_object)
+                    .ok_or(format!("trace `{name}` has no attributes"))?;
+                Ok(TraceSchema {
+                    name: name.clone(),
+                    description: trace
+                        .pointer("/meta/description")
+                        .and_then(Value::as_str)
+                        .map(str::to_owned),
+                    attributes: parse_object(name, attributes),
+                    layout_attributes: trace
+                        .get("layoutAttributes")
+                        .and_then(Value::as_object)
+                        .map(|attrs| parse_object("layout", attrs)),
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(Self {
+            traces,
+            layout: parse_object("layout", layout),
+        })
+    }
+}
+
+fn description(map: &Map<String, Value>) -> Option<String> {
+    map.get("description")
+        .and_then(Value::as_str)
+        .filter(|d| !d.is_empty())
+        .map(str::to_owned)
+}
+
+fn parse_object(name: &str, map: &Map<String, Value>) -> Object {
+    let children = map
+        .iter()
+        .filter(|(key, _)| !key.starts_with('_') && !META_KEYS.contains(&key.as_str()))
+        .filter_map(|(key, value)| parse_node(key, value.as_object()?))
+        .collect();
+
+    Object {
+        name: name.to_owned(),
+        description: description(map),
+        children,
+    }
+}
+
+fn parse_node(name: &str, map: &Map<String, Value>) -> Option<Node> {
+    if map.contains_key("_deprecated") || map.get("valType").is_none() && map.is_empty() {
+        return None;
+    }
+
+    if let Some(val_type) = map.get("valType").and_then(Value::as_str) {
+        // `*src` attributes only reference Chart Studio grids.
+        if name.ends_with("src") && val_type == "string" && name != "src" {
+            return None;
+        }
+        let val_type = match val_type {
+            "data_array" => ValType::DataArray,
+            "number" => ValType::Number,
+            "integer" => ValType::Integer,
+            "boolean" => ValType::Boolean,
+            "string" => ValType::String,
+            "color" => ValType::Color,
+            "colorlist" => ValType::ColorList,
+            "colorscale" => ValType::ColorScale,
+            "angle" => ValType::Angle,
+            "subplotid" => ValType::SubplotId,
+            "flaglist" => ValType::FlagList,
+            "info_array" => ValType::InfoArray,
+            "enumerated" => ValType::Enumerated(
+                map.get("values")
+                    .and_then(Value::as_array)
+                    .cloned()
+                    .unwrap_or_default(),
+            ),
+            _ => ValType::Any,
+        };
+        return Some(Node::Attribute(Attribute {
+            name: name.to_owned(),
+            val_type,
+            description: description(map),
+            array_ok: map.get("arrayOk").and_then(Value::as_bool).unwrap_or(false),
+        }));
+    }
+
+    // Containers declared as `{ items: { <item name>: { ... } } }` are arrays.
+    if let Some(item) = map
+        .get("items")
+        .and_then(Value::as_object)
+        .and_then(|items| items.values().next())
+        .and_then(Value::as_object)
+    {
+        let mut object = parse_object(name, item);
+        object.description = object.description.or_else(|| description(map));
+        return Some(Node::Array(object));
+    }
+
+    Some(Node::Object(parse_object(name, map)))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn parse_attribute_tree() {
+        let schema = json!({
+            "traces": {
+                "scatter": {
+                    "meta": {"description": "The scatter trace."},
+                    "attributes": {
+                        "type": "scatter",
+                        "opacity": {"valType": "number", "description": "Sets the opacity."},
+                        "xsrc": {"valType": "string"},
+                        "old": {"valType": "number", "_deprecated": true},
+                        "marker": {
+                            "role": "object",
+                            "size": {"valType": "number", "arrayOk": true},
+                            "editType": "calc"
+                        }
+                    },
+                    "layoutAttributes": {
+                        "scattermode": {"valType": "enumerated", "values": ["group", "overlay"]}
+                    }
+                }
+            },
+            "layout": {
+                "layoutAttributes": {
+                    "annotations": {
+                        "items": {"annotation": {"text": {"valType": "string"}}},
+                        "role": "object"
+                    }
+                }
+            }
+        });
+
+        let schema = Schema::parse(&schema).unwrap();
+        let scatter = &schema.traces[0];
+        assert_eq!(scatter.name, "scatter");
+        assert_eq!(scatter.description.as_deref(), Some("The scatter trace."));
+
+        let names: Vec<_> = scatter
+            .attributes
+            .children
+            .iter()
+            .map(|node| match node {
+                Node::Attribute(a) => a.name.as_str(),
+                Node::Object(o) | Node::Array(o) => o.name.as_str(),
+            })
+            .collect();
+        assert_eq!(names, ["opacity", "marker"]);
+
+        match &scatter.attributes.children[1] {
+            Node::Object(marker) => match &marker.children[0] {
+                Node::Attribute(size) => assert!(size.array_ok),
+                other => panic!("unexpected node {other:?}"),
+            },
+            other => panic!("unexpected node {other:?}"),
+        }
+        assert!(scatter.layout_attributes.is_some());
+        assert!(matches!(schema.layout.children[0], Node::Array(_)));
+    }
+
+    #[test]
+    fn missing_traces_is_an_error() {
+        assert!(Schema::parse(&json!({"layout": {}})).is_err());
+    }
+}
@@ -262,6 +262,10 @@ struct FieldReceiver {
     // If true, derive the 2D array / matrix setter for the field
     #[darling(default)]
     with_matrix: bool,
+
+    // If true, the setter takes any value converting into the field type
+    #[darling(default)]
+    into: bool,
 }
 
 impl FieldReceiver {
@@ -342,7 +346,7 @@ impl FieldReceiver {
                 quote![],
             ),
             FieldType::OptionOther(inner_ty) => {
-                if matches!(field_ident.to_string().as_str(), "x_axis" | "y_axis") {
+                if self.into || matches!(field_ident.to_string().as_str(), "x_axis" | "y_axis") {
                     (quote![impl Into<#inner_ty>], quote![value.into()], quote![])
                 } else {
                     (quote![#inner_ty], quote![value], quote![])
@@ -0,0 +1,163 @@
+//! A [Dioxus](https://dioxuslabs.com) component rendering a
+//! [`plotly::Plot`].
+//!
+//! The [`Plotly`] component draws its `plot` prop into a `<div>` with
+//! `Plotly.react`, redraws it whenever the prop changes and calls
+//! `Plotly.purge` when it is unmounted. The `on_*` props receive the typed
+//! plotly.js event payloads of [`plotly::events`].
+//!
+//! The crate is empty unless compiled for a WebAssembly target.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use dioxus::prelude::*;
+//! use plotly::{Plot, Scatter};
+//! use plotly_dioxus::Plotly;
+//!
+//! fn App() -> Element {
+//!     let mut plot = Plot::new();
+//!     plot.add_trace(Scatter::new(vec![0, 1, 2], vec![2, 1, 0]));
+//!
+//!     rsx! {
+//!         Plotly {
+//!             id: "plot-div",
+//!             plot,
+//!             on_click: move |event: plotly::events::ClickEvent| {
+//!                 tracing::info!("{:?}", event.points)
+//!             },
+//!         }
+//!     }
+//! }
+//! ```
+#![cfg(target_arch = "wasm32")]
+
+use std::{cell::RefCell, rc::Rc};
+
+use dioxus::prelude::*;
+use plotly::{
+    callbacks::{self, EventListener},
+    events::{ClickEvent, PointsEvent, RelayoutEvent, RestyleEvent, SelectionEvent},
+    Plot,
+};
+
+/// The properties of the [`Plotly`] component.
+#[derive(Props, Clone, PartialEq)]
+pub struct PlotlyProps {
+    /// The `id` of the `<div>` the plot is drawn into, unique on the page.
+    #[props(into)]
+    pub id: String,
+    /// The plot to draw. The plot is redrawn with `Plotly.react` whenever it
+    /// changes.
+    pub plot: Plot,
+    #[props(into, default)]
+    pub class: String,
+    /// Called with `plotly_click` events.
+    pub on_click: Option<EventHandler<ClickEvent>>,
+    /// Called with `plotly_hover` events.
+    pub on_hover: Option<EventHandler<PointsEvent>>,
+    /// Called with `plotly_unhover` events.
+    pub on_unhover: Option<EventHandler<PointsEvent>>,
+    /// Called with `plotly_selected` events.
+    pub on_selected: Option<EventHandler<SelectionEvent>>,
+    /// Called with `plotly_deselect` events.
+    pub on_deselect: Option<EventHandler<()>>,
+    /// Called with `plotly_relayout` events.
+    pub on_relayout: Option<EventHandler<RelayoutEvent>>,
+    /// Called with `plotly_restyle` events.
+    pub on_restyle: Option<EventHandler<RestyleEvent>>,
+}
+
+/// The event handlers of the latest render, read by the plotly.js listeners.
+#[derive(Default)]
+struct Handlers {
+    on_click: Option<EventHandler<ClickEvent>>,
+    on_hover: Option<EventHandler<PointsEvent>>,
+    on_unhover: Option<EventHandler<PointsEvent>>,
+    on_selected: Option<EventHandler<SelectionEvent>>,
+    on_deselect: Option<EventHandler<()>>,
+    on_relayout: Option<EventHandler<RelayoutEvent>>,
+    on_restyle: Option<EventHandler<RestyleEvent>>,
+}
+
+impl From<&PlotlyProps> for Handlers {
+    fn from(props: &PlotlyProps) -> Self {
+        Self {
+            on_click: props.on_click,
+            on_hover: props.on_hover,
+            on_unhover: props.on_unhover,
+            on_selected: props.on_selected,
+            on_deselect: props.on_deselect,
+            on_relayout: props.on_relayout,
+            on_restyle: props.on_restyle,
+        }
+    }
+}
+
+/// A `<div>` showing a [`Plot`], see the [crate] documentation.
+#[allow(non_snake_case)]
+pub fn Plotly(props: PlotlyProps) -> Element {
+    let handlers = use_hook(|| Rc::new(RefCell::new(Handlers::default())));
+    *handlers.borrow_mut() = Handlers::from(&props);
+    let listeners = use_hook(|| Rc::new(RefCell::new(Vec::<EventListener>::new())));
+
+    use_effect(use_reactive((&props.plot,), {
+        let id = props.id.clone();
+        let listeners = listeners.clone();
+        move |(plot,)| {
+            let id = id.clone();
+            let handlers = handlers.clone();
+            let listeners = listeners.clone();
+            spawn(async move {
+                if plotly::bindings::react(&id, &plot).await.is_err() {
+                    return;
+                }
+                // plotly.js only accepts listeners once the plot is drawn.
+                if listeners.borrow().is_empty() {
+                    *listeners.borrow_mut() = bind_listeners(&id, &handlers);
+                }
+            });
+        }
+    }));
+
+    use_drop({
+        let id = props.id.clone();
+        move || {
+            listeners.borrow_mut().clear();
+            let _ = plotly::bindings::purge(&id);
+        }
+    });
+
+    rsx! {
+        div { id: "{props.id}", class: "{props.class}" }
+    }
+}
+
+/// Register listeners for all supported events, forwarding them to the
+/// current handlers in `handlers`.
+fn bind_listeners(id: &str, handlers: &Rc<RefCell<Handlers>>) -> Vec<EventListener> {
+    let mut on_deselect = emit(handlers, |h| h.on_deselect);
+    vec![
+        callbacks::bind_click(id, emit(handlers, |h| h.on_click)),
+        callbacks::bind_hover(id, emit(handlers, |h| h.on_hover)),
+        callbacks::bind_unhover(id, emit(handlers, |h| h.on_unhover)),
+        callbacks::bind_selected(id, emit(handlers, |h| h.on_selected)),
+        callbacks::bind_deselect(id, move || on_deselect(())),
+        callbacks::bind_relayout(id, emit(handlers, |h| h.on_relayout)),
+        callbacks::bind_restyle(id, emit(handlers, |h| h.on_restyle)),
+    ]
+}
+
+/// A closure calling the handler selected by `handler` with events, if set.
+fn emit<E: 'static>(
+    handlers: &Rc<RefCell<Handlers>>,
+    handler: fn(&Handlers) -> Option<EventHandler<E>>,
+) -> impl FnMut(E) + 'static {
+    let handlers = handlers.clone();
+    move |event| {
+        let handler = handler(&handlers.borrow());
+        if let Some(handler) = handler {
+            handler.call(event);
+        }
+    }
+}
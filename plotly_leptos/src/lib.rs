@@ -0,0 +1,116 @@
+//! A [Leptos](https://leptos.dev) component rendering a [`plotly::Plot`].
+//!
+//! The [`Plotly`] component draws its `plot` signal into a `<div>` with
+//! `Plotly.react`, redraws it whenever the signal changes and calls
+//! `Plotly.purge` when it is unmounted. The `on_*` props receive the typed
+//! plotly.js event payloads of [`plotly::events`].
+//!
+//! As a `Plot` is not `Send`, the signal uses Leptos' local storage, e.g.
+//! `RwSignal::new_local(plot)`.
+//!
+//! The crate is empty unless compiled for a WebAssembly target.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use leptos::prelude::*;
+//! use plotly::{Plot, Scatter};
+//! use plotly_leptos::Plotly;
+//!
+//! #[component]
+//! fn App() -> impl IntoView {
+//!     let mut plot = Plot::new();
+//!     plot.add_trace(Scatter::new(vec![0, 1, 2], vec![2, 1, 0]));
+//!     let plot = RwSignal::new_local(plot);
+//!
+//!     view! {
+//!         <Plotly
+//!             id="plot-div"
+//!             plot=plot
+//!             on_click=move |event: plotly::events::ClickEvent| {
+//!                 leptos::logging::log!("{:?}", event.points)
+//!             }
+//!         />
+//!     }
+//! }
+//! ```
+#![cfg(target_arch = "wasm32")]
+
+use leptos::{prelude::*, task::spawn_local};
+use plotly::{
+    callbacks::{self, EventListener},
+    events::{ClickEvent, PointsEvent, RelayoutEvent, RestyleEvent, SelectionEvent},
+    Plot,
+};
+
+/// A `<div>` showing a [`Plot`], see the [crate] documentation.
+#[component]
+pub fn Plotly(
+    /// The `id` of the `<div>` the plot is drawn into, unique on the page.
+    #[prop(into)]
+    id: String,
+    /// The plot to draw. The plot is redrawn with `Plotly.react` whenever the
+    /// signal changes.
+    #[prop(into)]
+    plot: Signal<Plot, LocalStorage>,
+    #[prop(optional, into)] class: String,
+    /// Called with `plotly_click` events.
+    #[prop(optional, into)]
+    on_click: Option<Callback<ClickEvent>>,
+    /// Called with `plotly_hover` events.
+    #[prop(optional, into)]
+    on_hover: Option<Callback<PointsEvent>>,
+    /// Called with `plotly_unhover` events.
+    #[prop(optional, into)]
+    on_unhover: Option<Callback<PointsEvent>>,
+    /// Called with `plotly_selected` events.
+    #[prop(optional, into)]
+    on_selected: Option<Callback<SelectionEvent>>,
+    /// Called with `plotly_deselect` events.
+    #[prop(optional, into)]
+    on_deselect: Option<Callback<()>>,
+    /// Called with `plotly_relayout` events.
+    #[prop(optional, into)]
+    on_relayout: Option<Callback<RelayoutEvent>>,
+    /// Called with `plotly_restyle` events.
+    #[prop(optional, into)]
+    on_restyle: Option<Callback<RestyleEvent>>,
+) -> impl IntoView {
+    let listeners = StoredValue::new_local(Vec::<EventListener>::new());
+
+    Effect::new({
+        let id = id.clone();
+        move |_| {
+            let plot = plot.get();
+            let id = id.clone();
+            spawn_local(async move {
+                if plotly::bindings::react(&id, &plot).await.is_err() {
+                    return;
+                }
+                // plotly.js only accepts listeners once the plot is drawn.
+                if listeners.with_value(Vec::is_empty) {
+                    let bound = [
+                        on_click.map(|cb| callbacks::bind_click(&id, move |e| cb.run(e))),
+                        on_hover.map(|cb| callbacks::bind_hover(&id, move |e| cb.run(e))),
+                        on_unhover.map(|cb| callbacks::bind_unhover(&id, move |e| cb.run(e))),
+                        on_selected.map(|cb| callbacks::bind_selected(&id, move |e| cb.run(e))),
+                        on_deselect.map(|cb| callbacks::bind_deselect(&id, move || cb.run(()))),
+                        on_relayout.map(|cb| callbacks::bind_relayout(&id, move |e| cb.run(e))),
+                        on_restyle.map(|cb| callbacks::bind_restyle(&id, move |e| cb.run(e))),
+                    ];
+                    listeners.set_value(bound.into_iter().flatten().collect());
+                }
+            });
+        }
+    });
+
+    on_cleanup({
+        let id = id.clone();
+        move || {
+            listeners.update_value(Vec::clear);
+            let _ = plotly::bindings::purge(&id);
+        }
+    });
+
+    view! { <div id=id class=class></div> }
+}
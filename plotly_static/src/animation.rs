@@ -0,0 +1,309 @@
+//! Assembling the exported frames of animated plots.
+
+use std::fs::{self, File};
+use std::io::{BufWriter, Cursor, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose, Engine as _};
+
+use crate::{dpi, write_image_file, ExportError, ImageFormat};
+
+/// Output formats for exporting the frames of an animated plot.
+///
+/// # Examples
+///
+/// ```rust
+/// use plotly_static::{AnimationFormat, ImageFormat};
+/// use std::time::Duration;
+///
+/// let gif = AnimationFormat::GIF {
+///     frame_duration: Duration::from_millis(500),
+/// };
+/// assert_eq!(gif.to_string(), "gif");
+///
+/// let sequence = AnimationFormat::Sequence(ImageFormat::SVG);
+/// assert_eq!(sequence.to_string(), "svg");
+/// ```
+#[derive(Debug, Clone)]
+pub enum AnimationFormat {
+    /// Animated GIF showing each frame for `frame_duration`, looping forever
+    ///
+    /// GIF frame durations have a resolution of 10ms and the colors of each
+    /// frame are reduced to a palette of 256 colors.
+    GIF { frame_duration: Duration },
+    /// Animated PNG showing each frame for `frame_duration`, looping forever
+    APNG { frame_duration: Duration },
+    /// One image file per frame, named after the destination path with the
+    /// zero-based frame index appended, e.g. `plot_000.png`
+    ///
+    /// PDF and EPS are not supported.
+    Sequence(ImageFormat),
+}
+
+impl AnimationFormat {
+    /// The format of the images exported for each frame.
+    pub(crate) fn frame_format(&self) -> ImageFormat {
+        match self {
+            Self::GIF { .. } | Self::APNG { .. } => ImageFormat::PNG,
+            Self::Sequence(format) => format.clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for AnimationFormat {
+    /// Formats the AnimationFormat as the extension of its files.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::GIF { .. } => write!(f, "gif"),
+            Self::APNG { .. } => write!(f, "png"),
+            Self::Sequence(format) => write!(f, "{format}"),
+        }
+    }
+}
+
+/// Check that the frames of an animation can be exported in `format`.
+pub(crate) fn check_format(format: &AnimationFormat) -> Result<(), ExportError> {
+    match format {
+        #[allow(deprecated)]
+        AnimationFormat::Sequence(ImageFormat::PDF | ImageFormat::EPS) => {
+            Err(ExportError::InvalidInput(format!(
+                "Animation frames cannot be exported as {}",
+                format.frame_format()
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Write the exported `frames` of an animation to `dst` in `format`.
+///
+/// GIF and APNG frames are decoded one at a time and streamed to the encoder,
+/// so only one frame is held in memory as raw pixels.
+pub(crate) fn write_animation(
+    dst: &Path,
+    format: &AnimationFormat,
+    scale: f64,
+    frames: &[String],
+) -> Result<(), ExportError> {
+    let frame_duration = match format {
+        AnimationFormat::Sequence(image_format) => {
+            let digits = frames.len().saturating_sub(1).to_string().len().max(3);
+            for (index, frame) in frames.iter().enumerate() {
+                let path = frame_path(dst, image_format, index, digits);
+                write_image_file(&path, image_format, scale, frame)?;
+            }
+            return Ok(());
+        }
+        AnimationFormat::GIF { frame_duration } | AnimationFormat::APNG { frame_duration } => {
+            *frame_duration
+        }
+    };
+
+    let mut dst = PathBuf::from(dst);
+    dst.set_extension(format.to_string());
+    let mut file = BufWriter::new(File::create(dst.as_path())?);
+    let images = frames.iter().map(|frame| decode_frame(frame));
+    let result = match format {
+        AnimationFormat::GIF { .. } => encode_gif(&mut file, images, frame_duration),
+        _ => encode_apng(
+            &mut file,
+            images,
+            frames.len(),
+            frame_duration,
+            dpi::scale_to_dpi(scale),
+        ),
+    };
+    if let Err(e) = result {
+        drop(file);
+        let _ = fs::remove_file(&dst);
+        return Err(ExportError::classify(e, ExportError::Encode));
+    }
+    file.flush()?;
+
+    Ok(())
+}
+
+/// The file of frame `index` of an animation exported to `dst`, with the
+/// index padded to `digits` appended to the file stem.
+fn frame_path(dst: &Path, format: &ImageFormat, index: usize, digits: usize) -> PathBuf {
+    let stem = dst
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    dst.with_file_name(format!("{stem}_{index:0digits$}.{format}"))
+}
+
+/// Decode an exported base64 PNG frame, failing with
+/// [`ExportError::Decode`].
+fn decode_frame(frame: &str) -> Result<Rgba> {
+    general_purpose::STANDARD
+        .decode(frame)
+        .map_err(anyhow::Error::from)
+        .and_then(|data| decode_rgba(&data))
+        .map_err(|e| ExportError::Decode(format!("{e:#}")).into())
+}
+
+/// An 8-bit RGBA image.
+pub(crate) struct Rgba {
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) pixels: Vec<u8>,
+}
+
+pub(crate) fn decode_rgba(data: &[u8]) -> Result<Rgba> {
+    let mut decoder = png::Decoder::new(Cursor::new(data));
+    decoder.set_transformations(
+        png::Transformations::EXPAND | png::Transformations::STRIP_16 | png::Transformations::ALPHA,
+    );
+    let mut reader = decoder
+        .read_info()
+        .with_context(|| "Failed to decode PNG image")?;
+    let size = reader
+        .output_buffer_size()
+        .ok_or(anyhow!("PNG image is too large"))?;
+    let mut pixels = vec![0; size];
+    let info = reader.next_frame(&mut pixels)?;
+    if info.color_type != png::ColorType::Rgba {
+        return Err(anyhow!("Unsupported PNG color type {:?}", info.color_type));
+    }
+    pixels.truncate(info.buffer_size());
+    Ok(Rgba {
+        width: info.width,
+        height: info.height,
+        pixels,
+    })
+}
+
+fn encode_gif(
+    out: impl Write,
+    mut images: impl Iterator<Item = Result<Rgba>>,
+    frame_duration: Duration,
+) -> Result<()> {
+    let first = images.next().ok_or(anyhow!("No animation frames"))??;
+    let width = u16::try_from(first.width).with_context(|| "Image is too wide for a GIF")?;
+    let height = u16::try_from(first.height).with_context(|| "Image is too high for a GIF")?;
+    // GIF delays are given in hundredths of a second
+    let delay = u16::try_from(frame_duration.as_millis() / 10).unwrap_or(u16::MAX);
+
+    let mut encoder = gif::Encoder::new(out, width, height, &[])?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+    for image in std::iter::once(Ok(first)).chain(images) {
+        let mut image = image?;
+        if (image.width, image.height) != (u32::from(width), u32::from(height)) {
+            return Err(anyhow!("Animation frames differ in size"));
+        }
+        let mut frame = gif::Frame::from_rgba_speed(width, height, &mut image.pixels, 10);
+        frame.delay = delay;
+        encoder.write_frame(&frame)?;
+    }
+    Ok(())
+}
+
+/// Encode `num_frames` `images` as an APNG with a density of `dpi`.
+fn encode_apng(
+    out: impl Write,
+    mut images: impl Iterator<Item = Result<Rgba>>,
+    num_frames: usize,
+    frame_duration: Duration,
+    dpi: f64,
+) -> Result<()> {
+    let first = images.next().ok_or(anyhow!("No animation frames"))??;
+    let num_frames = u32::try_from(num_frames).with_context(|| "Too many animation frames")?;
+    let delay_ms = u16::try_from(frame_duration.as_millis()).unwrap_or(u16::MAX);
+
+    let mut encoder = png::Encoder::new(out, first.width, first.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_pixel_dims(dpi::png_pixel_dims(dpi));
+    encoder.set_animated(num_frames, 0)?;
+    encoder.set_frame_delay(delay_ms, 1000)?;
+    let mut writer = encoder.write_header()?;
+    for image in std::iter::once(Ok(first)).chain(images) {
+        writer.write_image_data(&image?.pixels)?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid_png(rgba: [u8; 4]) -> Vec<u8> {
+        let mut data = Vec::new();
+        {
+            let mut encoder = png::Encoder::new(&mut data, 2, 2);
+            encoder.set_color(png::ColorType::Rgba);
+            encoder.set_depth(png::BitDepth::Eight);
+            let mut writer = encoder.write_header().unwrap();
+            writer.write_image_data(&rgba.repeat(4)).unwrap();
+        }
+        data
+    }
+
+    #[test]
+    fn encode_frames_as_gif_and_apng() {
+        let frames = [[255, 0, 0, 255], [0, 0, 255, 255]]
+            .map(|rgba| general_purpose::STANDARD.encode(solid_png(rgba)));
+        let images = || frames.iter().map(|frame| decode_frame(frame));
+        assert_eq!(
+            images().nth(1).unwrap().unwrap().pixels,
+            [0, 0, 255, 255].repeat(4)
+        );
+
+        let mut gif = Vec::new();
+        encode_gif(&mut gif, images(), Duration::from_millis(250)).unwrap();
+        let mut decoder = gif::DecodeOptions::new().read_info(gif.as_slice()).unwrap();
+        let mut delays = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            delays.push(frame.delay);
+        }
+        assert_eq!(delays, [25, 25]);
+
+        let mut apng = Vec::new();
+        encode_apng(&mut apng, images(), 2, Duration::from_millis(250), 192.0).unwrap();
+        let reader = png::Decoder::new(Cursor::new(apng)).read_info().unwrap();
+        let animation = reader.info().animation_control().unwrap();
+        assert_eq!((animation.num_frames, animation.num_plays), (2, 0));
+        assert_eq!(reader.info().pixel_dims.unwrap().xppu, 7559);
+    }
+
+    #[test]
+    fn frame_errors_are_classified() {
+        let frames = [
+            general_purpose::STANDARD.encode(solid_png([0, 0, 0, 255])),
+            "?".into(),
+        ];
+        let images = frames.iter().map(|frame| decode_frame(frame));
+        let error = encode_gif(Vec::new(), images, Duration::from_millis(250)).unwrap_err();
+        assert!(matches!(
+            ExportError::classify(error, ExportError::Encode),
+            ExportError::Decode(_)
+        ));
+
+        let error = encode_gif(Vec::new(), std::iter::empty(), Duration::ZERO).unwrap_err();
+        assert!(matches!(
+            ExportError::classify(error, ExportError::Encode),
+            ExportError::Encode(_)
+        ));
+    }
+
+    #[test]
+    fn sequence_frames_are_numbered() {
+        assert_eq!(
+            frame_path(Path::new("out/plot"), &ImageFormat::PNG, 7, 3),
+            PathBuf::from("out/plot_007.png")
+        );
+        assert_eq!(
+            frame_path(Path::new("out/chart.v2.png"), &ImageFormat::SVG, 12, 3),
+            PathBuf::from("out/chart.v2_012.svg")
+        );
+        assert!(matches!(
+            check_format(&AnimationFormat::Sequence(ImageFormat::PDF)),
+            Err(ExportError::InvalidInput(_))
+        ));
+        assert!(check_format(&AnimationFormat::Sequence(ImageFormat::WEBP)).is_ok());
+    }
+}
@@ -0,0 +1,147 @@
+//! Exporting many plots in a single browser session.
+
+use std::cell::Cell;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+#[cfg(any(test, feature = "debug"))]
+use std::{println as debug, println as warn};
+
+#[cfg(not(any(test, feature = "debug")))]
+use log::{debug, warn};
+
+use crate::{
+    browser::Client, session::SessionConfig, write_image, AsyncStaticExporter, ExportError,
+    ImageFormat, PlotData,
+};
+
+/// One plot to export in a batch, see
+/// [`StaticExporter::write_batch`](crate::StaticExporter::write_batch).
+///
+/// The fields have the same meaning as the arguments of
+/// [`StaticExporter::write_fig`](crate::StaticExporter::write_fig).
+#[derive(Debug, Clone)]
+pub struct ExportJob {
+    /// Destination path, the extension is set from `format`
+    pub dst: PathBuf,
+    /// The Plotly plot as JSON
+    pub plot: serde_json::Value,
+    /// Output image format
+    pub format: ImageFormat,
+    /// Image width in pixels
+    pub width: usize,
+    /// Image height in pixels
+    pub height: usize,
+    /// Image scale factor
+    pub scale: f64,
+}
+
+impl ExportJob {
+    /// Create a job exporting `plot` to `dst`.
+    pub fn new(
+        dst: impl Into<PathBuf>,
+        plot: serde_json::Value,
+        format: ImageFormat,
+        width: usize,
+        height: usize,
+        scale: f64,
+    ) -> Self {
+        Self {
+            dst: dst.into(),
+            plot,
+            format,
+            width,
+            height,
+            scale,
+        }
+    }
+
+    /// The path of the file written by the job.
+    pub fn output_path(&self) -> PathBuf {
+        self.dst.with_extension(self.format.to_string())
+    }
+
+    fn plot_data(&self) -> PlotData<'_> {
+        PlotData {
+            format: self.format.clone(),
+            width: self.width,
+            height: self.height,
+            scale: self.scale,
+            data: &self.plot,
+        }
+    }
+}
+
+/// The outcome of one [`ExportJob`] of a batch.
+#[derive(Debug)]
+pub struct JobResult {
+    /// The path of the file written by the job
+    pub dst: PathBuf,
+    /// Whether the plot was rendered and written
+    pub result: Result<(), ExportError>,
+    /// Time spent rendering and writing the plot
+    pub duration: Duration,
+}
+
+impl JobResult {
+    /// Whether the job succeeded.
+    pub fn is_ok(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+/// Export `jobs` one after the other in the browser session of `exporter`.
+///
+/// The export page is loaded once and every plot is drawn into it. After a
+/// failed attempt the page is loaded again, in case the failure left it in a
+/// broken state. Each job goes through the retry policy of the exporter, so a
+/// session that dies halfway through the batch is replaced for the remaining
+/// jobs.
+///
+/// Fails only if no browser session with the export page could be opened.
+pub(crate) async fn run(
+    exporter: &mut AsyncStaticExporter,
+    jobs: &[ExportJob],
+) -> Result<Vec<JobResult>, ExportError> {
+    debug!("Exporting a batch of {} plots", jobs.len());
+    exporter
+        .with_retry(|session, client| async move { session.load_page(&client).await })
+        .await?;
+    let page_loaded = Cell::new(true);
+    let mut results = Vec::with_capacity(jobs.len());
+    for job in jobs {
+        let start = Instant::now();
+        let page_loaded = &page_loaded;
+        let result = match exporter
+            .with_retry(|session, client| render_job(session, client, job, page_loaded))
+            .await
+        {
+            Ok(image_data) => write_image(&job.dst, &job.format, job.scale, &image_data),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = &result {
+            warn!("Failed to export {}: {e}", job.dst.display());
+        }
+        results.push(JobResult {
+            dst: job.output_path(),
+            result,
+            duration: start.elapsed(),
+        });
+    }
+    Ok(results)
+}
+
+async fn render_job(
+    session: Arc<SessionConfig>,
+    client: Client,
+    job: &ExportJob,
+    page_loaded: &Cell<bool>,
+) -> Result<String, ExportError> {
+    if !page_loaded.get() {
+        session.load_page(&client).await?;
+        page_loaded.set(true);
+    }
+    let result = session.render_loaded(&client, &job.plot_data()).await;
+    page_loaded.set(result.is_ok());
+    result
+}
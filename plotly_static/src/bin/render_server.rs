@@ -0,0 +1,549 @@
+//! `plotly-render-server`: renders Plotly figures to static images over HTTP.
+//!
+//! `POST /render` takes a JSON body with the figure and the export options
+//! and responds with the bytes of the image:
+//!
+//! ```json
+//! {"figure": {"data": [], "layout": {}}, "format": "png", "width": 700, "height": 500, "scale": 1}
+//! ```
+//!
+//! The figure may also be given as `data`, like in the requests of Kaleido.
+//! Failed requests are answered with a JSON body of the form
+//! `{"code": 422, "error": "render", "message": "..."}`. `GET /health`
+//! reports the number of browser sessions.
+//!
+//! The figures are rendered by an [`ExporterPool`], so several requests are
+//! served concurrently. The number of open connections, the size of requests
+//! and the time for receiving them are limited, so that the server can be
+//! exposed beyond localhost.
+
+use std::convert::Infallible;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use base64::{engine::general_purpose, Engine as _};
+use clap::Parser;
+use http_body_util::{BodyExt, Full, LengthLimitError, Limited};
+use hyper::body::{Body, Bytes, Incoming};
+use hyper::header::{HeaderValue, CONTENT_TYPE};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, StatusCode};
+use hyper_util::rt::{TokioIo, TokioTimer};
+use log::{info, warn};
+use plotly_static::{
+    Backend, ExportError, ExporterPool, ImageFormat, RetryPolicy, StaticExporterBuilder,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::task::JoinSet;
+
+#[cfg(not(any(feature = "chromedriver", feature = "geckodriver", feature = "cdp")))]
+compile_error!(
+    "plotly-render-server needs a browser backend, enable one of the 'chromedriver', \
+     'geckodriver' or 'cdp' features along with 'render_server'"
+);
+
+/// Largest accepted request body
+const MAX_BODY_SIZE: usize = 64 * 1024 * 1024;
+
+/// Largest accepted request line and headers
+const MAX_HEADER_SIZE: usize = 64 * 1024;
+
+/// geckodriver supports a single session per process
+const DEFAULT_SESSIONS: usize = if cfg!(feature = "geckodriver") { 1 } else { 4 };
+
+#[derive(Parser)]
+#[command(name = "plotly-render-server")]
+#[command(about = "Render Plotly figures to static images over HTTP")]
+#[command(version)]
+struct Cli {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1")]
+    host: String,
+
+    /// Port to listen on
+    #[arg(short, long, default_value_t = 8000)]
+    port: u16,
+
+    /// Number of browser sessions rendering concurrently
+    #[arg(long, default_value_t = DEFAULT_SESSIONS)]
+    sessions: usize,
+
+    /// Use offline mode (bundled JavaScript)
+    #[arg(long)]
+    offline: bool,
+
+    /// Launch Chrome directly instead of using WebDriver (requires the `cdp`
+    /// feature, the default when no WebDriver feature is enabled)
+    #[arg(long, default_value_t = !cfg!(any(feature = "chromedriver", feature = "geckodriver")))]
+    cdp: bool,
+
+    /// Port of the WebDriver
+    #[arg(long, default_value_t = 4444)]
+    webdriver_port: u32,
+
+    /// Time in seconds for rendering one image
+    #[arg(long, default_value_t = 30)]
+    render_timeout: u64,
+
+    /// Attempts of an export that failed for reasons unrelated to the figure
+    #[arg(long, default_value_t = 1)]
+    attempts: u32,
+
+    /// Maximum number of open connections, further clients wait
+    #[arg(long, default_value_t = 64)]
+    max_connections: usize,
+
+    /// Time in seconds for receiving the headers and the body of a request
+    #[arg(long, default_value_t = 30)]
+    request_timeout: u64,
+}
+
+/// The body of a `POST /render` request.
+#[derive(Debug, Deserialize)]
+struct RenderRequest {
+    /// The Plotly figure, `data` in Kaleido requests
+    #[serde(alias = "data")]
+    figure: Value,
+    #[serde(default = "default_format")]
+    format: String,
+    #[serde(default = "default_width")]
+    width: usize,
+    #[serde(default = "default_height")]
+    height: usize,
+    #[serde(default = "default_scale")]
+    scale: f64,
+}
+
+fn default_format() -> String {
+    "png".to_string()
+}
+
+fn default_width() -> usize {
+    700
+}
+
+fn default_height() -> usize {
+    500
+}
+
+fn default_scale() -> f64 {
+    1.0
+}
+
+impl RenderRequest {
+    /// Check the export options and return the image format.
+    fn image_format(&self) -> Result<ImageFormat, Failure> {
+        if !self.figure.is_object() {
+            return Err(Failure::bad_request("The figure must be a JSON object"));
+        }
+        if self.width == 0 || self.height == 0 {
+            return Err(Failure::bad_request(
+                "The width and height must be positive",
+            ));
+        }
+        if !self.scale.is_finite() || self.scale <= 0.0 {
+            return Err(Failure::bad_request("The scale must be positive"));
+        }
+        self.format
+            .parse()
+            .map_err(|e: ExportError| Failure::from_export(&e))
+    }
+}
+
+/// A request whose response is a JSON error.
+#[derive(Debug)]
+struct Failure {
+    status: u16,
+    kind: &'static str,
+    message: String,
+}
+
+impl Failure {
+    fn new(status: u16, kind: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            kind,
+            message: message.into(),
+        }
+    }
+
+    fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(400, "invalid_request", message)
+    }
+
+    /// The response to a failed export.
+    fn from_export(error: &ExportError) -> Self {
+        let (status, kind) = match error {
+            ExportError::InvalidInput(_) => (400, "invalid_request"),
+            ExportError::Render(_) => (422, "render"),
+            ExportError::Timeout(_) => (504, "timeout"),
+            ExportError::DriverLaunch(_) => (503, "driver_launch"),
+            ExportError::SessionCreation(_) => (503, "session_creation"),
+            ExportError::PageLoad(_) => (503, "page_load"),
+            ExportError::Decode(_) => (500, "decode"),
+            ExportError::Encode(_) => (500, "encode"),
+            ExportError::Io(_) => (500, "io"),
+            _ => (500, "export"),
+        };
+        Self::new(status, kind, error.to_string())
+    }
+
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let body = json!({"code": self.status, "error": self.kind, "message": self.message});
+        response(status, "application/json", body.to_string())
+    }
+}
+
+type Response = hyper::Response<Full<Bytes>>;
+
+fn response(status: StatusCode, content_type: &'static str, body: impl Into<Bytes>) -> Response {
+    let mut response = hyper::Response::new(Full::new(body.into()));
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+    response
+}
+
+/// Read a request body of at most `MAX_BODY_SIZE` bytes within `timeout`.
+async fn read_body<B>(body: B, timeout: Duration) -> Result<Bytes, Failure>
+where
+    B: Body,
+    B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    let too_large = || {
+        Failure::new(
+            413,
+            "payload_too_large",
+            format!("The request body exceeds {MAX_BODY_SIZE} bytes"),
+        )
+    };
+    if body.size_hint().lower() > MAX_BODY_SIZE as u64 {
+        return Err(too_large());
+    }
+    match tokio::time::timeout(timeout, Limited::new(body, MAX_BODY_SIZE).collect()).await {
+        Ok(Ok(collected)) => Ok(collected.to_bytes()),
+        Ok(Err(e)) if e.is::<LengthLimitError>() => Err(too_large()),
+        Ok(Err(e)) => Err(Failure::bad_request(format!("Failed to read request: {e}"))),
+        Err(_) => Err(Failure::new(
+            408,
+            "request_timeout",
+            "Timed out reading the request body",
+        )),
+    }
+}
+
+fn content_type(format: &ImageFormat) -> &'static str {
+    match format {
+        ImageFormat::PNG => "image/png",
+        ImageFormat::JPEG => "image/jpeg",
+        ImageFormat::WEBP => "image/webp",
+        ImageFormat::SVG => "image/svg+xml",
+        ImageFormat::PDF => "application/pdf",
+        #[allow(deprecated)]
+        ImageFormat::EPS => "application/postscript",
+    }
+}
+
+async fn route(
+    pool: &ExporterPool,
+    request: hyper::Request<Incoming>,
+    timeout: Duration,
+) -> Result<Response, Failure> {
+    let (parts, body) = request.into_parts();
+    info!("{} {}", parts.method, parts.uri.path());
+    match (&parts.method, parts.uri.path()) {
+        (&Method::POST, "/render") => render(pool, &read_body(body, timeout).await?).await,
+        (&Method::GET, "/health") => Ok(response(
+            StatusCode::OK,
+            "application/json",
+            json!({
+                "status": "ok",
+                "sessions": pool.size(),
+                "idle_sessions": pool.idle_sessions(),
+            })
+            .to_string(),
+        )),
+        (_, "/render") => Err(Failure::new(405, "method_not_allowed", "Use POST /render")),
+        (method, path) => Err(Failure::new(
+            404,
+            "not_found",
+            format!("No route for {method} {path}"),
+        )),
+    }
+}
+
+async fn render(pool: &ExporterPool, body: &[u8]) -> Result<Response, Failure> {
+    let request: RenderRequest = serde_json::from_slice(body)
+        .map_err(|e| Failure::bad_request(format!("Invalid render request: {e}")))?;
+    let format = request.image_format()?;
+
+    let image = pool
+        .write_to_string(
+            &request.figure,
+            format.clone(),
+            request.width,
+            request.height,
+            request.scale,
+        )
+        .await
+        .map_err(|e| Failure::from_export(&e))?;
+    let body = match format {
+        ImageFormat::SVG => image.into_bytes(),
+        _ => general_purpose::STANDARD
+            .decode(image)
+            .map_err(|e| Failure::from_export(&e.into()))?,
+    };
+    Ok(response(StatusCode::OK, content_type(&format), body))
+}
+
+/// Serve the HTTP/1.1 requests of one connection with `handler`, closing it
+/// if the request headers take longer than `timeout` or exceed
+/// `MAX_HEADER_SIZE` bytes. The `permit` is held until the connection closes.
+async fn serve_connection<H, F>(
+    stream: TcpStream,
+    timeout: Duration,
+    permit: OwnedSemaphorePermit,
+    handler: H,
+) where
+    H: Fn(hyper::Request<Incoming>) -> F,
+    F: Future<Output = Response>,
+{
+    let service = service_fn(|request| {
+        let response = handler(request);
+        async move { Ok::<_, Infallible>(response.await) }
+    });
+    let connection = http1::Builder::new()
+        .timer(TokioTimer::new())
+        .header_read_timeout(timeout)
+        .max_buf_size(MAX_HEADER_SIZE)
+        .keep_alive(false)
+        .serve_connection(TokioIo::new(stream), service);
+    if let Err(e) = connection.await {
+        warn!("Connection failed: {e}");
+    }
+    drop(permit);
+}
+
+async fn handle(
+    pool: Arc<ExporterPool>,
+    request: hyper::Request<Incoming>,
+    timeout: Duration,
+) -> Response {
+    route(&pool, request, timeout)
+        .await
+        .unwrap_or_else(|failure| {
+            warn!("Request failed: {}", failure.message);
+            failure.into_response()
+        })
+}
+
+/// Wait until fewer than the maximum number of connections are open, then
+/// accept the next one.
+async fn accept(
+    listener: &TcpListener,
+    permits: &Arc<Semaphore>,
+) -> std::io::Result<(TcpStream, OwnedSemaphorePermit)> {
+    let permit = permits
+        .clone()
+        .acquire_owned()
+        .await
+        .expect("the connection semaphore is never closed");
+    let (stream, _) = listener.accept().await?;
+    Ok((stream, permit))
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::init();
+    let cli = Cli::parse();
+
+    let mut builder = StaticExporterBuilder::default()
+        .offline_mode(cli.offline)
+        .webdriver_port(cli.webdriver_port)
+        .render_timeout(Duration::from_secs(cli.render_timeout))
+        .retry_policy(RetryPolicy::new(cli.attempts));
+    if cli.cdp {
+        builder = builder.backend(Backend::Cdp);
+    }
+    let pool = Arc::new(builder.build_pool(cli.sessions)?);
+
+    let listener = TcpListener::bind((cli.host.as_str(), cli.port)).await?;
+    println!("Listening on http://{}", listener.local_addr()?);
+
+    let timeout = Duration::from_secs(cli.request_timeout);
+    let permits = Arc::new(Semaphore::new(cli.max_connections.max(1)));
+    let mut connections = JoinSet::new();
+    loop {
+        tokio::select! {
+            accepted = accept(&listener, &permits) => match accepted {
+                Ok((stream, permit)) => {
+                    let pool = pool.clone();
+                    connections.spawn(serve_connection(stream, timeout, permit, move |request| {
+                        handle(pool.clone(), request, timeout)
+                    }));
+                }
+                Err(e) => warn!("Failed to accept connection: {e}"),
+            },
+            Some(_) = connections.join_next(), if !connections.is_empty() => {}
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+
+    info!("Shutting down after {} open requests", connections.len());
+    while connections.join_next().await.is_some() {}
+    if let Ok(mut pool) = Arc::try_unwrap(pool) {
+        pool.close().await;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::*;
+
+    #[test]
+    fn render_request_defaults_and_kaleido_fields() {
+        let request: RenderRequest =
+            serde_json::from_str(r#"{"data": {"data": [], "layout": {}}}"#).unwrap();
+        assert!(matches!(request.image_format(), Ok(ImageFormat::PNG)));
+        assert_eq!(
+            (request.width, request.height, request.scale),
+            (700, 500, 1.0)
+        );
+
+        let request: RenderRequest = serde_json::from_str(
+            r#"{"figure": {"data": []}, "format": "SVG", "width": 300, "height": 200, "scale": 2}"#,
+        )
+        .unwrap();
+        assert!(matches!(request.image_format(), Ok(ImageFormat::SVG)));
+        assert_eq!(request.scale, 2.0);
+    }
+
+    #[test]
+    fn invalid_render_requests() {
+        for body in [
+            r#"{"figure": {}, "format": "gif"}"#,
+            r#"{"figure": {}, "width": 0}"#,
+            r#"{"figure": {}, "scale": -1}"#,
+            r#"{"figure": [1, 2]}"#,
+        ] {
+            let request: RenderRequest = serde_json::from_str(body).unwrap();
+            let failure = request.image_format().unwrap_err();
+            assert_eq!((failure.status, failure.kind), (400, "invalid_request"));
+        }
+    }
+
+    #[test]
+    fn export_errors_map_to_statuses() {
+        let failure = Failure::from_export(&ExportError::Render("Error: bad".into()));
+        assert_eq!((failure.status, failure.kind), (422, "render"));
+        let failure = Failure::from_export(&ExportError::Timeout("waiting for it".into()));
+        assert_eq!((failure.status, failure.kind), (504, "timeout"));
+    }
+
+    #[tokio::test]
+    async fn read_request_bodies() {
+        let timeout = Duration::from_secs(5);
+        let body = read_body(Full::new(Bytes::from_static(b"{}")), timeout)
+            .await
+            .unwrap();
+        assert_eq!(body, Bytes::from_static(b"{}"));
+
+        let large = Full::new(Bytes::from(vec![0; MAX_BODY_SIZE + 1]));
+        let failure = read_body(large, timeout).await.unwrap_err();
+        assert_eq!((failure.status, failure.kind), (413, "payload_too_large"));
+    }
+
+    #[tokio::test]
+    async fn failures_are_json_responses() {
+        let response = Failure::new(404, "not_found", "No route").into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            body,
+            json!({"code": 404, "error": "not_found", "message": "No route"})
+        );
+    }
+
+    /// Serve connections of `listener` with a handler answering with the
+    /// length of the request body.
+    fn spawn_server(listener: TcpListener, max_connections: usize, timeout: Duration) {
+        let permits = Arc::new(Semaphore::new(max_connections));
+        tokio::spawn(async move {
+            while let Ok((stream, permit)) = accept(&listener, &permits).await {
+                tokio::spawn(serve_connection(
+                    stream,
+                    timeout,
+                    permit,
+                    move |request| async move {
+                        match read_body(request.into_body(), timeout).await {
+                            Ok(body) => {
+                                response(StatusCode::OK, "text/plain", body.len().to_string())
+                            }
+                            Err(failure) => failure.into_response(),
+                        }
+                    },
+                ));
+            }
+        });
+    }
+
+    async fn exchange(addr: std::net::SocketAddr, request: &[u8]) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(request).await.unwrap();
+        let mut response = Vec::new();
+        let _ = stream.read_to_end(&mut response).await;
+        String::from_utf8_lossy(&response).into_owned()
+    }
+
+    #[tokio::test]
+    async fn limit_requests_and_connections() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        spawn_server(listener, 1, Duration::from_millis(300));
+
+        let response = exchange(
+            addr,
+            b"POST /render HTTP/1.1\r\nHost: a\r\nTransfer-Encoding: chunked\r\n\r\n2\r\n{}\r\n0\r\n\r\n",
+        )
+        .await;
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "{response}");
+        assert!(response.ends_with("\r\n\r\n2"));
+
+        // A header line longer than the limit is refused.
+        let mut request = b"GET / HTTP/1.1\r\nX-Long: ".to_vec();
+        request.resize(request.len() + 2 * MAX_HEADER_SIZE, b'a');
+        let response = exchange(addr, &request).await;
+        assert!(response.starts_with("HTTP/1.1 431"), "{response}");
+
+        // A client that never finishes its headers holds the only connection
+        // until the timeout closes it.
+        let mut idle = TcpStream::connect(addr).await.unwrap();
+        idle.write_all(b"GET / HTTP/1.1\r\n").await.unwrap();
+        let started = tokio::time::Instant::now();
+        let response = exchange(addr, b"GET / HTTP/1.1\r\nHost: a\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "{response}");
+        assert!(started.elapsed() >= Duration::from_millis(250));
+        let mut rest = Vec::new();
+        let _ = idle.read_to_end(&mut rest).await;
+
+        // A body that stops arriving times out.
+        let response = exchange(
+            addr,
+            b"POST /render HTTP/1.1\r\nHost: a\r\nContent-Length: 10\r\n\r\n{}",
+        )
+        .await;
+        assert!(response.starts_with("HTTP/1.1 408"), "{response}");
+    }
+}
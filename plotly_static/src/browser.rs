@@ -0,0 +1,154 @@
+//! The browser backends that plots are rendered in.
+//!
+//! A [`Browser`] is the process the exporters keep running, and a [`Client`]
+//! one session in it, so that rendering in [`crate::session`] works the same
+//! for WebDriver sessions and Chrome tabs controlled over the DevTools
+//! Protocol.
+
+use anyhow::Result;
+use serde_json::Value;
+
+#[cfg(feature = "cdp")]
+use crate::cdp::{Chrome, Page};
+use crate::{webdriver::WebDriver, ExportError};
+
+/// The ways of driving the headless browser that renders the plots.
+///
+/// # Examples
+///
+/// ```rust
+/// use plotly_static::{Backend, StaticExporterBuilder};
+///
+/// // Launch Chrome directly instead of going through chromedriver
+/// let builder = StaticExporterBuilder::default().backend(Backend::Cdp);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// A WebDriver process, chromedriver or geckodriver depending on the
+    /// enabled feature, that controls the browser
+    WebDriver,
+    /// Chrome/Chromium launched directly and controlled over the Chrome
+    /// DevTools Protocol, requires the `cdp` feature
+    ///
+    /// No chromedriver is needed, so exports keep working when Chrome updates
+    /// to a version the installed chromedriver does not support. The browser
+    /// is taken from the `BROWSER_PATH` environment variable or looked up in
+    /// `PATH` and the default install locations.
+    Cdp,
+}
+
+impl Default for Backend {
+    /// `Cdp` if the `cdp` feature is the only backend feature enabled,
+    /// otherwise `WebDriver`.
+    fn default() -> Self {
+        if cfg!(feature = "cdp") && !cfg!(any(feature = "chromedriver", feature = "geckodriver")) {
+            Self::Cdp
+        } else {
+            Self::WebDriver
+        }
+    }
+}
+
+/// The browser process of an exporter.
+#[derive(Debug)]
+pub(crate) enum Browser {
+    #[cfg_attr(
+        not(any(feature = "chromedriver", feature = "geckodriver")),
+        allow(dead_code)
+    )]
+    WebDriver(WebDriver),
+    #[cfg(feature = "cdp")]
+    Cdp(Chrome),
+}
+
+impl Browser {
+    /// Stop the browser process if it was spawned by the exporter.
+    pub(crate) fn stop(&mut self) -> Result<()> {
+        match self {
+            Self::WebDriver(webdriver) => webdriver.stop(),
+            #[cfg(feature = "cdp")]
+            Self::Cdp(chrome) => chrome.stop(),
+        }
+    }
+
+    /// Get diagnostic information about the browser process.
+    pub(crate) fn get_diagnostics(&self) -> String {
+        match self {
+            Self::WebDriver(webdriver) => webdriver.get_diagnostics(),
+            #[cfg(feature = "cdp")]
+            Self::Cdp(chrome) => chrome.get_diagnostics(),
+        }
+    }
+}
+
+/// A session in the browser that plots are rendered in.
+#[derive(Debug, Clone)]
+pub(crate) enum Client {
+    WebDriver(fantoccini::Client),
+    #[cfg(feature = "cdp")]
+    Cdp(Page),
+}
+
+impl Client {
+    /// Navigate to `url`.
+    pub(crate) async fn goto(&self, url: &str) -> Result<()> {
+        match self {
+            Self::WebDriver(client) => Ok(client.goto(url).await?),
+            #[cfg(feature = "cdp")]
+            Self::Cdp(page) => page.goto(url).await,
+        }
+    }
+
+    /// Run the body of the function `script` with `args` and return its
+    /// result.
+    pub(crate) async fn execute(&self, script: &str, args: Vec<Value>) -> Result<Value> {
+        match self {
+            Self::WebDriver(client) => client.execute(script, args).await.map_err(script_error),
+            #[cfg(feature = "cdp")]
+            Self::Cdp(page) => page.execute(script, args).await,
+        }
+    }
+
+    /// Run the body of the function `script` with `args` followed by a
+    /// callback, and return the value passed to the callback.
+    pub(crate) async fn execute_async(&self, script: &str, args: Vec<Value>) -> Result<Value> {
+        match self {
+            Self::WebDriver(client) => client
+                .execute_async(script, args)
+                .await
+                .map_err(script_error),
+            #[cfg(feature = "cdp")]
+            Self::Cdp(page) => page.execute_async(script, args).await,
+        }
+    }
+
+    /// Whether the session can be reused after an export failed with
+    /// `error`. A Chrome tab keeps running a script that timed out, so it is
+    /// closed instead.
+    #[cfg_attr(not(feature = "cdp"), allow(unused_variables))]
+    pub(crate) fn reusable_after(&self, error: &ExportError) -> bool {
+        match self {
+            Self::WebDriver(_) => true,
+            #[cfg(feature = "cdp")]
+            Self::Cdp(_) => !matches!(error, ExportError::Timeout(_)),
+        }
+    }
+
+    /// End the session.
+    pub(crate) async fn close(self) -> Result<()> {
+        match self {
+            Self::WebDriver(client) => Ok(client.close().await?),
+            #[cfg(feature = "cdp")]
+            Self::Cdp(page) => page.close().await,
+        }
+    }
+}
+
+/// Report the WebDriver `script timeout` error as an [`ExportError::Timeout`].
+fn script_error(e: fantoccini::error::CmdError) -> anyhow::Error {
+    if e.to_string().contains("script timeout") {
+        ExportError::Timeout("waiting for the script to finish".to_string()).into()
+    } else {
+        e.into()
+    }
+}
@@ -0,0 +1,873 @@
+//! Headless Chrome controlled over the Chrome DevTools Protocol.
+//!
+//! The `cdp` backend launches Chrome/Chromium itself with remote debugging on
+//! a free port and talks to it over the DevTools WebSocket, so no
+//! chromedriver matching the browser version is needed. Every browser session
+//! is a separate target (tab) of the one browser process.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+#[cfg(any(test, feature = "debug"))]
+use std::{println as debug, println as info, println as trace, println as warn};
+
+use anyhow::{anyhow, Context, Result};
+use futures_util::{SinkExt, StreamExt};
+#[cfg(not(any(test, feature = "debug")))]
+use log::{debug, info, trace, warn};
+use serde_json::{json, Value};
+use tokio::sync::{broadcast, mpsc as channel, oneshot, Mutex as AsyncMutex};
+use tokio_tungstenite::tungstenite::{protocol::WebSocketConfig, Message};
+
+use crate::ExportError;
+
+const BROWSER_PATH_ENV: &str = "BROWSER_PATH";
+
+/// Executable names of Chrome/Chromium looked up in `PATH`
+const BROWSER_NAMES: [&str; 5] = [
+    "google-chrome",
+    "google-chrome-stable",
+    "chromium",
+    "chromium-browser",
+    "chrome",
+];
+
+/// Time for Chrome to start and open the DevTools endpoint
+const LAUNCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Prefix of the line Chrome prints to stderr once the DevTools endpoint is
+/// open
+const DEVTOOLS_LISTENING: &str = "DevTools listening on ";
+
+static USER_DATA_DIR_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// A Chrome/Chromium browser with remote debugging enabled.
+///
+/// The WebSocket connection is opened by the first session, so that its tasks
+/// run on the runtime of the exporter instead of the one of the builder. The
+/// browser is relaunched when it exits or closes the connection, and stopped
+/// when dropped.
+#[derive(Debug)]
+pub(crate) struct Chrome {
+    browser_path: PathBuf,
+    args: Vec<String>,
+    process: AsyncMutex<Option<Process>>,
+}
+
+impl Chrome {
+    /// Launch Chrome with the command-line flags `args` and wait until it
+    /// accepts DevTools connections.
+    ///
+    /// The browser is looked up in the `BROWSER_PATH` environment variable,
+    /// then in `PATH` and in the default install locations.
+    pub(crate) fn launch(args: &[String]) -> Result<Self> {
+        let browser_path = find_browser()?;
+        let process = Process::spawn(&browser_path, args)?;
+        Ok(Self {
+            browser_path,
+            args: args.to_vec(),
+            process: AsyncMutex::new(Some(process)),
+        })
+    }
+
+    /// Open a new tab in the browser, waiting up to `page_load_timeout` for
+    /// pages to load and `script_timeout` for scripts, like the timeouts of a
+    /// WebDriver session.
+    pub(crate) async fn new_page(
+        &self,
+        page_load_timeout: Duration,
+        script_timeout: Duration,
+    ) -> Result<Page> {
+        let connection = {
+            let mut process = self.process.lock().await;
+            if !process.as_mut().is_some_and(Process::is_running) {
+                if process.take().is_some() {
+                    warn!("Chrome exited or closed the DevTools connection, relaunching it");
+                }
+                let (browser_path, args) = (self.browser_path.clone(), self.args.clone());
+                let spawned =
+                    tokio::task::spawn_blocking(move || Process::spawn(&browser_path, &args))
+                        .await
+                        .map_err(|e| anyhow!("failed to join browser launch thread: {e}"))??;
+                *process = Some(spawned);
+            }
+            let process = process.as_mut().expect("the browser was just launched");
+            match &process.connection {
+                Some(connection) => connection.clone(),
+                None => {
+                    let connection = Connection::open(&process.websocket_url).await?;
+                    process.connection = Some(connection.clone());
+                    connection
+                }
+            }
+        };
+        Page::open(connection, page_load_timeout, script_timeout).await
+    }
+
+    /// Stop the browser and remove its profile directory.
+    pub(crate) fn stop(&mut self) -> Result<()> {
+        self.process.get_mut().take();
+        Ok(())
+    }
+
+    /// Get diagnostic information about the browser process.
+    pub(crate) fn get_diagnostics(&self) -> String {
+        let mut diagnostics = String::new();
+        diagnostics.push_str("Chrome DevTools Diagnostics:\n");
+        diagnostics.push_str(&format!("  Browser Path: {:?}\n", self.browser_path));
+
+        let Ok(mut process) = self.process.try_lock() else {
+            diagnostics.push_str("  Process Status: Launching\n");
+            return diagnostics;
+        };
+        let Some(process) = process.as_mut() else {
+            diagnostics.push_str("  Process ID: None (stopped)\n");
+            return diagnostics;
+        };
+        diagnostics.push_str(&format!("  DevTools URL: {}\n", process.websocket_url));
+        diagnostics.push_str(&format!("  User Data Dir: {:?}\n", process.user_data_dir));
+        diagnostics.push_str(&format!("  Process ID: {}\n", process.child.id()));
+        match process.child.try_wait() {
+            Ok(None) => diagnostics.push_str("  Process Status: Running\n"),
+            Ok(Some(status)) => {
+                diagnostics.push_str(&format!("  Process Status: Exited with {status:?}\n"))
+            }
+            Err(e) => {
+                diagnostics.push_str(&format!("  Process Status: Error checking status: {e}\n"))
+            }
+        }
+        let connected = process
+            .connection
+            .as_ref()
+            .is_some_and(|connection| !connection.is_closed());
+        diagnostics.push_str(&format!("  DevTools Connected: {connected}\n"));
+
+        diagnostics
+    }
+}
+
+/// A running browser process, killed along with its profile directory when
+/// dropped.
+#[derive(Debug)]
+struct Process {
+    websocket_url: String,
+    user_data_dir: PathBuf,
+    child: Child,
+    connection: Option<Arc<Connection>>,
+}
+
+impl Process {
+    /// Launch the browser at `browser_path` with the command-line flags `args`
+    /// and a fresh profile, and wait until it accepts DevTools connections.
+    fn spawn(browser_path: &Path, args: &[String]) -> Result<Self> {
+        let user_data_dir = std::env::temp_dir().join(format!(
+            "plotly_static_cdp_{}_{}",
+            std::process::id(),
+            USER_DATA_DIR_COUNTER.fetch_add(1, Ordering::SeqCst)
+        ));
+
+        let mut command = Command::new(browser_path);
+        command
+            .args(launch_args(args, &user_data_dir))
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped());
+        info!(
+            "Executing command: {:?} {:?}",
+            command.get_program(),
+            command.get_args()
+        );
+        let mut child = command
+            .spawn()
+            .with_context(|| format!("Failed to launch the browser {browser_path:?}"))?;
+
+        let stderr = child.stderr.take().expect("stderr of the browser is piped");
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let mut sender = Some(sender);
+            for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                trace!("Chrome stderr: {line}");
+                if let Some(url) = websocket_url(&line) {
+                    if let Some(sender) = sender.take() {
+                        let _ = sender.send(url.to_string());
+                    }
+                }
+            }
+        });
+
+        let websocket_url = match receiver.recv_timeout(LAUNCH_TIMEOUT) {
+            Ok(url) => url,
+            Err(e) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                let _ = std::fs::remove_dir_all(&user_data_dir);
+                return Err(match e {
+                    mpsc::RecvTimeoutError::Timeout => anyhow!(
+                        "Chrome did not open the DevTools endpoint within {LAUNCH_TIMEOUT:?}"
+                    ),
+                    mpsc::RecvTimeoutError::Disconnected => {
+                        anyhow!("Chrome exited before opening the DevTools endpoint")
+                    }
+                });
+            }
+        };
+        info!("Chrome DevTools listening on {websocket_url}");
+
+        Ok(Self {
+            websocket_url,
+            user_data_dir,
+            child,
+            connection: None,
+        })
+    }
+
+    /// Whether the browser is still running with its DevTools connection, if
+    /// opened, usable.
+    fn is_running(&mut self) -> bool {
+        matches!(self.child.try_wait(), Ok(None))
+            && !self
+                .connection
+                .as_ref()
+                .is_some_and(|connection| connection.is_closed())
+    }
+}
+
+impl Drop for Process {
+    fn drop(&mut self) {
+        info!("Stopping Chrome (PID: {})", self.child.id());
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        if let Err(e) = std::fs::remove_dir_all(&self.user_data_dir) {
+            debug!("Failed to remove {:?}: {e}", self.user_data_dir);
+        }
+    }
+}
+
+/// A tab of the browser, attached to with its own DevTools session.
+#[derive(Debug, Clone)]
+pub(crate) struct Page {
+    connection: Arc<Connection>,
+    target_id: String,
+    session_id: String,
+    page_load_timeout: Duration,
+    script_timeout: Duration,
+}
+
+impl Page {
+    async fn open(
+        connection: Arc<Connection>,
+        page_load_timeout: Duration,
+        script_timeout: Duration,
+    ) -> Result<Self> {
+        let target = connection
+            .call(None, "Target.createTarget", json!({"url": "about:blank"}))
+            .await?;
+        let target_id = string_field(&target, "targetId")?;
+        let session = connection
+            .call(
+                None,
+                "Target.attachToTarget",
+                json!({"targetId": target_id, "flatten": true}),
+            )
+            .await?;
+        let page = Self {
+            connection,
+            session_id: string_field(&session, "sessionId")?,
+            target_id,
+            page_load_timeout,
+            script_timeout,
+        };
+        page.call("Page.enable", json!({})).await?;
+        debug!("Opened Chrome tab {}", page.target_id);
+        Ok(page)
+    }
+
+    /// Navigate to `url` and wait for its load event.
+    pub(crate) async fn goto(&self, url: &str) -> Result<()> {
+        let mut events = self.connection.events.subscribe();
+        let navigation = self.call("Page.navigate", json!({"url": url})).await?;
+        if let Some(error) = navigation.get("errorText").and_then(Value::as_str) {
+            return Err(anyhow!("Failed to navigate to the page: {error}"));
+        }
+
+        let loaded = async {
+            loop {
+                match events.recv().await {
+                    Ok(event) if event.is_load_of(&self.session_id) => return Ok(()),
+                    Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(broadcast::error::RecvError::Closed) => {
+                        return Err(anyhow!("Chrome DevTools connection closed"))
+                    }
+                }
+            }
+        };
+        tokio::time::timeout(self.page_load_timeout, loaded)
+            .await
+            .map_err(|_| ExportError::Timeout("waiting for the page to load".to_string()))?
+    }
+
+    /// Run the body of the function `script` with `args` as its `arguments`
+    /// and return its result, like WebDriver's Execute Script.
+    pub(crate) async fn execute(&self, script: &str, args: Vec<Value>) -> Result<Value> {
+        self.evaluate(&sync_script_expression(script, &args)?, false)
+            .await
+    }
+
+    /// Run the body of the function `script` with `args` as its `arguments`,
+    /// followed by a callback, and return the value passed to the callback,
+    /// like WebDriver's Execute Async Script.
+    pub(crate) async fn execute_async(&self, script: &str, args: Vec<Value>) -> Result<Value> {
+        self.evaluate(&async_script_expression(script, &args)?, true)
+            .await
+    }
+
+    /// Close the tab.
+    pub(crate) async fn close(self) -> Result<()> {
+        self.connection
+            .call(
+                None,
+                "Target.closeTarget",
+                json!({"targetId": self.target_id}),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn evaluate(&self, expression: &str, await_promise: bool) -> Result<Value> {
+        let params = json!({
+            "expression": expression,
+            "awaitPromise": await_promise,
+            "returnByValue": true,
+        });
+        let result =
+            tokio::time::timeout(self.script_timeout, self.call("Runtime.evaluate", params))
+                .await
+                .map_err(|_| {
+                    ExportError::Timeout("waiting for the script to finish".to_string())
+                })??;
+        evaluation_result(result)
+    }
+
+    async fn call(&self, method: &str, params: Value) -> Result<Value> {
+        self.connection
+            .call(Some(&self.session_id), method, params)
+            .await
+    }
+}
+
+/// An event sent by the browser.
+#[derive(Debug, Clone)]
+struct Event {
+    session_id: Option<String>,
+    method: String,
+}
+
+impl Event {
+    fn is_load_of(&self, session_id: &str) -> bool {
+        self.method == "Page.loadEventFired" && self.session_id.as_deref() == Some(session_id)
+    }
+}
+
+/// The commands waiting for a response, by id.
+#[derive(Debug, Default)]
+struct Pending {
+    senders: HashMap<u64, oneshot::Sender<Result<Value>>>,
+    /// Whether the browser closed the connection
+    closed: bool,
+}
+
+/// The DevTools WebSocket connection to the browser, shared by all tabs.
+///
+/// Commands are written by a sender task; a receiver task hands responses to
+/// the pending commands by id and broadcasts events.
+#[derive(Debug)]
+struct Connection {
+    next_id: AtomicU64,
+    outgoing: channel::UnboundedSender<String>,
+    pending: Arc<Mutex<Pending>>,
+    events: broadcast::Sender<Event>,
+}
+
+impl Connection {
+    async fn open(url: &str) -> Result<Arc<Self>> {
+        debug!("Connecting to Chrome DevTools at {url}");
+        // Plots and exported images easily exceed the default message size
+        let config = WebSocketConfig::default()
+            .max_message_size(None)
+            .max_frame_size(None);
+        let (websocket, _) = tokio_tungstenite::connect_async_with_config(url, Some(config), true)
+            .await
+            .with_context(|| "Failed to connect to Chrome DevTools")?;
+        let (mut sink, mut stream) = websocket.split();
+
+        let (outgoing, mut queue) = channel::unbounded_channel::<String>();
+        let pending: Arc<Mutex<Pending>> = Arc::default();
+        let (events, _) = broadcast::channel(1024);
+
+        tokio::spawn(async move {
+            while let Some(message) = queue.recv().await {
+                if let Err(e) = sink.send(Message::text(message)).await {
+                    warn!("Failed to send Chrome DevTools command: {e}");
+                    break;
+                }
+            }
+            let _ = sink.close().await;
+        });
+
+        let receiver_pending = pending.clone();
+        let receiver_events = events.clone();
+        tokio::spawn(async move {
+            while let Some(message) = stream.next().await {
+                let text = match message {
+                    Ok(Message::Text(text)) => text,
+                    Ok(Message::Close(_)) | Err(_) => break,
+                    Ok(_) => continue,
+                };
+                match serde_json::from_str::<Value>(&text) {
+                    Ok(message) => dispatch(
+                        message,
+                        &mut receiver_pending.lock().unwrap(),
+                        &receiver_events,
+                    ),
+                    Err(e) => warn!("Invalid Chrome DevTools message: {e}"),
+                }
+            }
+            debug!("Chrome DevTools connection closed");
+            // Fail the commands still waiting for a response
+            let mut pending = receiver_pending.lock().unwrap();
+            pending.closed = true;
+            pending.senders.clear();
+        });
+
+        Ok(Arc::new(Self {
+            next_id: AtomicU64::new(1),
+            outgoing,
+            pending,
+            events,
+        }))
+    }
+
+    /// Send the command `method` to the browser, or to the tab of
+    /// `session_id`, and wait for its result.
+    async fn call(&self, session_id: Option<&str>, method: &str, params: Value) -> Result<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let mut command = json!({"id": id, "method": method, "params": params});
+        if let Some(session_id) = session_id {
+            command["sessionId"] = session_id.into();
+        }
+
+        let (sender, receiver) = oneshot::channel();
+        {
+            let mut pending = self.pending.lock().unwrap();
+            if pending.closed || self.outgoing.send(command.to_string()).is_err() {
+                return Err(anyhow!("Chrome DevTools connection closed"));
+            }
+            pending.senders.insert(id, sender);
+        }
+        // Forget the command if the caller stops waiting, e.g. on a timeout
+        let _forget = ForgetOnDrop {
+            pending: &self.pending,
+            id,
+        };
+        receiver
+            .await
+            .map_err(|_| anyhow!("Chrome DevTools connection closed"))?
+            .with_context(|| format!("Chrome DevTools command {method} failed"))
+    }
+
+    fn is_closed(&self) -> bool {
+        self.pending.lock().unwrap().closed || self.outgoing.is_closed()
+    }
+}
+
+/// Removes a command from the pending commands when dropped.
+struct ForgetOnDrop<'a> {
+    pending: &'a Mutex<Pending>,
+    id: u64,
+}
+
+impl Drop for ForgetOnDrop<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.senders.remove(&self.id);
+        }
+    }
+}
+
+/// Hand a message of the browser to the command it responds to, or broadcast
+/// it as an event.
+fn dispatch(message: Value, pending: &mut Pending, events: &broadcast::Sender<Event>) {
+    if let Some(id) = message.get("id").and_then(Value::as_u64) {
+        let Some(sender) = pending.senders.remove(&id) else {
+            return;
+        };
+        let result = match message.get("error") {
+            Some(error) => Err(anyhow!(
+                "{}",
+                error
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .unwrap_or("unknown error")
+            )),
+            None => Ok(message.get("result").cloned().unwrap_or(Value::Null)),
+        };
+        let _ = sender.send(result);
+    } else if let Some(method) = message.get("method").and_then(Value::as_str) {
+        let _ = events.send(Event {
+            session_id: message
+                .get("sessionId")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            method: method.to_string(),
+        });
+    }
+}
+
+/// The value of a `Runtime.evaluate` result, or the exception it threw.
+fn evaluation_result(result: Value) -> Result<Value> {
+    if let Some(exception) = result.get("exceptionDetails") {
+        let message = exception
+            .pointer("/exception/description")
+            .or_else(|| exception.get("text"))
+            .and_then(Value::as_str)
+            .unwrap_or("unknown error");
+        return Err(anyhow!("JavaScript error: {message}"));
+    }
+    Ok(result
+        .pointer("/result/value")
+        .cloned()
+        .unwrap_or(Value::Null))
+}
+
+fn sync_script_expression(script: &str, args: &[Value]) -> Result<String> {
+    let args = serde_json::to_string(args)?;
+    Ok(format!(
+        "(function() {{\n{script}\n}}).apply(window, {args})"
+    ))
+}
+
+fn async_script_expression(script: &str, args: &[Value]) -> Result<String> {
+    let args = serde_json::to_string(args)?;
+    Ok(format!(
+        "new Promise((resolve, reject) => {{\n\
+         try {{\n\
+         (function() {{\n{script}\n}}).apply(window, [...{args}, resolve]);\n\
+         }} catch (e) {{\n\
+         reject(e);\n\
+         }}\n\
+         }})"
+    ))
+}
+
+fn string_field(value: &Value, field: &str) -> Result<String> {
+    value
+        .get(field)
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("Chrome DevTools response without {field}"))
+}
+
+/// The DevTools WebSocket URL printed by Chrome on `line` of its stderr.
+fn websocket_url(line: &str) -> Option<&str> {
+    line.trim()
+        .strip_prefix(DEVTOOLS_LISTENING)
+        .filter(|url| url.starts_with("ws://"))
+}
+
+/// The command-line flags to launch Chrome with, from the configured browser
+/// capabilities. Headless mode is enforced and remote debugging is opened on
+/// a free port with a fresh profile.
+fn launch_args(caps: &[String], user_data_dir: &Path) -> Vec<String> {
+    let mut args: Vec<String> = caps
+        .iter()
+        .filter(|arg| {
+            !arg.starts_with("--remote-debugging-") && !arg.starts_with("--user-data-dir")
+        })
+        .cloned()
+        .collect();
+    if !args
+        .iter()
+        .any(|arg| arg.trim_start_matches('-').starts_with("headless"))
+    {
+        args.push("--headless=new".to_string());
+    }
+    args.push("--remote-debugging-port=0".to_string());
+    args.push(format!("--user-data-dir={}", user_data_dir.display()));
+    args.push("about:blank".to_string());
+    args
+}
+
+fn find_browser() -> Result<PathBuf> {
+    if let Ok(path) = std::env::var(BROWSER_PATH_ENV) {
+        let path = PathBuf::from(path);
+        return if path.exists() {
+            Ok(path)
+        } else {
+            Err(anyhow!(
+                "Browser executable not found at {BROWSER_PATH_ENV}: '{}'",
+                path.display()
+            ))
+        };
+    }
+
+    let in_path = std::env::var_os("PATH")
+        .map(|paths| std::env::split_paths(&paths).collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter()
+        .flat_map(|dir| {
+            BROWSER_NAMES
+                .iter()
+                .map(move |name| dir.join(format!("{name}{}", std::env::consts::EXE_SUFFIX)))
+        });
+    in_path
+        .chain(default_locations())
+        .find(|path| path.is_file())
+        .ok_or_else(|| {
+            anyhow!(
+                "Chrome/Chromium not found. Install it or set the {BROWSER_PATH_ENV} environment variable to the browser executable"
+            )
+        })
+}
+
+#[cfg(target_os = "macos")]
+fn default_locations() -> Vec<PathBuf> {
+    [
+        "/Applications/Google Chrome.app/Contents/MacOS/Google Chrome",
+        "/Applications/Chromium.app/Contents/MacOS/Chromium",
+    ]
+    .iter()
+    .map(PathBuf::from)
+    .collect()
+}
+
+#[cfg(target_os = "windows")]
+fn default_locations() -> Vec<PathBuf> {
+    ["ProgramFiles", "ProgramFiles(x86)", "LocalAppData"]
+        .iter()
+        .filter_map(std::env::var_os)
+        .map(|dir| PathBuf::from(dir).join(r"Google\Chrome\Application\chrome.exe"))
+        .collect()
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn default_locations() -> Vec<PathBuf> {
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    #[test]
+    fn parse_devtools_endpoint() {
+        let line = "DevTools listening on ws://127.0.0.1:40215/devtools/browser/0c3e-4f1a\n";
+        assert_eq!(
+            websocket_url(line),
+            Some("ws://127.0.0.1:40215/devtools/browser/0c3e-4f1a")
+        );
+        assert_eq!(websocket_url("[0101/000000.0:INFO] Starting"), None);
+    }
+
+    #[test]
+    fn launch_args_enforce_headless_debugging() {
+        let caps = vec![
+            "--no-sandbox".to_string(),
+            "--remote-debugging-port=9222".to_string(),
+        ];
+        let args = launch_args(&caps, Path::new("/tmp/profile"));
+        assert_eq!(
+            args,
+            [
+                "--no-sandbox",
+                "--headless=new",
+                "--remote-debugging-port=0",
+                "--user-data-dir=/tmp/profile",
+                "about:blank"
+            ]
+        );
+
+        let caps = vec!["--headless".to_string()];
+        let args = launch_args(&caps, Path::new("/tmp/profile"));
+        assert_eq!(args.iter().filter(|a| a.contains("headless")).count(), 1);
+    }
+
+    #[test]
+    fn evaluation_results_and_exceptions() {
+        let result = json!({"result": {"type": "string", "value": "OK"}});
+        assert_eq!(evaluation_result(result).unwrap(), "OK");
+
+        let result = json!({"result": {"type": "undefined"}});
+        assert_eq!(evaluation_result(result).unwrap(), Value::Null);
+
+        let result = json!({
+            "result": {"type": "object"},
+            "exceptionDetails": {
+                "text": "Uncaught",
+                "exception": {"description": "ReferenceError: Plotly is not defined"}
+            }
+        });
+        let error = evaluation_result(result).unwrap_err().to_string();
+        assert_eq!(
+            error,
+            "JavaScript error: ReferenceError: Plotly is not defined"
+        );
+    }
+
+    /// Answer `Runtime.evaluate` with the length of its expression and fail
+    /// every other command, sending an event before each response.
+    async fn mock_devtools() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut websocket = tokio_tungstenite::accept_async(stream).await.unwrap();
+            while let Some(Ok(Message::Text(text))) = websocket.next().await {
+                let command: Value = serde_json::from_str(&text).unwrap();
+                let event = json!({"method": "Page.loadEventFired", "sessionId": "S1"});
+                websocket
+                    .send(Message::text(event.to_string()))
+                    .await
+                    .unwrap();
+                let response = match command["method"].as_str() {
+                    Some("Runtime.evaluate") => json!({
+                        "id": command["id"],
+                        "result": {"result": {
+                            "type": "number",
+                            "value": command["params"]["expression"].as_str().unwrap().len()
+                        }}
+                    }),
+                    _ => json!({"id": command["id"], "error": {"message": "Not supported"}}),
+                };
+                websocket
+                    .send(Message::text(response.to_string()))
+                    .await
+                    .unwrap();
+            }
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn commands_receive_their_responses() {
+        let connection = Connection::open(&mock_devtools().await).await.unwrap();
+        let mut events = connection.events.subscribe();
+        let page = Page {
+            connection: connection.clone(),
+            target_id: "T1".to_string(),
+            session_id: "S1".to_string(),
+            page_load_timeout: Duration::from_secs(300),
+            script_timeout: Duration::from_secs(30),
+        };
+
+        let expression = async_script_expression("arguments[1](arguments[0]);", &[json!(1)]);
+        let (short, long) = tokio::join!(
+            page.execute("return 1;", vec![]),
+            page.execute_async("arguments[1](arguments[0]);", vec![json!(1)])
+        );
+        assert_eq!(
+            short.unwrap(),
+            sync_script_expression("return 1;", &[]).unwrap().len()
+        );
+        assert_eq!(long.unwrap(), expression.unwrap().len());
+        assert!(events.recv().await.unwrap().is_load_of("S1"));
+
+        let error = page.close().await.unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "Chrome DevTools command Target.closeTarget failed: Not supported"
+        );
+    }
+
+    #[tokio::test]
+    async fn timed_out_commands_are_forgotten() {
+        // A browser that never responds
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut websocket = tokio_tungstenite::accept_async(stream).await.unwrap();
+            while let Some(Ok(_)) = websocket.next().await {}
+        });
+
+        let connection = Connection::open(&url).await.unwrap();
+        let page = Page {
+            connection: connection.clone(),
+            target_id: "T1".to_string(),
+            session_id: "S1".to_string(),
+            page_load_timeout: Duration::from_secs(300),
+            script_timeout: Duration::from_millis(50),
+        };
+        let error = page.execute("return 1;", vec![]).await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<ExportError>(),
+            Some(ExportError::Timeout(_))
+        ));
+        assert!(connection.pending.lock().unwrap().senders.is_empty());
+    }
+
+    /// A browser that announces an unreachable DevTools endpoint, creates
+    /// its profile directory and waits.
+    #[cfg(unix)]
+    fn fake_browser() -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = std::env::temp_dir().join(format!(
+            "plotly_static_fake_chrome_{}_{}",
+            std::process::id(),
+            USER_DATA_DIR_COUNTER.fetch_add(1, Ordering::SeqCst)
+        ));
+        let script = r#"#!/bin/sh
+for arg; do
+    case "$arg" in --user-data-dir=*) mkdir -p "${arg#--user-data-dir=}" ;; esac
+done
+echo "DevTools listening on ws://127.0.0.1:1/devtools/browser/fake" >&2
+exec sleep 60
+"#;
+        std::fs::write(&path, script).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn relaunch_exited_browser_and_stop_on_drop() {
+        let browser_path = fake_browser();
+        let chrome = Chrome {
+            process: AsyncMutex::new(Some(Process::spawn(&browser_path, &[]).unwrap())),
+            browser_path: browser_path.clone(),
+            args: Vec::new(),
+        };
+        let first_dir = {
+            let mut process = chrome.process.lock().await;
+            let process = process.as_mut().unwrap();
+            process.child.kill().unwrap();
+            process.child.wait().unwrap();
+            process.user_data_dir.clone()
+        };
+        assert!(first_dir.exists());
+
+        // Relaunched, but the fake endpoint refuses connections
+        let timeout = Duration::from_secs(5);
+        assert!(chrome.new_page(timeout, timeout).await.is_err());
+        assert!(!first_dir.exists());
+        let second_dir = {
+            let mut process = chrome.process.lock().await;
+            let process = process.as_mut().unwrap();
+            assert!(process.is_running());
+            process.user_data_dir.clone()
+        };
+        assert_ne!(first_dir, second_dir);
+        assert!(second_dir.exists());
+
+        drop(chrome);
+        assert!(!second_dir.exists());
+        std::fs::remove_file(browser_path).unwrap();
+    }
+}
@@ -0,0 +1,243 @@
+//! Physical print sizes and the pixel density metadata of raster images.
+//!
+//! Plotly lays plots out in CSS pixels, of which there are 96 per inch, and
+//! `Plotly.toImage` renders `scale` image pixels per CSS pixel. An exported
+//! image therefore has a density of `96 * scale` pixels per inch, which is
+//! recorded in the `pHYs` chunk of PNG files and the JFIF header of JPEG
+//! files.
+
+#[cfg(any(test, feature = "debug"))]
+use std::println as warn;
+
+#[cfg(not(any(test, feature = "debug")))]
+use log::warn;
+
+use crate::ImageFormat;
+
+/// CSS pixels per inch.
+const CSS_DPI: f64 = 96.0;
+
+const MM_PER_INCH: f64 = 25.4;
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Plot dimensions for printing at a physical size and resolution.
+///
+/// `width` and `height` are the layout size of the plot in CSS pixels and
+/// `scale` renders it with `dpi` image pixels per inch, so that fonts and
+/// lines keep their intended size on paper. Pass the fields to `write_fig`;
+/// PNG and JPEG files are tagged with the resolution.
+///
+/// # Examples
+///
+/// ```rust
+/// use plotly_static::PrintSize;
+///
+/// // A single column figure of a journal, 85mm x 60mm at 300 dpi
+/// let size = PrintSize::from_mm(85.0, 60.0, 300.0);
+/// assert_eq!((size.width, size.height), (321, 227));
+/// assert_eq!(size.dpi(), 300.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrintSize {
+    /// Plot width in CSS pixels
+    pub width: usize,
+    /// Plot height in CSS pixels
+    pub height: usize,
+    /// Image pixels per CSS pixel
+    pub scale: f64,
+}
+
+impl PrintSize {
+    /// The plot dimensions for a `width_mm` x `height_mm` print at `dpi`.
+    pub fn from_mm(width_mm: f64, height_mm: f64, dpi: f64) -> Self {
+        Self::from_inches(width_mm / MM_PER_INCH, height_mm / MM_PER_INCH, dpi)
+    }
+
+    /// The plot dimensions for a `width_in` x `height_in` inch print at
+    /// `dpi`.
+    pub fn from_inches(width_in: f64, height_in: f64, dpi: f64) -> Self {
+        Self {
+            width: (width_in * CSS_DPI).round() as usize,
+            height: (height_in * CSS_DPI).round() as usize,
+            scale: dpi / CSS_DPI,
+        }
+    }
+
+    /// The resolution of the exported image in pixels per inch.
+    pub fn dpi(&self) -> f64 {
+        scale_to_dpi(self.scale)
+    }
+}
+
+pub(crate) fn scale_to_dpi(scale: f64) -> f64 {
+    scale * CSS_DPI
+}
+
+/// Record a density of `dpi` in PNG and JPEG image data. Other formats and
+/// data that cannot be parsed are returned unchanged.
+pub(crate) fn set_density(format: &ImageFormat, data: Vec<u8>, dpi: f64) -> Vec<u8> {
+    if !dpi.is_finite() || dpi <= 0.0 {
+        return data;
+    }
+    let result = match format {
+        ImageFormat::PNG => set_png_density(&data, dpi),
+        ImageFormat::JPEG => set_jpeg_density(&data, dpi),
+        _ => return data,
+    };
+    result.unwrap_or_else(|| {
+        warn!("Failed to write the {format} density metadata, the image is left unchanged");
+        data
+    })
+}
+
+/// The `pHYs` density of `dpi` for the PNG encoder, `None` if `dpi` is not a
+/// valid density.
+pub(crate) fn png_pixel_dims(dpi: f64) -> Option<png::PixelDimensions> {
+    (dpi.is_finite() && dpi > 0.0).then(|| png::PixelDimensions {
+        xppu: pixels_per_meter(dpi),
+        yppu: pixels_per_meter(dpi),
+        unit: png::Unit::Meter,
+    })
+}
+
+fn pixels_per_meter(dpi: f64) -> u32 {
+    (dpi / MM_PER_INCH * 1000.0).round() as u32
+}
+
+/// Insert a `pHYs` chunk after the `IHDR` chunk, replacing an existing one.
+fn set_png_density(data: &[u8], dpi: f64) -> Option<Vec<u8>> {
+    let mut chunks = data.strip_prefix(PNG_SIGNATURE)?;
+    let pixels_per_meter = pixels_per_meter(dpi).to_be_bytes();
+
+    let mut phys = Vec::with_capacity(9);
+    phys.extend_from_slice(&pixels_per_meter);
+    phys.extend_from_slice(&pixels_per_meter);
+    phys.push(1); // the unit is the meter
+
+    let mut out = Vec::with_capacity(data.len() + 21);
+    out.extend_from_slice(PNG_SIGNATURE);
+    while !chunks.is_empty() {
+        let length = u32::from_be_bytes(chunks.get(..4)?.try_into().ok()?) as usize;
+        let chunk = chunks.get(..12 + length)?;
+        let chunk_type = &chunk[4..8];
+        chunks = &chunks[12 + length..];
+
+        if chunk_type == b"pHYs" {
+            continue;
+        }
+        out.extend_from_slice(chunk);
+        if chunk_type == b"IHDR" {
+            write_png_chunk(&mut out, b"pHYs", &phys);
+        }
+    }
+    Some(out)
+}
+
+fn write_png_chunk(out: &mut Vec<u8>, chunk_type: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    out.extend_from_slice(chunk_type);
+    out.extend_from_slice(data);
+    let crc = crc32(chunk_type.iter().chain(data));
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// The CRC-32 of PNG chunks (ISO 3309).
+fn crc32<'a>(bytes: impl Iterator<Item = &'a u8>) -> u32 {
+    let mut crc = 0xffff_ffff_u32;
+    for byte in bytes {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Set the density of the JFIF `APP0` segment, inserting one after the start
+/// of image marker if there is none.
+fn set_jpeg_density(data: &[u8], dpi: f64) -> Option<Vec<u8>> {
+    let body = data.strip_prefix(&[0xff, 0xd8])?;
+    let density = (dpi.round().min(f64::from(u16::MAX)) as u16).to_be_bytes();
+
+    let mut out = data.to_vec();
+    if body.starts_with(&[0xff, 0xe0]) && body.get(4..9) == Some(b"JFIF\0") && body.len() >= 16 {
+        // SOI, APP0 marker, length, identifier and version precede the units
+        out[13] = 1; // dots per inch
+        out[14..16].copy_from_slice(&density);
+        out[16..18].copy_from_slice(&density);
+    } else {
+        let mut app0 = vec![0xff, 0xe0, 0x00, 0x10];
+        app0.extend_from_slice(b"JFIF\0");
+        app0.extend_from_slice(&[1, 1, 1]);
+        app0.extend_from_slice(&density);
+        app0.extend_from_slice(&density);
+        app0.extend_from_slice(&[0, 0]);
+        out.splice(2..2, app0);
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png_chunk_types(data: &[u8]) -> Vec<String> {
+        let mut chunks = &data[8..];
+        let mut types = Vec::new();
+        while !chunks.is_empty() {
+            let length = u32::from_be_bytes(chunks[..4].try_into().unwrap()) as usize;
+            types.push(String::from_utf8_lossy(&chunks[4..8]).to_string());
+            chunks = &chunks[12 + length..];
+        }
+        types
+    }
+
+    #[test]
+    fn png_density_is_written_after_header() {
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png.extend_from_slice(&[0, 0, 0, 13]);
+        png.extend_from_slice(b"IHDR");
+        png.extend_from_slice(&[0, 0, 0, 1, 0, 0, 0, 1, 8, 6, 0, 0, 0]);
+        png.extend_from_slice(&[0x1f, 0x15, 0xc4, 0x89]);
+        png.extend_from_slice(&[0, 0, 0, 0]);
+        png.extend_from_slice(b"IEND");
+        png.extend_from_slice(&[0xae, 0x42, 0x60, 0x82]);
+
+        let tagged = set_density(&ImageFormat::PNG, png, 300.0);
+        assert_eq!(png_chunk_types(&tagged), ["IHDR", "pHYs", "IEND"]);
+        // 300 dpi are 11811 pixels per meter, the unit byte is 1 (meter)
+        let phys = &tagged[33 + 8..33 + 17];
+        assert_eq!(phys, [0, 0, 0x2e, 0x23, 0, 0, 0x2e, 0x23, 1]);
+        // The CRC of the pHYs chunk as computed by libpng
+        assert_eq!(&tagged[33 + 17..33 + 21], [0x78, 0xa5, 0x3f, 0x76]);
+
+        let retagged = set_density(&ImageFormat::PNG, tagged.clone(), 300.0);
+        assert_eq!(retagged, tagged);
+    }
+
+    #[test]
+    fn jpeg_density_is_written_to_jfif_header() {
+        let mut jpeg = vec![0xff, 0xd8, 0xff, 0xe0, 0x00, 0x10];
+        jpeg.extend_from_slice(b"JFIF\0");
+        jpeg.extend_from_slice(&[1, 1, 0, 0, 1, 0, 1, 0, 0, 0xff, 0xd9]);
+        let tagged = set_density(&ImageFormat::JPEG, jpeg.clone(), 300.0);
+        assert_eq!(tagged.len(), jpeg.len());
+        assert_eq!(&tagged[13..18], [1, 0x01, 0x2c, 0x01, 0x2c]);
+
+        let tagged = set_density(&ImageFormat::JPEG, vec![0xff, 0xd8, 0xff, 0xd9], 150.0);
+        assert_eq!(tagged.len(), 4 + 18);
+        assert_eq!(&tagged[6..11], b"JFIF\0");
+        assert_eq!(&tagged[13..18], [1, 0, 150, 0, 150]);
+        assert_eq!(&tagged[20..], [0xff, 0xd9]);
+    }
+
+    #[test]
+    fn print_size_matches_physical_size() {
+        let size = PrintSize::from_inches(3.5, 2.0, 600.0);
+        assert_eq!((size.width, size.height), (336, 192));
+        assert_eq!(size.scale, 6.25);
+        assert_eq!(size.dpi(), 600.0);
+    }
+}
@@ -0,0 +1,141 @@
+//! Error type returned by the exporters.
+
+use std::{fmt, io};
+
+/// Errors that can occur while exporting a plot.
+///
+/// The variants tell apart the stage of the export that failed, e.g. to retry
+/// after a [`Timeout`](ExportError::Timeout) but report the message of a
+/// plot that plotly.js cannot draw.
+///
+/// # Examples
+///
+/// ```no_run
+/// // This example requires a running WebDriver (chromedriver/geckodriver) and a browser.
+/// // It cannot be run as a doc test.
+/// use plotly_static::{ExportError, ImageFormat, StaticExporterBuilder};
+/// use serde_json::json;
+/// use std::path::Path;
+///
+/// let plot = json!({"data": [{"type": "scatter", "y": [1, 2]}], "layout": {}});
+/// let mut exporter = StaticExporterBuilder::default().build().unwrap();
+///
+/// match exporter.write_fig(Path::new("plot"), &plot, ImageFormat::PNG, 800, 600, 1.0) {
+///     Ok(()) => {}
+///     Err(ExportError::Render(message)) => eprintln!("Invalid plot: {message}"),
+///     Err(ExportError::Timeout(_)) => eprintln!("The browser is too slow, try again"),
+///     Err(e) => eprintln!("Export failed: {e}"),
+/// }
+/// exporter.close();
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ExportError {
+    /// The WebDriver or browser could not be found or started, or no backend
+    /// feature is enabled.
+    DriverLaunch(String),
+    /// No session could be opened in the WebDriver or browser.
+    SessionCreation(String),
+    /// The page with the Plotly libraries could not be loaded in the browser
+    /// session.
+    PageLoad(String),
+    /// plotly.js failed to draw or export the plot, with the message of the
+    /// JavaScript error.
+    Render(String),
+    /// The browser did not finish a step of the export in time.
+    Timeout(String),
+    /// The data returned by the browser is not a valid image.
+    Decode(String),
+    /// The exported images could not be assembled into an animation or PDF.
+    Encode(String),
+    /// Writing an exported file failed.
+    Io(io::Error),
+    /// The export cannot be done as requested, e.g. an animation without
+    /// frames or a sync exporter used in an async context.
+    InvalidInput(String),
+}
+
+impl ExportError {
+    /// `error` if it is an `ExportError`, otherwise `kind` with its message.
+    pub(crate) fn classify(error: anyhow::Error, kind: fn(String) -> Self) -> Self {
+        match error.downcast::<Self>() {
+            Ok(e) => e,
+            Err(e) => kind(format!("{e:#}")),
+        }
+    }
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::DriverLaunch(msg) => write!(f, "failed to launch the browser: {msg}"),
+            ExportError::SessionCreation(msg) => {
+                write!(f, "failed to create a browser session: {msg}")
+            }
+            ExportError::PageLoad(msg) => write!(f, "failed to load the export page: {msg}"),
+            ExportError::Render(msg) => write!(f, "plotly.js failed to render the plot: {msg}"),
+            ExportError::Timeout(msg) => write!(f, "timed out {msg}"),
+            ExportError::Decode(msg) => write!(f, "failed to decode the exported image: {msg}"),
+            ExportError::Encode(msg) => write!(f, "failed to encode the export: {msg}"),
+            ExportError::Io(e) => write!(f, "failed to write the export: {e}"),
+            ExportError::InvalidInput(msg) => write!(f, "invalid export: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ExportError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ExportError {
+    fn from(e: io::Error) -> Self {
+        ExportError::Io(e)
+    }
+}
+
+impl From<base64::DecodeError> for ExportError {
+    fn from(e: base64::DecodeError) -> Self {
+        ExportError::Decode(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::{anyhow, Context};
+
+    use super::*;
+
+    #[test]
+    fn classify_keeps_export_errors() {
+        let error = anyhow::Error::from(ExportError::Timeout("waiting for Plotly".into()))
+            .context("Failed to load the export page");
+        assert!(matches!(
+            ExportError::classify(error, ExportError::PageLoad),
+            ExportError::Timeout(msg) if msg == "waiting for Plotly"
+        ));
+
+        let error = Err::<(), _>(anyhow!("net::ERR_NAME_NOT_RESOLVED"))
+            .context("Failed to navigate")
+            .unwrap_err();
+        assert!(matches!(
+            ExportError::classify(error, ExportError::PageLoad),
+            ExportError::PageLoad(msg) if msg == "Failed to navigate: net::ERR_NAME_NOT_RESOLVED"
+        ));
+    }
+
+    #[test]
+    fn display_names_the_failed_stage() {
+        let e = ExportError::Render("Error: No DOM element with id 'x' exists".into());
+        assert_eq!(
+            e.to_string(),
+            "plotly.js failed to render the plot: Error: No DOM element with id 'x' exists"
+        );
+        let e = ExportError::Timeout("waiting for the page to load".into());
+        assert_eq!(e.to_string(), "timed out waiting for the page to load");
+    }
+}
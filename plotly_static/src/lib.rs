@@ -66,6 +66,8 @@
 //!
 //! - `chromedriver`: Use Chrome/Chromium for rendering
 //! - `geckodriver`: Use Firefox for rendering
+//! - `cdp`: Use Chrome/Chromium for rendering without a WebDriver, see
+//!   [`Backend::Cdp`]
 //!
 //! ### Optional Features
 //!
@@ -139,6 +141,25 @@
 //! The library will automatically use these binaries when creating WebDriver
 //! sessions.
 //!
+//! ### Chrome Without WebDriver
+//!
+//! With the `cdp` feature, [`Backend::Cdp`] launches Chrome/Chromium directly
+//! and controls it over the Chrome DevTools Protocol. No chromedriver is
+//! needed, so there is no driver version that has to match the browser. The
+//! browser is taken from `BROWSER_PATH`, or looked up in `PATH` and the default
+//! install locations.
+//!
+//! ```no_run
+//! // This example requires Chrome/Chromium and the `cdp` feature.
+//! // It cannot be run as a doc test.
+//! use plotly_static::{Backend, StaticExporterBuilder};
+//!
+//! let exporter = StaticExporterBuilder::default()
+//!     .backend(Backend::Cdp)
+//!     .build()
+//!     .expect("Failed to build StaticExporter");
+//! ```
+//!
 //! ### String Export
 //!
 //! ```no_run
@@ -208,6 +229,29 @@
 //!     .expect("Failed to build StaticExporter");
 //! ```
 //!
+//! For many exports from async code, `build_pool` returns an [`ExporterPool`]
+//! that renders plots concurrently in several browser sessions of a single
+//! WebDriver process:
+//!
+//! ```no_run
+//! use plotly_static::{StaticExporterBuilder, ImageFormat};
+//! use serde_json::json;
+//!
+//! # async fn run() {
+//! let mut pool = StaticExporterBuilder::default()
+//!     .build_pool(4)
+//!     .expect("Failed to build ExporterPool");
+//!
+//! let plot = json!({"data": [{"type": "scatter", "y": [1, 3, 2]}], "layout": {}});
+//! let (svg, png) = tokio::join!(
+//!     pool.write_to_string(&plot, ImageFormat::SVG, 800, 600, 1.0),
+//!     pool.write_to_string(&plot, ImageFormat::PNG, 800, 600, 1.0),
+//! );
+//!
+//! pool.close().await;
+//! # }
+//! ```
+//!
 //! ## WebDriver Management
 //!
 //! The library automatically manages WebDriver processes:
@@ -239,16 +283,23 @@
 //!
 //! ## Error Handling
 //!
-//! The library uses `anyhow::Result` for error handling. Common errors include:
+//! All exports return an [`ExportError`] on failure, whose variant tells the
+//! stage of the export that failed:
 //!
-//! - WebDriver not available or not running
-//! - Invalid Plotly JSON format
-//! - File system errors
-//! - Browser rendering errors
+//! - `DriverLaunch`: WebDriver or browser not available or not running
+//! - `SessionCreation`: the browser session could not be opened
+//! - `PageLoad`: the page with the Plotly libraries did not load
+//! - `Render`: plotly.js could not draw the plot, e.g. invalid Plotly JSON
+//! - `Timeout`: the browser did not finish in time
+//! - `Decode` and `Encode`: invalid image data returned by the browser, or
+//!   images that cannot be assembled into an animation or report
+//! - `Io`: file system errors
+//! - `InvalidInput`: exports that cannot be done as requested
 //!
 //! ## Browser Support
 //!
-//! - **Chrome/Chromium**: Full support via chromedriver
+//! - **Chrome/Chromium**: Full support via chromedriver, or directly over the
+//!   Chrome DevTools Protocol with the `cdp` feature
 //! - **Firefox**: Full support via geckodriver
 //! - **Safari**: Not currently supported
 //! - **Edge**: Not currently supported
@@ -256,7 +307,8 @@
 //! ## Performance Considerations
 //!
 //! - **Reuse Exporters**: Reuse `StaticExporter` instances for multiple exports
-//! - **Parallel Usage**: Use unique ports for parallel operations
+//! - **Parallel Usage**: Use unique ports for parallel operations, or an
+//!   [`ExporterPool`] for concurrent exports in async code
 //! - **WebDriver Reuse**: The library automatically reuses WebDriver sessions
 //!   when possible
 //!
@@ -285,29 +337,52 @@
 use std::fs::File;
 use std::io::prelude::*;
 use std::path::{Path, PathBuf};
-use std::vec;
+use std::sync::Arc;
+use std::time::Duration;
 #[cfg(any(test, feature = "debug"))]
 use std::{println as error, println as warn, println as debug};
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, Result};
 use base64::{engine::general_purpose, Engine as _};
-use fantoccini::{wd::Capabilities, Client, ClientBuilder};
 #[cfg(not(any(test, feature = "debug")))]
 use log::{debug, error, warn};
 use serde::Serialize;
 #[cfg(any(feature = "chromedriver", feature = "geckodriver"))]
-use serde_json::map::Map as JsonMap;
-use urlencoding::encode;
 use webdriver::WebDriver;
 
-use crate::template::{image_export_js_script, pdf_export_js_script};
-
+pub use crate::animation::AnimationFormat;
+pub use crate::batch::{ExportJob, JobResult};
+pub use crate::browser::Backend;
+use crate::browser::{Browser, Client};
+pub use crate::dpi::PrintSize;
+pub use crate::error::ExportError;
+pub use crate::pool::ExporterPool;
+pub use crate::report::{Orientation, PageSize, PdfReport};
+pub use crate::retry::RetryPolicy;
+use crate::session::SessionConfig;
+
+mod animation;
+mod batch;
+mod browser;
+#[cfg(feature = "cdp")]
+mod cdp;
+mod dpi;
+mod error;
+mod pdf;
+mod pool;
+mod report;
+mod retry;
+mod session;
 mod template;
 mod webdriver;
 
 #[cfg(not(any(feature = "chromedriver", feature = "geckodriver")))]
 const DRIVER_FEATURE_REQUIRED: &str = "Static image export at runtime requires enabling either the 'chromedriver' or 'geckodriver' feature.";
 
+#[cfg(not(feature = "cdp"))]
+const CDP_FEATURE_REQUIRED: &str =
+    "Static image export with Backend::Cdp requires enabling the 'cdp' feature.";
+
 /// Supported image formats for static image export.
 ///
 /// This enum defines all the image formats that can be exported from Plotly
@@ -390,6 +465,36 @@ impl std::fmt::Display for ImageFormat {
     }
 }
 
+impl std::str::FromStr for ImageFormat {
+    type Err = ExportError;
+
+    /// Parses the name or file extension of an image format, ignoring case.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use plotly_static::ImageFormat;
+    ///
+    /// assert!(matches!("png".parse(), Ok(ImageFormat::PNG)));
+    /// assert!(matches!("JPG".parse(), Ok(ImageFormat::JPEG)));
+    /// assert!("gif".parse::<ImageFormat>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "png" => Ok(Self::PNG),
+            "jpeg" | "jpg" => Ok(Self::JPEG),
+            "webp" => Ok(Self::WEBP),
+            "svg" => Ok(Self::SVG),
+            "pdf" => Ok(Self::PDF),
+            #[allow(deprecated)]
+            "eps" => Ok(Self::EPS),
+            _ => Err(ExportError::InvalidInput(format!(
+                "Unknown image format '{s}', expected png, jpeg, webp, svg or pdf"
+            ))),
+        }
+    }
+}
+
 /// TODO: ideally data would be a Plot object which is later serialized to JSON
 /// but with the current workspace set up, that would be a cyclic dependency.
 #[derive(Serialize)]
@@ -431,6 +536,8 @@ struct PlotData<'a> {
 /// - Spawn webdriver: true (automatically manages WebDriver lifecycle)
 /// - Offline mode: false
 /// - PDF export timeout: 250ms
+/// - Page load timeout: 300s, render and script timeouts: 30s
+/// - Retry policy: a single attempt
 /// - Browser capabilities: Default Chrome/Firefox headless options
 /// - Automatic WebDriver detection and connection reuse
 pub struct StaticExporterBuilder {
@@ -446,6 +553,17 @@ pub struct StaticExporterBuilder {
     pdf_export_timeout: u32,
     /// Browser command-line flags (e.g., "--headless", "--no-sandbox")
     webdriver_browser_caps: Vec<String>,
+    /// How the browser is driven (default: WebDriver, or Cdp if only the `cdp`
+    /// feature is enabled)
+    backend: Backend,
+    /// Time for the export page to load (default: 300s)
+    page_load_timeout: Duration,
+    /// Time for plotly.js to render and export one image (default: 30s)
+    render_timeout: Duration,
+    /// Time for other scripts run in the browser session (default: 30s)
+    script_timeout: Duration,
+    /// Retries of failed exports (default: a single attempt)
+    retry_policy: RetryPolicy,
 }
 
 impl Default for StaticExporterBuilder {
@@ -457,7 +575,10 @@ impl Default for StaticExporterBuilder {
     /// - Spawn webdriver: true
     /// - Offline mode: false
     /// - PDF export timeout: 250ms
+    /// - Page load timeout: 300s, render and script timeouts: 30s
+    /// - Retry policy: a single attempt
     /// - Default browser capabilities for headless operation
+    /// - Backend: WebDriver, or Cdp if `cdp` is the only backend feature
     fn default() -> Self {
         Self {
             webdriver_port: webdriver::WEBDRIVER_PORT,
@@ -466,7 +587,10 @@ impl Default for StaticExporterBuilder {
             offline_mode: false,
             pdf_export_timeout: 150,
             webdriver_browser_caps: {
-                #[cfg(feature = "chromedriver")]
+                #[cfg(any(
+                    feature = "chromedriver",
+                    all(feature = "cdp", not(feature = "geckodriver"))
+                ))]
                 {
                     crate::webdriver::chrome_default_caps()
                         .into_iter()
@@ -480,11 +604,20 @@ impl Default for StaticExporterBuilder {
                         .map(|s| s.to_string())
                         .collect()
                 }
-                #[cfg(not(any(feature = "chromedriver", feature = "geckodriver")))]
+                #[cfg(not(any(
+                    feature = "chromedriver",
+                    feature = "geckodriver",
+                    feature = "cdp"
+                )))]
                 {
                     Vec::new()
                 }
             },
+            backend: Backend::default(),
+            page_load_timeout: Duration::from_secs(300),
+            render_timeout: Duration::from_secs(30),
+            script_timeout: Duration::from_secs(30),
+            retry_policy: RetryPolicy::default(),
         }
     }
 }
@@ -590,6 +723,80 @@ impl StaticExporterBuilder {
         self
     }
 
+    /// Sets how long to wait for the export page with the Plotly libraries to
+    /// load, including downloading them from the CDN when not in offline mode.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use plotly_static::StaticExporterBuilder;
+    /// use std::time::Duration;
+    ///
+    /// // Slow network on a CI runner
+    /// let builder = StaticExporterBuilder::default()
+    ///     .page_load_timeout(Duration::from_secs(600));
+    /// ```
+    pub fn page_load_timeout(mut self, timeout: Duration) -> Self {
+        self.page_load_timeout = timeout;
+        self
+    }
+
+    /// Sets how long plotly.js may take to render and export one image, or
+    /// one frame of an animation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use plotly_static::StaticExporterBuilder;
+    /// use std::time::Duration;
+    ///
+    /// // Plots with millions of points
+    /// let builder = StaticExporterBuilder::default()
+    ///     .render_timeout(Duration::from_secs(120));
+    /// ```
+    pub fn render_timeout(mut self, timeout: Duration) -> Self {
+        self.render_timeout = timeout;
+        self
+    }
+
+    /// Sets how long any other script run in the browser session may take,
+    /// e.g. the checks that a session still responds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use plotly_static::StaticExporterBuilder;
+    /// use std::time::Duration;
+    ///
+    /// let builder = StaticExporterBuilder::default()
+    ///     .script_timeout(Duration::from_secs(10));
+    /// ```
+    pub fn script_timeout(mut self, timeout: Duration) -> Self {
+        self.script_timeout = timeout;
+        self
+    }
+
+    /// Sets how exports that failed for reasons unrelated to the plot, like
+    /// timeouts or a crashed browser session, are retried. See
+    /// [`RetryPolicy`].
+    ///
+    /// Retries apply to `write_fig`, `write_to_string` and `write_animation`,
+    /// and to the exports of an [`ExporterPool`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use plotly_static::{RetryPolicy, StaticExporterBuilder};
+    /// use std::time::Duration;
+    ///
+    /// let builder = StaticExporterBuilder::default()
+    ///     .retry_policy(RetryPolicy::new(3).backoff(Duration::from_millis(500)));
+    /// ```
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
     /// Sets custom browser capabilities for the WebDriver.
     ///
     /// # Examples
@@ -611,6 +818,29 @@ impl StaticExporterBuilder {
         self
     }
 
+    /// Sets how the headless browser is driven.
+    ///
+    /// With [`Backend::Cdp`] Chrome/Chromium is launched directly and
+    /// controlled over the DevTools Protocol, so no chromedriver has to match
+    /// the installed browser version. The WebDriver port, URL and spawn
+    /// settings are not used then, while the browser capabilities are passed
+    /// to Chrome as command-line flags. If the `geckodriver` feature is also
+    /// enabled, the default Firefox capabilities are replaced by the default
+    /// Chrome flags.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use plotly_static::{Backend, StaticExporterBuilder};
+    ///
+    /// let builder = StaticExporterBuilder::default()
+    ///     .backend(Backend::Cdp);
+    /// ```
+    pub fn backend(mut self, backend: Backend) -> Self {
+        self.backend = backend;
+        self
+    }
+
     /// Builds a synchronous `StaticExporter` instance with the current
     /// configuration.
     ///
@@ -639,7 +869,7 @@ impl StaticExporterBuilder {
     ///     .build()
     ///     .expect("Failed to build StaticExporter");
     /// ```
-    pub fn build(&self) -> Result<StaticExporter> {
+    pub fn build(&self) -> Result<StaticExporter, ExportError> {
         let runtime = std::sync::Arc::new(
             tokio::runtime::Builder::new_multi_thread()
                 .enable_all()
@@ -652,6 +882,58 @@ impl StaticExporterBuilder {
         Ok(StaticExporter { runtime, inner })
     }
 
+    /// Start the browser of the configured backend.
+    fn create_browser(&self) -> Result<Browser, ExportError> {
+        self.launch_browser()
+            .map_err(|e| ExportError::classify(e, ExportError::DriverLaunch))
+    }
+
+    fn launch_browser(&self) -> Result<Browser> {
+        match self.backend {
+            Backend::WebDriver => {
+                #[cfg(not(any(feature = "chromedriver", feature = "geckodriver")))]
+                return Err(anyhow!(DRIVER_FEATURE_REQUIRED));
+
+                #[cfg(any(feature = "chromedriver", feature = "geckodriver"))]
+                Ok(Browser::WebDriver(self.create_webdriver()?))
+            }
+            Backend::Cdp => {
+                #[cfg(not(feature = "cdp"))]
+                return Err(anyhow!(CDP_FEATURE_REQUIRED));
+
+                #[cfg(feature = "cdp")]
+                {
+                    let args = self.cdp_browser_args();
+                    let work = move || cdp::Chrome::launch(&args);
+                    let chrome = if tokio::runtime::Handle::try_current().is_ok() {
+                        std::thread::spawn(work)
+                            .join()
+                            .map_err(|_| anyhow!("failed to join browser launch thread"))??
+                    } else {
+                        work()?
+                    };
+                    Ok(Browser::Cdp(chrome))
+                }
+            }
+        }
+    }
+
+    /// The flags Chrome is launched with by the CDP backend. With
+    /// `geckodriver` enabled the default capabilities are Firefox flags, so
+    /// Chrome gets its own default flags unless the capabilities were
+    /// changed.
+    #[cfg(feature = "cdp")]
+    fn cdp_browser_args(&self) -> Vec<String> {
+        #[cfg(feature = "geckodriver")]
+        if self.webdriver_browser_caps == webdriver::firefox_default_caps() {
+            return webdriver::chrome_default_caps()
+                .into_iter()
+                .map(|s| s.to_string())
+                .collect();
+        }
+        self.webdriver_browser_caps.clone()
+    }
+
     /// Create a new WebDriver instance based on the spawn_webdriver flag
     #[cfg(any(feature = "chromedriver", feature = "geckodriver"))]
     fn create_webdriver(&self) -> Result<WebDriver> {
@@ -693,22 +975,64 @@ impl StaticExporterBuilder {
     ///     .build_async()
     ///     .expect("Failed to build AsyncStaticExporter");
     /// ```
-    pub fn build_async(&self) -> Result<AsyncStaticExporter> {
-        #[cfg(not(any(feature = "chromedriver", feature = "geckodriver")))]
-        return Err(anyhow!(DRIVER_FEATURE_REQUIRED));
-
-        #[cfg(any(feature = "chromedriver", feature = "geckodriver"))]
-        {
-            let wd = self.create_webdriver()?;
-            Ok(AsyncStaticExporter {
-                webdriver_port: self.webdriver_port,
-                webdriver_url: self.webdriver_url.clone(),
-                webdriver: wd,
-                offline_mode: self.offline_mode,
-                pdf_export_timeout: self.pdf_export_timeout,
-                webdriver_browser_caps: self.webdriver_browser_caps.clone(),
-                webdriver_client: None,
-            })
+    pub fn build_async(&self) -> Result<AsyncStaticExporter, ExportError> {
+        let browser = self.create_browser()?;
+        Ok(AsyncStaticExporter {
+            browser,
+            session: Arc::new(self.session_config()),
+            webdriver_client: None,
+        })
+    }
+
+    /// Build a pool of `size` browser sessions for exporting plots
+    /// concurrently from async contexts.
+    ///
+    /// All sessions share a single WebDriver or browser process. They are
+    /// opened lazily, so a pool only holds as many sessions as the highest
+    /// number of concurrent exports so far. See [`ExporterPool`] for details.
+    ///
+    /// geckodriver supports only one session per process, so pools with more
+    /// than one session require the `chromedriver` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use plotly_static::StaticExporterBuilder;
+    ///
+    /// let pool = StaticExporterBuilder::default()
+    ///     .build_pool(4)
+    ///     .expect("Failed to build ExporterPool");
+    /// ```
+    pub fn build_pool(&self, size: usize) -> Result<ExporterPool, ExportError> {
+        if size == 0 {
+            return Err(ExportError::InvalidInput(
+                "An ExporterPool needs at least one session".to_string(),
+            ));
+        }
+        #[cfg(feature = "geckodriver")]
+        if size > 1 && self.backend == Backend::WebDriver {
+            return Err(ExportError::InvalidInput(
+                "geckodriver supports a single session per process, use a pool of size 1 or the 'chromedriver' feature".to_string(),
+            ));
+        }
+
+        let browser = self.create_browser()?;
+        Ok(ExporterPool::new(browser, self.session_config(), size))
+    }
+
+    /// The settings for the browser sessions of the built exporters.
+    fn session_config(&self) -> SessionConfig {
+        SessionConfig {
+            webdriver_port: self.webdriver_port,
+            webdriver_url: self.webdriver_url.clone(),
+            offline_mode: self.offline_mode,
+            pdf_export_timeout: self.pdf_export_timeout,
+            webdriver_browser_caps: self.webdriver_browser_caps.clone(),
+            page_load_timeout: self.page_load_timeout,
+            render_timeout: self.render_timeout,
+            script_timeout: self.script_timeout,
+            retry: self.retry_policy,
+            offline_page: Arc::default(),
         }
     }
 }
@@ -790,7 +1114,7 @@ impl StaticExporter {
     /// # Examples
     ///
     /// ```no_run
-    /// 
+    ///
     /// // This example requires a running WebDriver (chromedriver/geckodriver) and a browser.
     /// // It cannot be run as a doc test.
     ///
@@ -826,13 +1150,13 @@ impl StaticExporter {
         width: usize,
         height: usize,
         scale: f64,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<(), ExportError> {
         if tokio::runtime::Handle::try_current().is_ok() {
-            return Err(anyhow!(
+            return Err(ExportError::InvalidInput(
                 "StaticExporter sync methods cannot be used inside an async context. \
              Use StaticExporterBuilder::build_async() and the associated AsyncStaticExporter::write_fig(...)."
-            )
-            .into());
+                .to_string(),
+            ));
         }
         let rt = self.runtime.clone();
         rt.block_on(
@@ -853,7 +1177,7 @@ impl StaticExporter {
     /// # Examples
     ///
     /// ```no_run
-    /// 
+    ///
     /// // This example requires a running WebDriver (chromedriver/geckodriver) and a browser.
     /// // It cannot be run as a doc test.
     /// use plotly_static::{StaticExporterBuilder, ImageFormat};
@@ -887,13 +1211,13 @@ impl StaticExporter {
         width: usize,
         height: usize,
         scale: f64,
-    ) -> Result<String, Box<dyn std::error::Error>> {
+    ) -> Result<String, ExportError> {
         if tokio::runtime::Handle::try_current().is_ok() {
-            return Err(anyhow!(
+            return Err(ExportError::InvalidInput(
                 "StaticExporter sync methods cannot be used inside an async context. \
              Use StaticExporterBuilder::build_async() and the associated AsyncStaticExporter::write_to_string(...)."
-            )
-            .into());
+                .to_string(),
+            ));
         }
         let rt = self.runtime.clone();
         rt.block_on(
@@ -902,6 +1226,174 @@ impl StaticExporter {
         )
     }
 
+    /// Exports several plots one after the other in the same browser session.
+    ///
+    /// The export page is loaded once and every plot of `jobs` is rendered
+    /// into it, which is faster than calling `write_fig` for each of them.
+    /// A failing job does not abort the batch: the returned [`JobResult`]s
+    /// hold the outcome and duration of each job, in the order of `jobs`.
+    /// Each job is retried according to [`StaticExporterBuilder::retry_policy`].
+    ///
+    /// Returns an error only if no browser session could be opened.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // This example requires a running WebDriver (chromedriver/geckodriver) and a browser.
+    /// // It cannot be run as a doc test.
+    /// use plotly_static::{ExportJob, ImageFormat, StaticExporterBuilder};
+    /// use serde_json::json;
+    ///
+    /// let plot = json!({
+    ///     "data": [{"type": "scatter", "x": [1,2,3], "y": [4,5,6]}],
+    ///     "layout": {}
+    /// });
+    ///
+    /// let mut exporter = StaticExporterBuilder::default().build().unwrap();
+    ///
+    /// let jobs = vec![
+    ///     ExportJob::new("report/figure", plot.clone(), ImageFormat::PNG, 1200, 800, 2.0),
+    ///     ExportJob::new("report/figure", plot, ImageFormat::SVG, 1200, 800, 1.0),
+    /// ];
+    /// for job in exporter.write_batch(&jobs).expect("Failed to open a session") {
+    ///     match job.result {
+    ///         Ok(()) => println!("{} in {:?}", job.dst.display(), job.duration),
+    ///         Err(e) => eprintln!("{}: {e}", job.dst.display()),
+    ///     }
+    /// }
+    ///
+    /// // Close the exporter
+    /// exporter.close();
+    /// ```
+    pub fn write_batch(&mut self, jobs: &[ExportJob]) -> Result<Vec<JobResult>, ExportError> {
+        if tokio::runtime::Handle::try_current().is_ok() {
+            return Err(ExportError::InvalidInput(
+                "StaticExporter sync methods cannot be used inside an async context. \
+             Use StaticExporterBuilder::build_async() and the associated AsyncStaticExporter::write_batch(...)."
+                .to_string(),
+            ));
+        }
+        let rt = self.runtime.clone();
+        rt.block_on(self.inner.write_batch(jobs))
+    }
+
+    /// Exports every frame of an animated Plotly plot.
+    ///
+    /// The plot is drawn in a headless browser and each entry of its
+    /// `frames` is applied in turn with `Plotly.animate`, without
+    /// transition. The captured states are written to `dst` as an animated
+    /// GIF or APNG, or as one image file per frame, see [`AnimationFormat`].
+    /// The remaining arguments have the same meaning as for `write_fig`.
+    ///
+    /// Returns an error if the plot has no frames.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // This example requires a running WebDriver (chromedriver/geckodriver) and a browser.
+    /// // It cannot be run as a doc test.
+    /// use plotly_static::{AnimationFormat, StaticExporterBuilder};
+    /// use serde_json::json;
+    /// use std::path::Path;
+    /// use std::time::Duration;
+    ///
+    /// let plot = json!({
+    ///     "data": [{"type": "bar", "x": ["a", "b"], "y": [1, 2]}],
+    ///     "layout": {"yaxis": {"range": [0, 5]}},
+    ///     "frames": [
+    ///         {"name": "2024", "data": [{"y": [2, 3]}]},
+    ///         {"name": "2025", "data": [{"y": [4, 1]}]}
+    ///     ]
+    /// });
+    ///
+    /// let mut exporter = StaticExporterBuilder::default().build().unwrap();
+    ///
+    /// // Creates "sales.gif" showing each frame for one second
+    /// exporter.write_animation(
+    ///     Path::new("sales"),
+    ///     &plot,
+    ///     AnimationFormat::GIF { frame_duration: Duration::from_secs(1) },
+    ///     800,
+    ///     600,
+    ///     1.0
+    /// ).expect("Failed to export animation");
+    ///
+    /// // Close the exporter
+    /// exporter.close();
+    /// ```
+    pub fn write_animation(
+        &mut self,
+        dst: &Path,
+        plot: &serde_json::Value,
+        format: AnimationFormat,
+        width: usize,
+        height: usize,
+        scale: f64,
+    ) -> Result<(), ExportError> {
+        if tokio::runtime::Handle::try_current().is_ok() {
+            return Err(ExportError::InvalidInput(
+                "StaticExporter sync methods cannot be used inside an async context. \
+             Use StaticExporterBuilder::build_async() and the associated AsyncStaticExporter::write_animation(...)."
+                .to_string(),
+            ));
+        }
+        let rt = self.runtime.clone();
+        rt.block_on(
+            self.inner
+                .write_animation(dst, plot, format, width, height, scale),
+        )
+    }
+
+    /// Exports a multi-page PDF report of several plots.
+    ///
+    /// All plots of `report` are rendered in the same browser session and
+    /// assembled with its title and text pages into one PDF file, see
+    /// [`PdfReport`]. Plots are embedded as PNG images at the resolution set
+    /// by [`PdfReport::scale`], not as vector graphics. The `.pdf` extension
+    /// is added to `dst`. Rendering is retried as a whole according to
+    /// [`StaticExporterBuilder::retry_policy`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// // This example requires a running WebDriver (chromedriver/geckodriver) and a browser.
+    /// // It cannot be run as a doc test.
+    /// use plotly_static::{PageSize, PdfReport, StaticExporterBuilder};
+    /// use serde_json::json;
+    /// use std::path::Path;
+    ///
+    /// let sales = json!({"data": [{"type": "bar", "y": [3, 1, 2]}], "layout": {}});
+    /// let returns = json!({"data": [{"type": "scatter", "y": [1, 2, 1]}], "layout": {}});
+    ///
+    /// let report = PdfReport::new()
+    ///     .page_size(PageSize::Letter)
+    ///     .title_page("Weekly report", "Week 42")
+    ///     .plot(sales)
+    ///     .text_page("Returns", "Returns are back to normal.")
+    ///     .plot(returns);
+    ///
+    /// let mut exporter = StaticExporterBuilder::default().build().unwrap();
+    ///
+    /// // Creates "weekly_report.pdf" with four pages
+    /// exporter
+    ///     .write_pdf_report(Path::new("weekly_report"), &report)
+    ///     .expect("Failed to export report");
+    ///
+    /// // Close the exporter
+    /// exporter.close();
+    /// ```
+    pub fn write_pdf_report(&mut self, dst: &Path, report: &PdfReport) -> Result<(), ExportError> {
+        if tokio::runtime::Handle::try_current().is_ok() {
+            return Err(ExportError::InvalidInput(
+                "StaticExporter sync methods cannot be used inside an async context. \
+             Use StaticExporterBuilder::build_async() and the associated AsyncStaticExporter::write_pdf_report(...)."
+                .to_string(),
+            ));
+        }
+        let rt = self.runtime.clone();
+        rt.block_on(self.inner.write_pdf_report(dst, report))
+    }
+
     /// Get diagnostic information about the underlying WebDriver process.
     ///
     /// This method provides detailed information about the WebDriver process
@@ -923,29 +1415,13 @@ impl StaticExporter {
 /// Async StaticExporter for async contexts. Keeps the same API as the sync
 /// StaticExporter for compatibility.
 pub struct AsyncStaticExporter {
-    /// WebDriver server port (default: 4444)
-    webdriver_port: u32,
-
-    /// WebDriver server base URL (default: "http://localhost")
-    webdriver_url: String,
-
-    /// WebDriver process manager for spawning and cleanup
-    webdriver: WebDriver,
-
-    /// Use bundled JS libraries instead of CDN
-    offline_mode: bool,
+    /// WebDriver or browser process manager for spawning and cleanup
+    browser: Browser,
 
-    /// PDF export timeout in milliseconds
-    pdf_export_timeout: u32,
-
-    /// Browser command-line flags (e.g., "--headless", "--no-sandbox")
-    #[cfg_attr(
-        not(any(feature = "chromedriver", feature = "geckodriver")),
-        allow(dead_code)
-    )]
-    webdriver_browser_caps: Vec<String>,
+    /// Settings for the browser session
+    session: Arc<SessionConfig>,
 
-    /// Cached WebDriver client for session reuse
+    /// Cached browser session for session reuse
     webdriver_client: Option<Client>,
 }
 
@@ -961,10 +1437,7 @@ impl AsyncStaticExporter {
         width: usize,
         height: usize,
         scale: f64,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        let mut dst = PathBuf::from(dst);
-        dst.set_extension(format.to_string());
-
+    ) -> Result<(), ExportError> {
         let plot_data = PlotData {
             format: format.clone(),
             width,
@@ -974,15 +1447,7 @@ impl AsyncStaticExporter {
         };
 
         let image_data = self.static_export(&plot_data).await?;
-        let data = match format {
-            ImageFormat::SVG => image_data.as_bytes().to_vec(),
-            _ => general_purpose::STANDARD.decode(image_data)?,
-        };
-        let mut file = File::create(dst.as_path())?;
-        file.write_all(&data)?;
-        file.flush()?;
-
-        Ok(())
+        write_image(dst, &format, scale, &image_data)
     }
 
     /// Exports a Plotly plot to a string representation.
@@ -995,7 +1460,7 @@ impl AsyncStaticExporter {
         width: usize,
         height: usize,
         scale: f64,
-    ) -> Result<String, Box<dyn std::error::Error>> {
+    ) -> Result<String, ExportError> {
         let plot_data = PlotData {
             format,
             width,
@@ -1007,6 +1472,84 @@ impl AsyncStaticExporter {
         Ok(image_data)
     }
 
+    /// Exports several plots one after the other in the same browser session.
+    ///
+    /// Same as [`StaticExporter::write_batch`] but async.
+    pub async fn write_batch(&mut self, jobs: &[ExportJob]) -> Result<Vec<JobResult>, ExportError> {
+        batch::run(self, jobs).await
+    }
+
+    /// Exports every frame of an animated Plotly plot.
+    ///
+    /// Same as [`StaticExporter::write_animation`] but async.
+    pub async fn write_animation(
+        &mut self,
+        dst: &Path,
+        plot: &serde_json::Value,
+        format: AnimationFormat,
+        width: usize,
+        height: usize,
+        scale: f64,
+    ) -> Result<(), ExportError> {
+        animation::check_format(&format)?;
+        let plot_data = &PlotData {
+            format: format.frame_format(),
+            width,
+            height,
+            scale,
+            data: plot,
+        };
+
+        let frames = self
+            .with_retry(
+                |session, client| async move { session.render_frames(&client, plot_data).await },
+            )
+            .await?;
+        animation::write_animation(dst, &format, scale, &frames)
+    }
+
+    /// Exports a multi-page PDF report of several plots.
+    ///
+    /// Same as [`StaticExporter::write_pdf_report`] but async.
+    pub async fn write_pdf_report(
+        &mut self,
+        dst: &Path,
+        report: &PdfReport,
+    ) -> Result<(), ExportError> {
+        report.check()?;
+        let (width, height) = report.plot_size();
+        let scale = report.scale_factor();
+        let images = if report.plots().next().is_some() {
+            self.with_retry(|session, client| async move {
+                session.load_page(&client).await?;
+                let mut images = Vec::new();
+                for plot in report.plots() {
+                    let plot_data = PlotData {
+                        format: ImageFormat::PNG,
+                        width,
+                        height,
+                        scale,
+                        data: plot,
+                    };
+                    images.push(session.render_loaded(&client, &plot_data).await?);
+                }
+                Ok(images)
+            })
+            .await?
+        } else {
+            Vec::new()
+        };
+
+        let data = report
+            .to_pdf(&images)
+            .map_err(|e| ExportError::classify(e, ExportError::Encode))?;
+        let mut file = File::create(dst.with_extension("pdf"))?;
+        file.write_all(&data)?;
+        file.flush()?;
+
+        Ok(())
+    }
+
     /// Close the WebDriver session and stop the driver if it was spawned.
     ///
     /// Always call close to ensure proper cleanup.
@@ -1016,239 +1559,115 @@ impl AsyncStaticExporter {
                 error!("Failed to close WebDriver client: {e}");
             }
         }
-        if let Err(e) = self.webdriver.stop() {
+        if let Err(e) = self.browser.stop() {
             error!("Failed to stop WebDriver: {e}");
         }
     }
 
     /// Get diagnostic information about the underlying WebDriver process.
     pub fn get_webdriver_diagnostics(&self) -> String {
-        self.webdriver.get_diagnostics()
+        self.browser.get_diagnostics()
     }
 
     /// Export the Plotly plot image to a string representation calling the
     /// Plotly.toImage function.
-    async fn static_export(&mut self, plot: &PlotData<'_>) -> Result<String> {
-        let html_content = template::get_html_body(self.offline_mode);
-        self.extract(&html_content, plot)
+    async fn static_export(&mut self, plot: &PlotData<'_>) -> Result<String, ExportError> {
+        self.with_retry(|session, client| async move { session.render(&client, plot).await })
             .await
-            .with_context(|| "Failed to extract static image from browser session")
     }
 
-    /// Extract a static image from a browser session.
-    async fn extract(&mut self, html_content: &str, plot: &PlotData<'_>) -> Result<String> {
-        let caps = self.build_webdriver_caps()?;
-        debug!(
-            "Use WebDriver and headless browser to export static plot (offline_mode={}, port={})",
-            self.offline_mode, self.webdriver_port
-        );
-        let webdriver_url = format!("{}:{}", self.webdriver_url, self.webdriver_port);
-        debug!("Connecting to WebDriver at {webdriver_url}");
-
-        // Reuse existing client or create new one
-        let client = if let Some(ref client) = self.webdriver_client {
-            debug!("Reusing existing WebDriver session");
-            client.clone()
-        } else {
-            debug!("Creating new WebDriver session");
-            let new_client = ClientBuilder::native()
-                .capabilities(caps)
-                .connect(&webdriver_url)
-                .await
-                .with_context(|| "WebDriver session error")?;
-            self.webdriver_client = Some(new_client.clone());
-            new_client
-        };
-
-        // For offline mode, write HTML to file to avoid data URI size limits since JS
-        // libraries are embedded in the file
-        let url = if self.offline_mode {
-            let temp_file = template::to_file(html_content)
-                .with_context(|| "Failed to create temporary HTML file")?;
-            format!("file://{}", temp_file.to_string_lossy())
-        } else {
-            // For online mode, use data URI (smaller size since JS is loaded from CDN)
-            format!("data:text/html,{}", encode(html_content))
-        };
-
-        // Open the HTML
-        client.goto(&url).await?;
-
-        #[cfg(target_os = "windows")]
-        Self::wait_for_document_ready(&client, std::time::Duration::from_secs(20)).await?;
-
-        // Wait for Plotly container element
-        #[cfg(target_os = "windows")]
-        Self::wait_for_plotly_container(&client, std::time::Duration::from_secs(10)).await?;
-
-        // In online mode, ensure Plotly is loaded
-        if !self.offline_mode {
-            #[cfg(target_os = "windows")]
-            Self::wait_for_plotly_loaded(&client, std::time::Duration::from_secs(15)).await?;
-        }
-
-        let (js_script, args) = match plot.format {
-            ImageFormat::PDF => {
-                // Always use SVG for PDF export
-                let args = vec![
-                    plot.data.clone(),
-                    ImageFormat::SVG.to_string().into(),
-                    plot.width.into(),
-                    plot.height.into(),
-                    plot.scale.into(),
-                ];
-
-                (pdf_export_js_script(self.pdf_export_timeout), args)
-            }
-            _ => {
-                let args = vec![
-                    plot.data.clone(),
-                    plot.format.to_string().into(),
-                    plot.width.into(),
-                    plot.height.into(),
-                    plot.scale.into(),
-                ];
-
-                (image_export_js_script(), args)
-            }
-        };
-
-        let data = client.execute_async(&js_script, args).await?;
-
-        let result = data.as_str().ok_or(anyhow!(
-            "Failed to execute Plotly.toImage in browser session"
-        ))?;
-
-        if let Some(err) = result.strip_prefix("ERROR:") {
-            return Err(anyhow!("JavaScript error during export: {err}"));
-        }
-
-        match plot.format {
-            ImageFormat::SVG => common::extract_plain(result, &plot.format),
-            ImageFormat::PNG | ImageFormat::JPEG | ImageFormat::WEBP | ImageFormat::PDF => {
-                common::extract_encoded(result, &plot.format)
-            }
-            #[allow(deprecated)]
-            ImageFormat::EPS => {
-                error!("EPS format is deprecated. Use SVG or PDF instead.");
-                common::extract_encoded(result, &plot.format)
-            }
-        }
-    }
-
-    fn build_webdriver_caps(&self) -> Result<Capabilities> {
-        #[cfg(not(any(feature = "chromedriver", feature = "geckodriver")))]
-        {
-            Err(anyhow!(DRIVER_FEATURE_REQUIRED))
-        }
-        #[cfg(any(feature = "chromedriver", feature = "geckodriver"))]
-        {
-            // Define browser capabilities (copied to avoid reordering existing code)
-            let mut caps = JsonMap::new();
-            let mut browser_opts = JsonMap::new();
-            let browser_args = self.webdriver_browser_caps.clone();
-
-            browser_opts.insert("args".to_string(), serde_json::json!(browser_args));
-
-            // Add Chrome binary capability if BROWSER_PATH is set
-            #[cfg(feature = "chromedriver")]
-            if let Ok(chrome_path) = std::env::var("BROWSER_PATH") {
-                browser_opts.insert("binary".to_string(), serde_json::json!(chrome_path));
-                debug!("Added Chrome binary capability: {chrome_path}");
-            }
-            // Add Firefox binary capability if BROWSER_PATH is set
-            #[cfg(feature = "geckodriver")]
-            if let Ok(firefox_path) = std::env::var("BROWSER_PATH") {
-                browser_opts.insert("binary".to_string(), serde_json::json!(firefox_path));
-                debug!("Added Firefox binary capability: {firefox_path}");
-            }
-
-            // Add Firefox-specific preferences for CI environments
-            #[cfg(feature = "geckodriver")]
+    /// Run `export` in the browser session, retrying it according to the
+    /// retry policy.
+    async fn with_retry<T, F, Fut>(&mut self, mut export: F) -> Result<T, ExportError>
+    where
+        F: FnMut(Arc<SessionConfig>, Client) -> Fut,
+        Fut: std::future::Future<Output = Result<T, ExportError>>,
+    {
+        let mut attempt = 1;
+        loop {
+            let error = match self.client().await {
+                Ok(client) => match export(self.session.clone(), client.clone()).await {
+                    Ok(value) => return Ok(value),
+                    Err(e) => e,
+                },
+                Err(e) => e,
+            };
+            let responding = match &self.webdriver_client {
+                Some(client) => self.session.is_alive(client).await,
+                None => false,
+            };
+            if let Some(client) = self
+                .webdriver_client
+                .take_if(|client| !client.reusable_after(&error))
             {
-                let prefs = common::get_firefox_ci_preferences();
-                browser_opts.insert("prefs".to_string(), serde_json::json!(prefs));
-                debug!("Added Firefox preferences for CI compatibility");
+                let _ = client.close().await;
+            }
+            let retry = self.session.retry;
+            if !retry.should_retry(attempt, &error, responding) {
+                return Err(error);
             }
 
-            caps.insert(
-                "browserName".to_string(),
-                serde_json::json!(get_browser_name()),
+            warn!(
+                "Export attempt {attempt} of {} failed: {error}",
+                retry.attempts()
             );
-            caps.insert(
-                get_options_key().to_string(),
-                serde_json::json!(browser_opts),
-            );
-
-            debug!("WebDriver capabilities: {caps:?}");
-
-            Ok(caps)
-        }
-    }
-
-    #[cfg(target_os = "windows")]
-    async fn wait_for_document_ready(client: &Client, timeout: std::time::Duration) -> Result<()> {
-        let start = std::time::Instant::now();
-        loop {
-            let state = client
-                .execute("return document.readyState;", vec![])
-                .await
-                .unwrap_or(serde_json::Value::Null);
-            if state.as_str().map(|s| s == "complete").unwrap_or(false) {
-                return Ok(());
-            }
-            if start.elapsed() > timeout {
-                return Err(anyhow!(
-                    "Timeout waiting for document.readyState === 'complete'"
-                ));
+            if retry.restarts_session() || !responding {
+                if let Some(client) = self.webdriver_client.take() {
+                    let _ = client.close().await;
+                }
             }
-            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            tokio::time::sleep(retry.delay(attempt)).await;
+            attempt += 1;
         }
     }
 
-    #[cfg(target_os = "windows")]
-    async fn wait_for_plotly_container(
-        client: &Client,
-        timeout: std::time::Duration,
-    ) -> Result<()> {
-        let start = std::time::Instant::now();
-        loop {
-            let has_el = client
-                .execute(
-                    "return !!document.getElementById('plotly-html-element');",
-                    vec![],
-                )
-                .await
-                .unwrap_or(serde_json::Value::Bool(false));
-            if has_el.as_bool().unwrap_or(false) {
-                return Ok(());
-            }
-            if start.elapsed() > timeout {
-                return Err(anyhow!(
-                    "Timeout waiting for #plotly-html-element to appear in DOM"
-                ));
-            }
-            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    /// Reuse the existing WebDriver session or create a new one.
+    async fn client(&mut self) -> Result<Client, ExportError> {
+        if let Some(ref client) = self.webdriver_client {
+            debug!("Reusing existing WebDriver session");
+            Ok(client.clone())
+        } else {
+            let new_client = self.session.connect(&self.browser).await?;
+            self.webdriver_client = Some(new_client.clone());
+            Ok(new_client)
         }
     }
+}
 
-    #[cfg(target_os = "windows")]
-    async fn wait_for_plotly_loaded(client: &Client, timeout: std::time::Duration) -> Result<()> {
-        let start = std::time::Instant::now();
-        loop {
-            let has_plotly = client
-                .execute("return !!window.Plotly;", vec![])
-                .await
-                .unwrap_or(serde_json::Value::Bool(false));
-            if has_plotly.as_bool().unwrap_or(false) {
-                return Ok(());
-            }
-            if start.elapsed() > timeout {
-                return Err(anyhow!("Timeout waiting for Plotly library to load"));
-            }
-            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
-        }
-    }
+/// Write the image data returned by an export to `dst`, with the extension
+/// of `format` and the pixel density of `scale`.
+fn write_image(
+    dst: &Path,
+    format: &ImageFormat,
+    scale: f64,
+    image_data: &str,
+) -> Result<(), ExportError> {
+    let mut dst = PathBuf::from(dst);
+    dst.set_extension(format.to_string());
+    write_image_file(&dst, format, scale, image_data)
+}
+
+/// Write the exported `image_data` to exactly `dst`, without changing its
+/// extension.
+fn write_image_file(
+    dst: &Path,
+    format: &ImageFormat,
+    scale: f64,
+    image_data: &str,
+) -> Result<(), ExportError> {
+    let data = match format {
+        ImageFormat::SVG => image_data.as_bytes().to_vec(),
+        _ => dpi::set_density(
+            format,
+            general_purpose::STANDARD.decode(image_data)?,
+            dpi::scale_to_dpi(scale),
+        ),
+    };
+    let mut file = File::create(dst)?;
+    file.write_all(&data)?;
+    file.flush()?;
+
+    Ok(())
 }
 
 mod common {
@@ -1360,12 +1779,51 @@ mod tests {
     #[test]
     #[cfg(not(any(feature = "chromedriver", feature = "geckodriver")))]
     fn build_without_driver_feature_returns_error() {
-        match StaticExporterBuilder::default().build_async() {
-            Err(e) => assert_eq!(e.to_string(), DRIVER_FEATURE_REQUIRED),
+        let builder = StaticExporterBuilder::default().backend(Backend::WebDriver);
+        match builder.build_async() {
+            Err(ExportError::DriverLaunch(msg)) => assert_eq!(msg, DRIVER_FEATURE_REQUIRED),
+            Err(e) => panic!("unexpected error: {e}"),
             Ok(_) => panic!("expected build to fail without a driver feature"),
         }
     }
 
+    #[test]
+    #[cfg(not(feature = "cdp"))]
+    fn build_cdp_without_feature_returns_error() {
+        let builder = StaticExporterBuilder::default().backend(Backend::Cdp);
+        match builder.build_async() {
+            Err(ExportError::DriverLaunch(msg)) => assert_eq!(msg, CDP_FEATURE_REQUIRED),
+            Err(e) => panic!("unexpected error: {e}"),
+            Ok(_) => panic!("expected build to fail without the cdp feature"),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "cdp")]
+    fn cdp_backend_launches_chrome_with_chrome_flags() {
+        let chrome_caps: Vec<String> = webdriver::chrome_default_caps()
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect();
+        let builder = StaticExporterBuilder::default().backend(Backend::Cdp);
+        assert_eq!(builder.cdp_browser_args(), chrome_caps);
+
+        let custom = vec!["--headless".to_string()];
+        let builder = builder.webdriver_browser_caps(custom.clone());
+        assert_eq!(builder.cdp_browser_args(), custom);
+    }
+
+    #[test]
+    fn build_pool_requires_a_session() {
+        match StaticExporterBuilder::default().build_pool(0) {
+            Err(ExportError::InvalidInput(msg)) => {
+                assert_eq!(msg, "An ExporterPool needs at least one session")
+            }
+            Err(e) => panic!("unexpected error: {e}"),
+            Ok(_) => panic!("expected build_pool(0) to fail"),
+        }
+    }
+
     // Helper to generate unique ports for parallel tests
     #[cfg(not(feature = "debug"))]
     fn get_unique_port() -> u32 {
@@ -1477,6 +1935,43 @@ mod tests {
         exporter.close();
     }
 
+    #[test]
+    fn save_png_with_scale() {
+        init();
+        let test_plot = create_test_plot();
+        let mut exporter = StaticExporterBuilder::default()
+            .spawn_webdriver(true)
+            .webdriver_port(get_unique_port())
+            .build()
+            .unwrap();
+
+        let size = PrintSize::from_mm(85.0, 60.0, 300.0);
+        let dst = PathBuf::from("static_example_300dpi.png");
+        exporter
+            .write_fig(
+                dst.as_path(),
+                &test_plot,
+                ImageFormat::PNG,
+                size.width,
+                size.height,
+                size.scale,
+            )
+            .unwrap();
+        let data = std::fs::read(&dst).expect("Could not read the exported image");
+        // The IHDR chunk starts with the pixel width and height
+        let width = u32::from_be_bytes(data[16..20].try_into().unwrap());
+        let height = u32::from_be_bytes(data[20..24].try_into().unwrap());
+        assert_eq!((width, height), (1003, 709));
+        let reader = png::Decoder::new(std::io::Cursor::new(&data))
+            .read_info()
+            .unwrap();
+        assert!(reader.info().pixel_dims.is_some());
+        #[cfg(not(feature = "debug"))]
+        assert!(std::fs::remove_file(dst.as_path()).is_ok());
+
+        exporter.close();
+    }
+
     #[test]
     fn save_jpeg() {
         init();
@@ -1641,6 +2136,173 @@ mod tests {
         exporter.close();
     }
 
+    #[test]
+    fn save_batch() {
+        init();
+        let test_plot = create_test_plot();
+        let mut exporter = StaticExporterBuilder::default()
+            .spawn_webdriver(true)
+            .webdriver_port(get_unique_port())
+            .build()
+            .unwrap();
+
+        let jobs = vec![
+            ExportJob::new(
+                "static_batch",
+                test_plot.clone(),
+                ImageFormat::PNG,
+                1200,
+                900,
+                1.0,
+            ),
+            // Fails to write the file, the remaining jobs must still run
+            ExportJob::new(
+                "missing_dir/static_batch",
+                test_plot.clone(),
+                ImageFormat::SVG,
+                1200,
+                900,
+                1.0,
+            ),
+            ExportJob::new("static_batch", test_plot, ImageFormat::SVG, 1200, 900, 1.0),
+        ];
+        let results = exporter.write_batch(&jobs).unwrap();
+        assert_eq!(results.len(), 3);
+        assert!(results[1].result.is_err());
+        for job in [&results[0], &results[2]] {
+            assert!(job.is_ok());
+            let metadata = std::fs::metadata(&job.dst).expect("Could not retrieve file metadata");
+            assert!(metadata.len() > 0);
+            #[cfg(not(feature = "debug"))]
+            assert!(std::fs::remove_file(&job.dst).is_ok());
+        }
+        assert_eq!(results[2].dst, PathBuf::from("static_batch.svg"));
+
+        exporter.close();
+    }
+
+    #[test]
+    fn save_gif_animation() {
+        init();
+        let test_plot = serde_json::json!({
+            "data": [{"type": "bar", "x": ["a", "b"], "y": [1, 2]}],
+            "layout": {"yaxis": {"range": [0, 5]}},
+            "frames": [
+                {"name": "first", "data": [{"y": [2, 3]}]},
+                {"data": [{"y": [4, 1]}]}
+            ]
+        });
+        let mut exporter = StaticExporterBuilder::default()
+            .spawn_webdriver(true)
+            .webdriver_port(get_unique_port())
+            .build()
+            .unwrap();
+
+        let dst = PathBuf::from("static_animation.gif");
+        let format = AnimationFormat::GIF {
+            frame_duration: std::time::Duration::from_millis(500),
+        };
+        exporter
+            .write_animation(dst.as_path(), &test_plot, format, 400, 300, 1.0)
+            .unwrap();
+        let data = std::fs::read(&dst).expect("Could not read the exported animation");
+        assert!(data.starts_with(b"GIF89a"));
+        #[cfg(not(feature = "debug"))]
+        assert!(std::fs::remove_file(dst.as_path()).is_ok());
+
+        let no_frames = exporter.write_animation(
+            dst.as_path(),
+            &create_test_plot(),
+            AnimationFormat::Sequence(ImageFormat::PNG),
+            400,
+            300,
+            1.0,
+        );
+        assert!(matches!(no_frames, Err(ExportError::InvalidInput(_))));
+
+        exporter.close();
+    }
+
+    #[test]
+    #[cfg(feature = "cdp")]
+    fn save_png_with_cdp() {
+        init();
+        let test_plot = create_test_plot();
+        let mut exporter = StaticExporterBuilder::default()
+            .backend(Backend::Cdp)
+            .build()
+            .unwrap();
+
+        let dst = PathBuf::from("static_example_cdp.png");
+        exporter
+            .write_fig(dst.as_path(), &test_plot, ImageFormat::PNG, 1024, 680, 1.0)
+            .unwrap();
+        let svg = exporter
+            .write_to_string(&test_plot, ImageFormat::SVG, 1024, 680, 1.0)
+            .unwrap();
+        assert!(svg.starts_with("<svg"));
+        let metadata = std::fs::metadata(&dst).expect("Could not retrieve file metadata");
+        assert!(metadata.len() > 0);
+        #[cfg(not(feature = "debug"))]
+        assert!(std::fs::remove_file(dst.as_path()).is_ok());
+
+        exporter.close();
+    }
+
+    #[test]
+    fn save_pdf_report() {
+        init();
+        let report = PdfReport::new()
+            .page_size(PageSize::Letter)
+            .orientation(Orientation::Landscape)
+            .title_page("Report", "Static export")
+            .plot(create_test_plot())
+            .text_page("Notes", "The second plot is the same as the first.")
+            .plot(create_test_plot());
+        let mut exporter = StaticExporterBuilder::default()
+            .spawn_webdriver(true)
+            .webdriver_port(get_unique_port())
+            .build()
+            .unwrap();
+
+        let dst = PathBuf::from("static_report.pdf");
+        exporter.write_pdf_report(dst.as_path(), &report).unwrap();
+        let data = std::fs::read(&dst).expect("Could not read the exported report");
+        assert!(data.starts_with(b"%PDF-"));
+        assert!(data.windows(9).any(|w| w == b"/Count 4 "));
+        #[cfg(not(feature = "debug"))]
+        assert!(std::fs::remove_file(dst.as_path()).is_ok());
+
+        exporter.close();
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "chromedriver")]
+    // geckodriver supports a single session per process
+    async fn export_concurrently_with_pool() {
+        init();
+        let test_plot = create_test_plot();
+
+        let mut pool = StaticExporterBuilder::default()
+            .spawn_webdriver(true)
+            .webdriver_port(get_unique_port())
+            .build_pool(2)
+            .unwrap();
+        assert_eq!(pool.size(), 2);
+
+        let (svg1, svg2, svg3) = tokio::join!(
+            pool.write_to_string(&test_plot, ImageFormat::SVG, 800, 600, 1.0),
+            pool.write_to_string(&test_plot, ImageFormat::SVG, 800, 600, 1.0),
+            pool.write_to_string(&test_plot, ImageFormat::SVG, 800, 600, 1.0),
+        );
+        for svg in [svg1, svg2, svg3] {
+            assert!(svg.unwrap().starts_with("<svg"));
+        }
+        assert!(pool.idle_sessions() <= 2);
+
+        pool.close().await;
+    }
+
     #[test]
     #[cfg(feature = "chromedriver")]
     // Skip this test for geckodriver as it doesn't support multiple concurrent
@@ -0,0 +1,196 @@
+//! A minimal PDF writer for the pages of a [`crate::PdfReport`].
+//!
+//! Text is set in the standard Helvetica font with `WinAnsiEncoding`, which
+//! every PDF reader provides, so no font has to be embedded. Characters
+//! outside of Windows-1252 are replaced by `?`.
+
+use std::io::Write;
+
+use anyhow::Result;
+use flate2::{write::ZlibEncoder, Compression};
+
+/// Advance widths of the Helvetica glyphs of the characters ' ' to '~', in
+/// thousandths of the font size.
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667,
+    611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500,
+    222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+/// Approximate width of the glyphs that are not in `HELVETICA_WIDTHS`.
+const DEFAULT_WIDTH: u16 = 556;
+
+/// The objects of a PDF file, numbered from 1 in the order they are added.
+pub(crate) struct PdfDocument {
+    objects: Vec<Vec<u8>>,
+}
+
+impl PdfDocument {
+    pub(crate) fn new() -> Self {
+        Self {
+            objects: Vec::new(),
+        }
+    }
+
+    /// Reserve the number of an object that is set later, e.g. because it
+    /// refers to objects that are not added yet.
+    pub(crate) fn reserve(&mut self) -> usize {
+        self.objects.push(Vec::new());
+        self.objects.len()
+    }
+
+    pub(crate) fn set(&mut self, id: usize, object: impl Into<Vec<u8>>) {
+        self.objects[id - 1] = object.into();
+    }
+
+    pub(crate) fn add(&mut self, object: impl Into<Vec<u8>>) -> usize {
+        self.objects.push(object.into());
+        self.objects.len()
+    }
+
+    /// Add a stream object with the entries of `dict` and the compressed
+    /// `data`.
+    pub(crate) fn add_stream(&mut self, dict: &str, data: &[u8]) -> Result<usize> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data)?;
+        let data = encoder.finish()?;
+
+        let mut object = format!(
+            "<< {dict}{}/Filter /FlateDecode /Length {} >>\nstream\n",
+            if dict.is_empty() { "" } else { " " },
+            data.len()
+        )
+        .into_bytes();
+        object.extend_from_slice(&data);
+        object.extend_from_slice(b"\nendstream");
+        Ok(self.add(object))
+    }
+
+    /// Serialize the document with the document catalog `catalog`.
+    pub(crate) fn finish(self, catalog: usize) -> Vec<u8> {
+        let mut out = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+        let mut offsets = Vec::with_capacity(self.objects.len());
+        for (index, object) in self.objects.iter().enumerate() {
+            offsets.push(out.len());
+            out.extend_from_slice(format!("{} 0 obj\n", index + 1).as_bytes());
+            out.extend_from_slice(object);
+            out.extend_from_slice(b"\nendobj\n");
+        }
+
+        let xref = out.len();
+        out.extend_from_slice(format!("xref\n0 {}\n", self.objects.len() + 1).as_bytes());
+        out.extend_from_slice(b"0000000000 65535 f \n");
+        for offset in offsets {
+            out.extend_from_slice(format!("{offset:010} 00000 n \n").as_bytes());
+        }
+        out.extend_from_slice(
+            format!(
+                "trailer\n<< /Size {} /Root {catalog} 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+                self.objects.len() + 1
+            )
+            .as_bytes(),
+        );
+        out
+    }
+}
+
+/// The width of `text` set in Helvetica at `size`.
+pub(crate) fn text_width(text: &str, size: f64) -> f64 {
+    let width: u32 = text
+        .chars()
+        .map(|c| match c {
+            ' '..='~' => u32::from(HELVETICA_WIDTHS[c as usize - ' ' as usize]),
+            _ => u32::from(DEFAULT_WIDTH),
+        })
+        .sum();
+    f64::from(width) * size / 1000.0
+}
+
+/// Break `text` into lines no wider than `max_width` when set at `size`.
+/// Words wider than a line are put on a line of their own.
+pub(crate) fn wrap_text(text: &str, size: f64, max_width: f64) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let candidate = if line.is_empty() {
+                word.to_string()
+            } else {
+                format!("{line} {word}")
+            };
+            if text_width(&candidate, size) <= max_width || line.is_empty() {
+                line = candidate;
+            } else {
+                lines.push(std::mem::replace(&mut line, word.to_string()));
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// `text` as a PDF string literal in `WinAnsiEncoding`.
+pub(crate) fn string_literal(text: &str) -> Vec<u8> {
+    let mut out = vec![b'('];
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => out.extend_from_slice(&[b'\\', c as u8]),
+            ' '..='~' | '\u{a0}'..='\u{ff}' => out.push(c as u32 as u8),
+            // Punctuation of Windows-1252 outside of Latin-1
+            '€' => out.push(0x80),
+            '…' => out.push(0x85),
+            '‘' => out.push(0x91),
+            '’' => out.push(0x92),
+            '“' => out.push(0x93),
+            '”' => out.push(0x94),
+            '•' => out.push(0x95),
+            '–' => out.push(0x96),
+            '—' => out.push(0x97),
+            _ => out.push(b'?'),
+        }
+    }
+    out.push(b')');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_text_at_word_boundaries() {
+        // "aaaa" is 2.224 wide at size 1, a space 0.278
+        let lines = wrap_text("aaaa aaaa aaaa\n\naaaaaaaaaaaa", 1.0, 5.0);
+        assert_eq!(lines, ["aaaa aaaa", "aaaa", "", "aaaaaaaaaaaa"]);
+    }
+
+    #[test]
+    fn escape_string_literals() {
+        assert_eq!(string_literal("f(x) = 1\\2"), b"(f\\(x\\) = 1\\\\2)");
+        assert_eq!(string_literal("10 °C – 5 € ≈"), b"(10 \xb0C \x96 5 \x80 ?)");
+    }
+
+    #[test]
+    fn cross_reference_table_points_to_objects() {
+        let mut document = PdfDocument::new();
+        let catalog = document.reserve();
+        let pages = document.add("<< /Type /Pages /Kids [] /Count 0 >>");
+        document.set(catalog, format!("<< /Type /Catalog /Pages {pages} 0 R >>"));
+        let pdf = document.finish(catalog);
+
+        let find = |needle: &str| {
+            pdf.windows(needle.len())
+                .position(|w| w == needle.as_bytes())
+                .unwrap()
+        };
+        let table = format!(
+            "0000000000 65535 f \n{:010} 00000 n \n{:010} 00000 n \n",
+            find("1 0 obj"),
+            find("2 0 obj")
+        );
+        find(&table);
+        assert!(pdf.ends_with(format!("startxref\n{}\n%%EOF\n", find("xref\n0 3")).as_bytes()));
+    }
+}
@@ -0,0 +1,241 @@
+//! A pool of browser sessions for exporting plots concurrently.
+
+use std::path::Path;
+use std::sync::Mutex;
+#[cfg(any(test, feature = "debug"))]
+use std::{println as error, println as warn};
+
+#[cfg(not(any(test, feature = "debug")))]
+use log::{error, warn};
+use tokio::sync::Semaphore;
+
+use crate::{
+    browser::{Browser, Client},
+    session::SessionConfig,
+    write_image, ExportError, ImageFormat, PlotData,
+};
+
+/// A pool of browser sessions for exporting plots concurrently from async
+/// contexts.
+///
+/// Where an [`AsyncStaticExporter`](crate::AsyncStaticExporter) renders one
+/// plot at a time in a single browser session, an `ExporterPool` hands out up
+/// to `size` sessions of one WebDriver or browser process to concurrent
+/// `write_fig` and `write_to_string` calls. Further calls wait until a session
+/// is free.
+///
+/// Sessions are opened lazily and reused. An idle session is health-checked
+/// before it is handed out, and a session that fails an export is checked as
+/// well: sessions that no longer respond, e.g. because the browser crashed,
+/// are closed and replaced by a new one on the next export.
+///
+/// The pool is `Sync`, so it can be shared between tasks, e.g. in an `Arc`.
+/// Always call `close` when you are done with the pool to ensure proper
+/// cleanup of the sessions and the WebDriver process.
+///
+/// # Examples
+///
+/// ```no_run
+/// // This example requires a running WebDriver (chromedriver) and a browser.
+/// // It cannot be run as a doc test.
+/// use plotly_static::{ImageFormat, StaticExporterBuilder};
+/// use serde_json::json;
+/// use std::path::PathBuf;
+/// use std::sync::Arc;
+///
+/// # async fn run() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+/// let pool = Arc::new(StaticExporterBuilder::default().build_pool(4)?);
+///
+/// let mut tasks = Vec::new();
+/// for i in 0..16 {
+///     let pool = pool.clone();
+///     tasks.push(tokio::spawn(async move {
+///         let plot = json!({"data": [{"type": "bar", "y": [i, 2 * i]}], "layout": {}});
+///         let dst = PathBuf::from(format!("report_{i}"));
+///         pool.write_fig(&dst, &plot, ImageFormat::PNG, 800, 600, 1.0)
+///             .await
+///             .map_err(|e| e.to_string())
+///     }));
+/// }
+/// for task in tasks {
+///     task.await??;
+/// }
+///
+/// Arc::into_inner(pool).unwrap().close().await;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ExporterPool {
+    /// WebDriver or browser process manager for spawning and cleanup
+    browser: Browser,
+
+    /// Settings for the browser sessions
+    session: SessionConfig,
+
+    /// Maximum number of sessions
+    size: usize,
+
+    /// Sessions not in use by an export
+    idle: Mutex<Vec<Client>>,
+
+    /// One permit per session
+    permits: Semaphore,
+}
+
+impl ExporterPool {
+    pub(crate) fn new(browser: Browser, session: SessionConfig, size: usize) -> Self {
+        Self {
+            browser,
+            session,
+            size,
+            idle: Mutex::new(Vec::with_capacity(size)),
+            permits: Semaphore::new(size),
+        }
+    }
+
+    /// Exports a Plotly plot to a static image file.
+    ///
+    /// Same as [`StaticExporter::write_fig`](crate::StaticExporter::write_fig)
+    /// but async, and callable concurrently.
+    pub async fn write_fig(
+        &self,
+        dst: &Path,
+        plot: &serde_json::Value,
+        format: ImageFormat,
+        width: usize,
+        height: usize,
+        scale: f64,
+    ) -> Result<(), ExportError> {
+        let plot_data = PlotData {
+            format: format.clone(),
+            width,
+            height,
+            scale,
+            data: plot,
+        };
+
+        let image_data = self.static_export(&plot_data).await?;
+        write_image(dst, &format, scale, &image_data)
+    }
+
+    /// Exports a Plotly plot to a string representation.
+    ///
+    /// Same as
+    /// [`StaticExporter::write_to_string`](crate::StaticExporter::write_to_string)
+    /// but async, and callable concurrently.
+    pub async fn write_to_string(
+        &self,
+        plot: &serde_json::Value,
+        format: ImageFormat,
+        width: usize,
+        height: usize,
+        scale: f64,
+    ) -> Result<String, ExportError> {
+        let plot_data = PlotData {
+            format,
+            width,
+            height,
+            scale,
+            data: plot,
+        };
+        let image_data = self.static_export(&plot_data).await?;
+        Ok(image_data)
+    }
+
+    /// The maximum number of concurrent sessions.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The number of open sessions that are not in use by an export.
+    pub fn idle_sessions(&self) -> usize {
+        self.idle.lock().unwrap().len()
+    }
+
+    /// Close all sessions and stop the driver if it was spawned.
+    ///
+    /// Always call close to ensure proper cleanup.
+    pub async fn close(&mut self) {
+        self.permits.close();
+        let sessions = std::mem::take(&mut *self.idle.lock().unwrap());
+        for client in sessions {
+            if let Err(e) = client.close().await {
+                error!("Failed to close WebDriver client: {e}");
+            }
+        }
+        if let Err(e) = self.browser.stop() {
+            error!("Failed to stop WebDriver: {e}");
+        }
+    }
+
+    /// Get diagnostic information about the underlying WebDriver process.
+    pub fn get_webdriver_diagnostics(&self) -> String {
+        self.browser.get_diagnostics()
+    }
+
+    /// Render `plot` in a session of the pool, waiting for a free session if
+    /// all are busy.
+    async fn static_export(&self, plot: &PlotData<'_>) -> Result<String, ExportError> {
+        let _permit = self
+            .permits
+            .acquire()
+            .await
+            .map_err(|_| ExportError::InvalidInput("ExporterPool is closed".to_string()))?;
+        let retry = self.session.retry;
+        let mut attempt = 1;
+        loop {
+            let error = match self.checkout().await {
+                Ok(client) => match self.session.render(&client, plot).await {
+                    Ok(image) => {
+                        self.idle.lock().unwrap().push(client);
+                        return Ok(image);
+                    }
+                    Err(e) => {
+                        let responding = self.session.is_alive(&client).await;
+                        let retrying = retry.should_retry(attempt, &e, responding);
+                        if responding
+                            && client.reusable_after(&e)
+                            && !(retrying && retry.restarts_session())
+                        {
+                            self.idle.lock().unwrap().push(client);
+                        } else {
+                            if !responding {
+                                warn!("Recycling WebDriver session that stopped responding");
+                            }
+                            let _ = client.close().await;
+                        }
+                        if !retrying {
+                            return Err(e);
+                        }
+                        e
+                    }
+                },
+                Err(e) if retry.should_retry(attempt, &e, true) => e,
+                Err(e) => return Err(e),
+            };
+            warn!(
+                "Export attempt {attempt} of {} failed: {error}",
+                retry.attempts()
+            );
+            tokio::time::sleep(retry.delay(attempt)).await;
+            attempt += 1;
+        }
+    }
+
+    /// Take a responsive idle session, or open a new one.
+    async fn checkout(&self) -> Result<Client, ExportError> {
+        loop {
+            let idle = self.idle.lock().unwrap().pop();
+            match idle {
+                Some(client) if self.session.is_alive(&client).await => return Ok(client),
+                Some(client) => {
+                    warn!("Discarding idle WebDriver session that stopped responding");
+                    let _ = client.close().await;
+                }
+                None => {
+                    return self.session.connect(&self.browser).await;
+                }
+            }
+        }
+    }
+}
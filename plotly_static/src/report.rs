@@ -0,0 +1,515 @@
+//! Multi-page PDF reports of several plots.
+
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose, Engine as _};
+
+use crate::{
+    animation::decode_rgba,
+    pdf::{string_literal, text_width, wrap_text, PdfDocument},
+    ExportError,
+};
+
+const MM_PER_INCH: f64 = 25.4;
+
+/// PDF units (points) per inch.
+const PT_PER_INCH: f64 = 72.0;
+
+/// CSS pixels per point.
+const PX_PER_PT: f64 = 96.0 / 72.0;
+
+const TITLE_SIZE: f64 = 28.0;
+const SUBTITLE_SIZE: f64 = 16.0;
+const HEADING_SIZE: f64 = 18.0;
+const TEXT_SIZE: f64 = 11.0;
+const LINE_SPACING: f64 = 1.35;
+
+/// The default scale of the plots, for a print resolution of 300 dpi.
+const PRINT_SCALE: f64 = 300.0 / 96.0;
+
+/// Paper sizes of a [`PdfReport`], in portrait orientation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PageSize {
+    /// ISO A3, 297mm x 420mm
+    A3,
+    /// ISO A4, 210mm x 297mm
+    A4,
+    /// ISO A5, 148mm x 210mm
+    A5,
+    /// US Letter, 8.5in x 11in
+    Letter,
+    /// US Legal, 8.5in x 14in
+    Legal,
+    /// A custom paper size in millimeters
+    Custom { width_mm: f64, height_mm: f64 },
+}
+
+impl PageSize {
+    /// Width and height in millimeters.
+    fn size_mm(&self) -> (f64, f64) {
+        match *self {
+            Self::A3 => (297.0, 420.0),
+            Self::A4 => (210.0, 297.0),
+            Self::A5 => (148.0, 210.0),
+            Self::Letter => (8.5 * MM_PER_INCH, 11.0 * MM_PER_INCH),
+            Self::Legal => (8.5 * MM_PER_INCH, 14.0 * MM_PER_INCH),
+            Self::Custom {
+                width_mm,
+                height_mm,
+            } => (width_mm, height_mm),
+        }
+    }
+}
+
+/// Page orientation of a [`PdfReport`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Orientation {
+    #[default]
+    Portrait,
+    Landscape,
+}
+
+#[derive(Debug, Clone)]
+enum ReportPage {
+    Title { title: String, subtitle: String },
+    Text { heading: String, text: String },
+    Plot(serde_json::Value),
+}
+
+/// A multi-page PDF document of plots, title pages and text pages.
+///
+/// Pages are added in order with `title_page`, `text_page` and `plot`. Each
+/// plot fills the area within the margins of its own page; text that does
+/// not fit on one page continues on the next. Text is set in Helvetica,
+/// characters outside of Windows-1252 are replaced by `?`.
+///
+/// Plots are embedded as raster images: each plot is rendered to PNG and
+/// stored losslessly in the PDF, so unlike a single plot exported with
+/// [`ImageFormat::PDF`](crate::ImageFormat::PDF) it is not vector graphics.
+/// The default scale renders plots at 300 dpi, which prints sharply; lower it
+/// with [`scale`](PdfReport::scale) for smaller files.
+///
+/// The report is exported with
+/// [`StaticExporter::write_pdf_report`](crate::StaticExporter::write_pdf_report),
+/// which renders all plots in one browser session.
+///
+/// # Examples
+///
+/// ```rust
+/// use plotly_static::{Orientation, PageSize, PdfReport};
+/// use serde_json::json;
+///
+/// let plot = json!({"data": [{"type": "bar", "y": [3, 1, 2]}], "layout": {}});
+///
+/// let report = PdfReport::new()
+///     .page_size(PageSize::A4)
+///     .orientation(Orientation::Landscape)
+///     .margins_mm(20.0, 15.0, 20.0, 15.0)
+///     .title_page("Weekly report", "Week 42")
+///     .text_page("Summary", "Sales went up.\nReturns went down.")
+///     .plot(plot.clone())
+///     .plot(plot);
+/// ```
+///
+/// # Default Configuration
+///
+/// - Page size: A4
+/// - Orientation: portrait
+/// - Margins: 15mm
+/// - Plot scale: 3.125 (300 dpi)
+#[derive(Debug, Clone)]
+pub struct PdfReport {
+    page_size: PageSize,
+    orientation: Orientation,
+    /// Top, right, bottom and left margins in millimeters
+    margins_mm: [f64; 4],
+    scale: f64,
+    pages: Vec<ReportPage>,
+}
+
+impl Default for PdfReport {
+    fn default() -> Self {
+        Self {
+            page_size: PageSize::A4,
+            orientation: Orientation::Portrait,
+            margins_mm: [15.0; 4],
+            scale: PRINT_SCALE,
+            pages: Vec::new(),
+        }
+    }
+}
+
+impl PdfReport {
+    /// Create an empty report with the default configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the paper size.
+    pub fn page_size(mut self, page_size: PageSize) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Set the page orientation.
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Set the page margins in millimeters, in CSS order.
+    pub fn margins_mm(mut self, top: f64, right: f64, bottom: f64, left: f64) -> Self {
+        self.margins_mm = [top, right, bottom, left];
+        self
+    }
+
+    /// Set the scale the plots are rendered at.
+    ///
+    /// Plots are laid out at 96 pixels per inch of the page and rendered with
+    /// `scale` image pixels per layout pixel, so the resolution of the
+    /// embedded images is `96 * scale` dpi.
+    pub fn scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Add a page with a centered title and an optional subtitle, which is
+    /// omitted if empty.
+    pub fn title_page(mut self, title: &str, subtitle: &str) -> Self {
+        self.pages.push(ReportPage::Title {
+            title: title.to_string(),
+            subtitle: subtitle.to_string(),
+        });
+        self
+    }
+
+    /// Add a page of text below an optional heading, which is omitted if
+    /// empty. Lines of `text` are set as separate paragraphs.
+    pub fn text_page(mut self, heading: &str, text: &str) -> Self {
+        self.pages.push(ReportPage::Text {
+            heading: heading.to_string(),
+            text: text.to_string(),
+        });
+        self
+    }
+
+    /// Add a page showing a Plotly plot, given as JSON.
+    pub fn plot(mut self, plot: serde_json::Value) -> Self {
+        self.pages.push(ReportPage::Plot(plot));
+        self
+    }
+
+    /// The plots of the report, in page order.
+    pub(crate) fn plots(&self) -> impl Iterator<Item = &serde_json::Value> {
+        self.pages.iter().filter_map(|page| match page {
+            ReportPage::Plot(plot) => Some(plot),
+            _ => None,
+        })
+    }
+
+    pub(crate) fn scale_factor(&self) -> f64 {
+        self.scale
+    }
+
+    /// Check that the report can be exported, before any plot is rendered.
+    pub(crate) fn check(&self) -> Result<(), ExportError> {
+        if self.pages.is_empty() {
+            return Err(ExportError::InvalidInput(
+                "A PdfReport needs at least one page".to_string(),
+            ));
+        }
+        if !(self.scale.is_finite() && self.scale > 0.0) {
+            return Err(ExportError::InvalidInput(format!(
+                "The scale of a PdfReport must be positive, got {}",
+                self.scale
+            )));
+        }
+        let (width, height) = self.plot_size();
+        if width == 0 || height == 0 {
+            return Err(ExportError::InvalidInput(
+                "The margins of the PdfReport leave no space".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// The width and height plots are rendered at, in CSS pixels.
+    ///
+    /// Both are 0 if the margins leave no space, see [`PdfReport::check`].
+    pub(crate) fn plot_size(&self) -> (usize, usize) {
+        let layout = self.layout();
+        let (width, height) = (
+            (layout.content_width() * PX_PER_PT).round(),
+            (layout.content_height() * PX_PER_PT).round(),
+        );
+        if width >= 1.0 && height >= 1.0 {
+            (width as usize, height as usize)
+        } else {
+            (0, 0)
+        }
+    }
+
+    fn layout(&self) -> PageLayout {
+        let (width, height) = match (self.page_size.size_mm(), self.orientation) {
+            ((w, h), Orientation::Portrait) => (w.min(h), w.max(h)),
+            ((w, h), Orientation::Landscape) => (w.max(h), w.min(h)),
+        };
+        let [top, right, bottom, left] = self.margins_mm.map(mm_to_pt);
+        PageLayout {
+            width: mm_to_pt(width),
+            height: mm_to_pt(height),
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+
+    /// Assemble the PDF document, with the PNG `images` exported for the
+    /// plots of the report, base64 encoded.
+    pub(crate) fn to_pdf(&self, images: &[String]) -> Result<Vec<u8>> {
+        self.check()?;
+        let layout = self.layout();
+
+        let mut document = PdfDocument::new();
+        let catalog = document.reserve();
+        let pages = document.reserve();
+        let font = document.add(
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>",
+        );
+
+        let mut images = images.iter();
+        let mut kids = Vec::new();
+        for page in &self.pages {
+            let mut x_object = String::new();
+            let contents = match page {
+                ReportPage::Title { title, subtitle } => vec![layout.title(title, subtitle)],
+                ReportPage::Text { heading, text } => layout.text(heading, text),
+                ReportPage::Plot(_) => {
+                    let image = images
+                        .next()
+                        .ok_or(anyhow!("Missing the image of a plot of the PdfReport"))?;
+                    let image = general_purpose::STANDARD
+                        .decode(image)
+                        .map_err(anyhow::Error::from)
+                        .and_then(|data| decode_rgba(&data))
+                        .map_err(|e| ExportError::Decode(format!("{e:#}")))?;
+                    let id = document.add_stream(
+                        &format!(
+                            "/Type /XObject /Subtype /Image /Width {} /Height {} \
+                             /ColorSpace /DeviceRGB /BitsPerComponent 8",
+                            image.width, image.height
+                        ),
+                        &to_rgb_on_white(&image.pixels),
+                    )?;
+                    x_object = format!(" /XObject << /Im0 {id} 0 R >>");
+                    vec![layout.image(image.width, image.height)]
+                }
+            };
+
+            for content in contents {
+                let content = document.add_stream("", &content)?;
+                kids.push(document.add(format!(
+                    "<< /Type /Page /Parent {pages} 0 R /MediaBox [0 0 {:.2} {:.2}] \
+                     /Resources << /Font << /F1 {font} 0 R >>{x_object} >> /Contents {content} 0 R >>",
+                    layout.width, layout.height
+                )));
+            }
+        }
+
+        let kids: Vec<String> = kids.iter().map(|id| format!("{id} 0 R")).collect();
+        document.set(
+            pages,
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                kids.join(" "),
+                kids.len()
+            ),
+        );
+        document.set(catalog, format!("<< /Type /Catalog /Pages {pages} 0 R >>"));
+        Ok(document.finish(catalog))
+    }
+}
+
+/// Page dimensions and margins in points.
+struct PageLayout {
+    width: f64,
+    height: f64,
+    top: f64,
+    right: f64,
+    bottom: f64,
+    left: f64,
+}
+
+impl PageLayout {
+    fn content_width(&self) -> f64 {
+        self.width - self.left - self.right
+    }
+
+    fn content_height(&self) -> f64 {
+        self.height - self.top - self.bottom
+    }
+
+    /// The content stream of a title page.
+    fn title(&self, title: &str, subtitle: &str) -> Vec<u8> {
+        let mut lines: Vec<(String, f64)> = wrap_text(title, TITLE_SIZE, self.content_width())
+            .into_iter()
+            .map(|line| (line, TITLE_SIZE))
+            .collect();
+        if !subtitle.is_empty() {
+            lines.extend(
+                wrap_text(subtitle, SUBTITLE_SIZE, self.content_width())
+                    .into_iter()
+                    .map(|line| (line, SUBTITLE_SIZE)),
+            );
+        }
+
+        // Center the block of lines slightly above the middle of the page
+        let block_height: f64 = lines.iter().map(|(_, size)| size * LINE_SPACING).sum();
+        let mut y = self.bottom + (self.content_height() + block_height) / 2.0 + TITLE_SIZE;
+        let mut content = Vec::new();
+        for (line, size) in lines {
+            y -= size * LINE_SPACING;
+            let x = self.left + (self.content_width() - text_width(&line, size)) / 2.0;
+            show_text(&mut content, x, y, size, &line);
+        }
+        content
+    }
+
+    /// The content streams of a text page, continued on as many pages as
+    /// needed.
+    fn text(&self, heading: &str, text: &str) -> Vec<Vec<u8>> {
+        let mut pages = Vec::new();
+        let mut content = Vec::new();
+        let mut y = self.height - self.top;
+        if !heading.is_empty() {
+            for line in wrap_text(heading, HEADING_SIZE, self.content_width()) {
+                y -= HEADING_SIZE * LINE_SPACING;
+                show_text(&mut content, self.left, y, HEADING_SIZE, &line);
+            }
+            y -= TEXT_SIZE;
+        }
+        for line in wrap_text(text, TEXT_SIZE, self.content_width()) {
+            y -= TEXT_SIZE * LINE_SPACING;
+            if y < self.bottom {
+                pages.push(std::mem::take(&mut content));
+                y = self.height - self.top - TEXT_SIZE * LINE_SPACING;
+            }
+            show_text(&mut content, self.left, y, TEXT_SIZE, &line);
+        }
+        pages.push(content);
+        pages
+    }
+
+    /// The content stream of a page showing an image of `width` x `height`
+    /// pixels as large as fits within the margins, centered.
+    fn image(&self, width: u32, height: u32) -> Vec<u8> {
+        let ratio = (self.content_width() / f64::from(width))
+            .min(self.content_height() / f64::from(height));
+        let (w, h) = (f64::from(width) * ratio, f64::from(height) * ratio);
+        let x = self.left + (self.content_width() - w) / 2.0;
+        let y = self.bottom + (self.content_height() - h) / 2.0;
+        format!("q {w:.2} 0 0 {h:.2} {x:.2} {y:.2} cm /Im0 Do Q\n").into_bytes()
+    }
+}
+
+fn show_text(content: &mut Vec<u8>, x: f64, y: f64, size: f64, text: &str) {
+    content.extend_from_slice(format!("BT /F1 {size:.1} Tf {x:.2} {y:.2} Td ").as_bytes());
+    content.extend_from_slice(&string_literal(text));
+    content.extend_from_slice(b" Tj ET\n");
+}
+
+fn mm_to_pt(mm: f64) -> f64 {
+    mm / MM_PER_INCH * PT_PER_INCH
+}
+
+/// Blend RGBA pixels onto a white background, as PDF images are opaque.
+fn to_rgb_on_white(rgba: &[u8]) -> Vec<u8> {
+    rgba.chunks_exact(4)
+        .flat_map(|pixel| {
+            let alpha = u16::from(pixel[3]);
+            let blend = move |c: u8| ((u16::from(c) * alpha + 255 * (255 - alpha)) / 255) as u8;
+            [blend(pixel[0]), blend(pixel[1]), blend(pixel[2])]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png_base64(width: u32, height: u32) -> String {
+        let mut data = Vec::new();
+        {
+            let mut encoder = png::Encoder::new(&mut data, width, height);
+            encoder.set_color(png::ColorType::Rgba);
+            encoder.set_depth(png::BitDepth::Eight);
+            let mut writer = encoder.write_header().unwrap();
+            let pixels = [0, 0, 255, 128].repeat((width * height) as usize);
+            writer.write_image_data(&pixels).unwrap();
+        }
+        general_purpose::STANDARD.encode(data)
+    }
+
+    #[test]
+    fn plots_fill_the_page_within_margins() {
+        let report = PdfReport::new()
+            .page_size(PageSize::Letter)
+            .orientation(Orientation::Landscape)
+            .margins_mm(0.0, MM_PER_INCH, 0.0, MM_PER_INCH);
+        // 9in x 8.5in at 96 px/in
+        assert_eq!(report.plot_size(), (864, 816));
+    }
+
+    #[test]
+    fn reject_invalid_reports_before_rendering() {
+        let report = PdfReport::new().plot(serde_json::json!({}));
+        assert!(report.check().is_ok());
+
+        let invalid = [
+            PdfReport::new(),
+            report.clone().scale(0.0),
+            report.clone().scale(f64::NAN),
+            report.clone().margins_mm(0.0, 150.0, 0.0, 150.0),
+            report.clone().margins_mm(0.0, f64::INFINITY, 0.0, 0.0),
+        ];
+        for report in invalid {
+            assert!(matches!(report.check(), Err(ExportError::InvalidInput(_))));
+        }
+        // A negative width is not cast to 0 pixels
+        assert_eq!(
+            report.margins_mm(0.0, 150.0, 0.0, 150.0).plot_size(),
+            (0, 0)
+        );
+    }
+
+    #[test]
+    fn report_pages_are_assembled_in_order() {
+        let long_text = "All work and no play makes Jack a dull boy.\n".repeat(80);
+        let report = PdfReport::new()
+            .title_page("Weekly report", "")
+            .plot(serde_json::json!({}))
+            .text_page("Notes", &long_text)
+            .plot(serde_json::json!({}));
+        assert_eq!(report.plots().count(), 2);
+
+        let pdf = report
+            .to_pdf(&[png_base64(4, 3), png_base64(4, 3)])
+            .unwrap();
+        let pdf = String::from_utf8_lossy(&pdf);
+        assert!(pdf.starts_with("%PDF-1.4"));
+        // The 80 lines of text need two A4 pages
+        assert!(pdf.contains("/Count 5 >>"));
+        assert_eq!(pdf.matches("/Subtype /Image /Width 4 /Height 3").count(), 2);
+
+        assert!(report.to_pdf(&[png_base64(4, 3)]).is_err());
+        assert!(PdfReport::new().to_pdf(&[]).is_err());
+    }
+
+    #[test]
+    fn blend_transparent_pixels_onto_white() {
+        assert_eq!(
+            to_rgb_on_white(&[0, 0, 255, 128, 10, 20, 30, 255]),
+            [127, 127, 255, 10, 20, 30]
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! Retrying exports that failed for reasons unrelated to the plot.
+
+use std::time::Duration;
+
+use crate::ExportError;
+
+/// How often and how an export is retried after a transient failure.
+///
+/// An export is retried if the browser timed out, the export page did not
+/// load or no session could be opened, or if the browser session stopped
+/// responding. Plots that plotly.js cannot draw fail immediately. Between
+/// attempts the exporter waits for the backoff, which doubles after every
+/// attempt, and by default opens a new browser session.
+///
+/// # Examples
+///
+/// ```rust
+/// use plotly_static::{RetryPolicy, StaticExporterBuilder};
+/// use std::time::Duration;
+///
+/// // Try every export up to three times, waiting 2s and then 4s in between
+/// let policy = RetryPolicy::new(3).backoff(Duration::from_secs(2));
+/// assert_eq!(policy.attempts(), 3);
+///
+/// let builder = StaticExporterBuilder::default().retry_policy(policy);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    attempts: u32,
+    backoff: Duration,
+    restart_session: bool,
+}
+
+impl RetryPolicy {
+    /// A policy trying each export up to `attempts` times, at least once.
+    pub fn new(attempts: u32) -> Self {
+        Self {
+            attempts: attempts.max(1),
+            backoff: Duration::from_secs(1),
+            restart_session: true,
+        }
+    }
+
+    /// Sets the wait before the second attempt, doubled before every further
+    /// attempt (default: 1s).
+    pub fn backoff(mut self, delay: Duration) -> Self {
+        self.backoff = delay;
+        self
+    }
+
+    /// Sets whether a failed browser session is closed and a new one opened
+    /// for the next attempt (default: true).
+    pub fn restart_session(mut self, yes: bool) -> Self {
+        self.restart_session = yes;
+        self
+    }
+
+    /// The maximum number of attempts of an export.
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Whether attempt number `attempt`, counted from 1, is followed by
+    /// another one after it failed with `error`. `responding` tells whether
+    /// the browser session still works.
+    pub(crate) fn should_retry(&self, attempt: u32, error: &ExportError, responding: bool) -> bool {
+        let transient = matches!(
+            error,
+            ExportError::Timeout(_) | ExportError::PageLoad(_) | ExportError::SessionCreation(_)
+        );
+        attempt < self.attempts && (transient || !responding)
+    }
+
+    /// Whether the browser session is replaced after a failed attempt.
+    pub(crate) fn restarts_session(&self) -> bool {
+        self.restart_session
+    }
+
+    /// The wait after the failed attempt number `attempt`.
+    pub(crate) fn delay(&self, attempt: u32) -> Duration {
+        self.backoff
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+    }
+}
+
+impl Default for RetryPolicy {
+    /// A single attempt, without retries.
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retries_transient_failures_only() {
+        let policy = RetryPolicy::new(3);
+        let timeout = ExportError::Timeout("waiting for the plot to render".into());
+        let render = ExportError::Render("Error: invalid trace type".into());
+
+        assert!(policy.should_retry(1, &timeout, true));
+        assert!(policy.should_retry(2, &timeout, true));
+        assert!(!policy.should_retry(3, &timeout, true));
+        assert!(!policy.should_retry(1, &render, true));
+        assert!(policy.should_retry(1, &render, false));
+        assert!(!RetryPolicy::default().should_retry(1, &timeout, false));
+    }
+
+    #[test]
+    fn backoff_doubles() {
+        let policy = RetryPolicy::new(4).backoff(Duration::from_millis(250));
+        let delays: Vec<_> = (1..4).map(|attempt| policy.delay(attempt)).collect();
+        assert_eq!(delays, [250, 500, 1000].map(Duration::from_millis).to_vec());
+        assert_eq!(RetryPolicy::new(0).attempts(), 1);
+    }
+}
@@ -0,0 +1,479 @@
+//! Browser session handling shared by the exporters.
+//!
+//! A [`SessionConfig`] holds the settings needed to open browser sessions
+//! and to render plots in them, independently of how the sessions are kept
+//! around by [`crate::AsyncStaticExporter`] or [`crate::ExporterPool`].
+
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+#[cfg(any(test, feature = "debug"))]
+use std::{println as error, println as debug};
+
+use anyhow::{Context, Result};
+use fantoccini::{wd::Capabilities, ClientBuilder};
+#[cfg(not(any(test, feature = "debug")))]
+use log::{debug, error};
+#[cfg(any(feature = "chromedriver", feature = "geckodriver"))]
+use serde_json::map::Map as JsonMap;
+use urlencoding::encode;
+
+#[cfg(not(any(feature = "chromedriver", feature = "geckodriver")))]
+use crate::DRIVER_FEATURE_REQUIRED;
+use crate::{
+    browser::{Browser, Client},
+    common,
+    template::{
+        self, animation_frame_js_script, animation_setup_js_script, image_export_js_script,
+        pdf_export_js_script,
+    },
+    ExportError, ImageFormat, PlotData, RetryPolicy,
+};
+
+/// Settings for opening browser sessions and rendering plots in them.
+#[derive(Debug, Clone)]
+pub(crate) struct SessionConfig {
+    /// WebDriver server port
+    pub(crate) webdriver_port: u32,
+    /// WebDriver server base URL
+    pub(crate) webdriver_url: String,
+    /// Use bundled JS libraries instead of CDN
+    pub(crate) offline_mode: bool,
+    /// PDF export timeout in milliseconds
+    pub(crate) pdf_export_timeout: u32,
+    /// Browser command-line flags (e.g., "--headless", "--no-sandbox")
+    #[cfg_attr(
+        not(any(feature = "chromedriver", feature = "geckodriver")),
+        allow(dead_code)
+    )]
+    pub(crate) webdriver_browser_caps: Vec<String>,
+    /// Time for the export page to load
+    pub(crate) page_load_timeout: Duration,
+    /// Time for plotly.js to render and export one image
+    pub(crate) render_timeout: Duration,
+    /// Time for any other script run in the browser session
+    pub(crate) script_timeout: Duration,
+    /// Retries of exports that failed for reasons unrelated to the plot
+    pub(crate) retry: RetryPolicy,
+    /// The export page of offline mode, shared by all sessions
+    pub(crate) offline_page: Arc<OfflinePage>,
+}
+
+/// The export page of offline mode, written to a temporary file on first use
+/// and removed when the last exporter sharing it is dropped.
+///
+/// The page embeds the JS libraries, so it is too large for a data URI and
+/// too large to write again for every export.
+#[derive(Debug, Default)]
+pub(crate) struct OfflinePage {
+    path: Mutex<Option<PathBuf>>,
+}
+
+impl OfflinePage {
+    /// The `file://` URL of the page, writing it if needed.
+    fn url(&self) -> Result<String> {
+        let mut path = self.path.lock().unwrap();
+        // Written again if removed, e.g. by a cleanup of the temp directory
+        let path = match path.as_ref().filter(|path| path.exists()) {
+            Some(path) => path,
+            None => path.insert(
+                template::to_file(&template::get_html_body(true))
+                    .with_context(|| "Failed to create temporary HTML file")?,
+            ),
+        };
+        Ok(format!("file://{}", path.to_string_lossy()))
+    }
+}
+
+impl Drop for OfflinePage {
+    fn drop(&mut self) {
+        if let Some(path) = self.path.get_mut().ok().and_then(|path| path.take()) {
+            if let Err(e) = std::fs::remove_file(&path) {
+                debug!("Failed to remove {path:?}: {e}");
+            }
+        }
+    }
+}
+
+impl SessionConfig {
+    /// Open a new session in `browser`.
+    pub(crate) async fn connect(&self, browser: &Browser) -> Result<Client, ExportError> {
+        let client = match browser {
+            Browser::WebDriver(_) => self.connect_webdriver().await.map(Client::WebDriver),
+            #[cfg(feature = "cdp")]
+            Browser::Cdp(chrome) => {
+                debug!("Opening new Chrome DevTools session");
+                chrome
+                    .new_page(self.page_load_timeout, self.browser_script_timeout())
+                    .await
+                    .map(Client::Cdp)
+                    .with_context(|| "Chrome DevTools session error")
+            }
+        };
+        client.map_err(|e| ExportError::classify(e, ExportError::SessionCreation))
+    }
+
+    async fn connect_webdriver(&self) -> Result<fantoccini::Client> {
+        let caps = self.build_webdriver_caps()?;
+        let webdriver_url = format!("{}:{}", self.webdriver_url, self.webdriver_port);
+        debug!("Creating new WebDriver session at {webdriver_url}");
+        ClientBuilder::native()
+            .capabilities(caps)
+            .connect(&webdriver_url)
+            .await
+            .with_context(|| "WebDriver session error")
+    }
+
+    /// Check whether a WebDriver session still responds, e.g. after the
+    /// browser crashed during an export.
+    pub(crate) async fn is_alive(&self, client: &Client) -> bool {
+        within(
+            self.script_timeout,
+            "the browser session to respond",
+            client.execute("return true;", vec![]),
+        )
+        .await
+        .is_ok()
+    }
+
+    /// The time the browser allows a script to run: exporting an image runs
+    /// a script too, so this is the longer of the script and render timeouts.
+    #[cfg_attr(
+        not(any(feature = "chromedriver", feature = "geckodriver", feature = "cdp")),
+        allow(dead_code)
+    )]
+    fn browser_script_timeout(&self) -> Duration {
+        self.script_timeout.max(self.render_timeout)
+    }
+
+    /// Export the Plotly plot image to a string representation calling the
+    /// Plotly.toImage function in the browser session `client`.
+    pub(crate) async fn render(
+        &self,
+        client: &Client,
+        plot: &PlotData<'_>,
+    ) -> Result<String, ExportError> {
+        self.load_page(client).await?;
+        self.render_loaded(client, plot).await
+    }
+
+    /// Same as `render`, in a browser session that already shows the export
+    /// page opened by `load_page`.
+    pub(crate) async fn render_loaded(
+        &self,
+        client: &Client,
+        plot: &PlotData<'_>,
+    ) -> Result<String, ExportError> {
+        self.extract(client, plot)
+            .await
+            .with_context(|| "Failed to extract static image from browser session")
+            .map_err(|e| ExportError::classify(e, ExportError::Render))
+    }
+
+    /// Open the HTML page with the Plotly libraries that plots are rendered
+    /// into.
+    pub(crate) async fn load_page(&self, client: &Client) -> Result<(), ExportError> {
+        within(
+            self.page_load_timeout,
+            "the export page to load",
+            self.open_page(client),
+        )
+        .await
+        .with_context(|| "Failed to load the export page in the browser session")
+        .map_err(|e| ExportError::classify(e, ExportError::PageLoad))
+    }
+
+    async fn open_page(&self, client: &Client) -> Result<()> {
+        debug!(
+            "Use WebDriver and headless browser to export static plot (offline_mode={}, port={})",
+            self.offline_mode, self.webdriver_port
+        );
+        // For offline mode, load the HTML from a file to avoid data URI size limits
+        // since JS libraries are embedded in the file
+        let url = if self.offline_mode {
+            self.offline_page.url()?
+        } else {
+            // For online mode, use data URI (smaller size since JS is loaded from CDN)
+            let html_content = template::get_html_body(false);
+            format!("data:text/html,{}", encode(&html_content))
+        };
+
+        // Open the HTML
+        client.goto(&url).await?;
+
+        #[cfg(target_os = "windows")]
+        wait_for_document_ready(client).await;
+
+        // Wait for Plotly container element
+        #[cfg(target_os = "windows")]
+        wait_for_plotly_container(client).await;
+
+        // In online mode, ensure Plotly is loaded
+        if !self.offline_mode {
+            #[cfg(target_os = "windows")]
+            wait_for_plotly_loaded(client).await;
+        }
+
+        Ok(())
+    }
+
+    /// Extract a static image from a browser session showing the export page.
+    async fn extract(&self, client: &Client, plot: &PlotData<'_>) -> Result<String> {
+        let (js_script, args) = match plot.format {
+            ImageFormat::PDF => {
+                // Always use SVG for PDF export
+                let args = vec![
+                    plot.data.clone(),
+                    ImageFormat::SVG.to_string().into(),
+                    plot.width.into(),
+                    plot.height.into(),
+                    plot.scale.into(),
+                ];
+
+                (pdf_export_js_script(self.pdf_export_timeout), args)
+            }
+            _ => {
+                let args = vec![
+                    plot.data.clone(),
+                    plot.format.to_string().into(),
+                    plot.width.into(),
+                    plot.height.into(),
+                    plot.scale.into(),
+                ];
+
+                (image_export_js_script(), args)
+            }
+        };
+
+        let data = within(
+            self.render_timeout,
+            "the plot to render",
+            client.execute_async(&js_script, args),
+        )
+        .await?;
+        Ok(decode_result(&data, &plot.format)?)
+    }
+
+    /// Draw the animated `plot` and export an image of every frame, in the
+    /// order of its `frames`, stepping through them with `Plotly.animate`.
+    pub(crate) async fn render_frames(
+        &self,
+        client: &Client,
+        plot: &PlotData<'_>,
+    ) -> Result<Vec<String>, ExportError> {
+        let frames = match plot.data.get("frames").and_then(|f| f.as_array()) {
+            Some(frames) if !frames.is_empty() => frames,
+            _ => {
+                return Err(ExportError::InvalidInput(
+                    "The plot has no animation frames".to_string(),
+                ))
+            }
+        };
+
+        self.load_page(client).await?;
+        self.capture_frames(client, plot, frames)
+            .await
+            .map_err(|e| ExportError::classify(e, ExportError::Render))
+    }
+
+    async fn capture_frames(
+        &self,
+        client: &Client,
+        plot: &PlotData<'_>,
+        frames: &[serde_json::Value],
+    ) -> Result<Vec<String>> {
+        let data = within(
+            self.render_timeout,
+            "the animation to render",
+            client.execute_async(&animation_setup_js_script(), vec![plot.data.clone()]),
+        )
+        .await?;
+        if let Some(err) = data.as_str().and_then(|r| r.strip_prefix("ERROR:")) {
+            return Err(ExportError::Render(err.to_string()).into());
+        }
+
+        let mut images = Vec::with_capacity(frames.len());
+        for (index, frame) in frames.iter().enumerate() {
+            debug!(
+                "Exporting animation frame {} of {}",
+                index + 1,
+                frames.len()
+            );
+            let args = vec![
+                frame.clone(),
+                plot.format.to_string().into(),
+                plot.width.into(),
+                plot.height.into(),
+                plot.scale.into(),
+            ];
+            let data = within(
+                self.render_timeout,
+                "the animation frame to render",
+                client.execute_async(&animation_frame_js_script(), args),
+            )
+            .await?;
+            let image = decode_result(&data, &plot.format)
+                .with_context(|| format!("Failed to export animation frame {index}"))?;
+            images.push(image);
+        }
+        Ok(images)
+    }
+
+    fn build_webdriver_caps(&self) -> Result<Capabilities> {
+        #[cfg(not(any(feature = "chromedriver", feature = "geckodriver")))]
+        {
+            Err(anyhow::anyhow!(DRIVER_FEATURE_REQUIRED))
+        }
+        #[cfg(any(feature = "chromedriver", feature = "geckodriver"))]
+        {
+            // Define browser capabilities (copied to avoid reordering existing code)
+            let mut caps = JsonMap::new();
+            let mut browser_opts = JsonMap::new();
+            let browser_args = self.webdriver_browser_caps.clone();
+
+            browser_opts.insert("args".to_string(), serde_json::json!(browser_args));
+
+            // Add Chrome binary capability if BROWSER_PATH is set
+            #[cfg(feature = "chromedriver")]
+            if let Ok(chrome_path) = std::env::var("BROWSER_PATH") {
+                browser_opts.insert("binary".to_string(), serde_json::json!(chrome_path));
+                debug!("Added Chrome binary capability: {chrome_path}");
+            }
+            // Add Firefox binary capability if BROWSER_PATH is set
+            #[cfg(feature = "geckodriver")]
+            if let Ok(firefox_path) = std::env::var("BROWSER_PATH") {
+                browser_opts.insert("binary".to_string(), serde_json::json!(firefox_path));
+                debug!("Added Firefox binary capability: {firefox_path}");
+            }
+
+            // Add Firefox-specific preferences for CI environments
+            #[cfg(feature = "geckodriver")]
+            {
+                let prefs = common::get_firefox_ci_preferences();
+                browser_opts.insert("prefs".to_string(), serde_json::json!(prefs));
+                debug!("Added Firefox preferences for CI compatibility");
+            }
+
+            caps.insert(
+                "timeouts".to_string(),
+                serde_json::json!({
+                    "pageLoad": self.page_load_timeout.as_millis() as u64,
+                    "script": self.browser_script_timeout().as_millis() as u64,
+                }),
+            );
+            caps.insert(
+                "browserName".to_string(),
+                serde_json::json!(crate::get_browser_name()),
+            );
+            caps.insert(
+                crate::get_options_key().to_string(),
+                serde_json::json!(browser_opts),
+            );
+
+            debug!("WebDriver capabilities: {caps:?}");
+
+            Ok(caps)
+        }
+    }
+}
+
+/// Run `future`, failing with a timeout while `waiting for` its end after
+/// `timeout`.
+async fn within<T>(
+    timeout: Duration,
+    waiting_for: &str,
+    future: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    tokio::time::timeout(timeout, future)
+        .await
+        .map_err(|_| ExportError::Timeout(format!("waiting for {waiting_for}")))?
+}
+
+/// Extract the image data from the result of an export script.
+fn decode_result(data: &serde_json::Value, format: &ImageFormat) -> Result<String, ExportError> {
+    let result = data.as_str().ok_or(ExportError::Render(
+        "Failed to execute Plotly.toImage in browser session".to_string(),
+    ))?;
+
+    if let Some(err) = result.strip_prefix("ERROR:") {
+        return Err(ExportError::Render(err.to_string()));
+    }
+
+    let image = match format {
+        ImageFormat::SVG => common::extract_plain(result, format),
+        ImageFormat::PNG | ImageFormat::JPEG | ImageFormat::WEBP | ImageFormat::PDF => {
+            common::extract_encoded(result, format)
+        }
+        #[allow(deprecated)]
+        ImageFormat::EPS => {
+            error!("EPS format is deprecated. Use SVG or PDF instead.");
+            common::extract_encoded(result, format)
+        }
+    };
+    image.map_err(|e| ExportError::Decode(e.to_string()))
+}
+
+/// Wait until the document of the export page is loaded. The page load
+/// timeout of `load_page` bounds the waits.
+#[cfg(target_os = "windows")]
+async fn wait_for_document_ready(client: &Client) {
+    loop {
+        let state = client
+            .execute("return document.readyState;", vec![])
+            .await
+            .unwrap_or(serde_json::Value::Null);
+        if state.as_str().map(|s| s == "complete").unwrap_or(false) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+}
+
+#[cfg(target_os = "windows")]
+async fn wait_for_plotly_container(client: &Client) {
+    loop {
+        let has_el = client
+            .execute(
+                "return !!document.getElementById('plotly-html-element');",
+                vec![],
+            )
+            .await
+            .unwrap_or(serde_json::Value::Bool(false));
+        if has_el.as_bool().unwrap_or(false) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+}
+
+#[cfg(target_os = "windows")]
+async fn wait_for_plotly_loaded(client: &Client) {
+    loop {
+        let has_plotly = client
+            .execute("return !!window.Plotly;", vec![])
+            .await
+            .unwrap_or(serde_json::Value::Bool(false));
+        if has_plotly.as_bool().unwrap_or(false) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offline_page_is_written_once_and_removed_on_drop() {
+        let page = OfflinePage::default();
+        let url = page.url().unwrap();
+        assert_eq!(page.url().unwrap(), url);
+        let path = PathBuf::from(url.strip_prefix("file://").unwrap());
+        assert!(path.exists());
+
+        drop(page);
+        assert!(!path.exists());
+    }
+}
@@ -27,6 +27,55 @@ pub(crate) fn image_export_js_script() -> String {
             format: format,
             width: width,
             height: height,
+            scale: scale,
+        });
+    }).then(function(dataUrl) {
+        callback(dataUrl);
+    }).catch(function(err) {
+        console.error('Plotly error:', err);
+        callback('ERROR:' + err.toString());
+    });
+"#
+    .to_string()
+}
+
+pub(crate) fn animation_setup_js_script() -> String {
+    r#"
+    const plot = arguments[0];
+    const callback = arguments[arguments.length - 1];
+    const graph_div = document.getElementById("plotly-html-element");
+    Plotly.newPlot(graph_div, plot).then(function() {
+        callback('OK');
+    }).catch(function(err) {
+        console.error('Plotly error:', err);
+        callback('ERROR:' + err.toString());
+    });
+"#
+    .to_string()
+}
+
+pub(crate) fn animation_frame_js_script() -> String {
+    r#"
+    const frame = arguments[0];
+    const format = arguments[1];
+    const width = arguments[2];
+    const height = arguments[3];
+    const scale = arguments[4];
+    const callback = arguments[arguments.length - 1];
+    const graph_div = document.getElementById("plotly-html-element");
+    // Jump to the frame without transition, named frames are looked up so
+    // that their baseframe is applied as well
+    const target = frame.name !== undefined ? [frame.name] : [frame];
+    Plotly.animate(graph_div, target, {
+        mode: 'immediate',
+        frame: { duration: 0, redraw: true },
+        transition: { duration: 0 },
+    }).then(function() {
+        return Plotly.toImage(graph_div, {
+            format: format,
+            width: width,
+            height: height,
+            scale: scale,
         });
     }).then(function(dataUrl) {
         callback(dataUrl);
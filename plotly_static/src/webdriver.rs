@@ -26,7 +26,10 @@ pub(crate) const WEBDRIVER_PORT: u32 = 4444;
 /// Default WebDriver URL
 pub(crate) const WEBDRIVER_URL: &str = "http://127.0.0.1";
 
-#[cfg(all(feature = "chromedriver", not(target_os = "windows")))]
+#[cfg(all(
+    any(feature = "chromedriver", feature = "cdp"),
+    not(target_os = "windows")
+))]
 pub(crate) fn chrome_default_caps() -> Vec<&'static str> {
     vec![
         "--headless",
@@ -57,7 +60,7 @@ pub(crate) fn chrome_default_caps() -> Vec<&'static str> {
     ]
 }
 
-#[cfg(all(feature = "chromedriver", target_os = "windows"))]
+#[cfg(all(any(feature = "chromedriver", feature = "cdp"), target_os = "windows"))]
 pub(crate) fn chrome_default_caps() -> Vec<&'static str> {
     vec![
         "--headless=new",
@@ -0,0 +1,161 @@
+//! A [Yew](https://yew.rs) component rendering a [`plotly::Plot`].
+//!
+//! The [`Plotly`] component draws its `plot` prop into a `<div>` with
+//! `Plotly.react`, redraws it whenever the prop changes and calls
+//! `Plotly.purge` when it is unmounted. The `on_*` props receive the typed
+//! plotly.js event payloads of [`plotly::events`].
+//!
+//! The crate is empty unless compiled for a WebAssembly target.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use plotly::{Plot, Scatter};
+//! use plotly_yew::Plotly;
+//! use yew::prelude::*;
+//!
+//! #[function_component(App)]
+//! fn app() -> Html {
+//!     let mut plot = Plot::new();
+//!     plot.add_trace(Scatter::new(vec![0, 1, 2], vec![2, 1, 0]));
+//!     let on_click = Callback::from(|event: plotly::events::ClickEvent| {
+//!         log::info!("{:?}", event.points);
+//!     });
+//!
+//!     html! { <Plotly id="plot-div" {plot} {on_click} /> }
+//! }
+//! ```
+#![cfg(target_arch = "wasm32")]
+
+use std::{cell::RefCell, rc::Rc};
+
+use plotly::{
+    callbacks::{self, EventListener},
+    events::{ClickEvent, PointsEvent, RelayoutEvent, RestyleEvent, SelectionEvent},
+    Plot,
+};
+use yew::{platform::spawn_local, prelude::*};
+
+/// The properties of the [`Plotly`] component.
+#[derive(Properties, PartialEq)]
+pub struct PlotlyProps {
+    /// The `id` of the `<div>` the plot is drawn into, unique on the page.
+    pub id: AttrValue,
+    /// The plot to draw. The plot is redrawn with `Plotly.react` whenever it
+    /// changes.
+    pub plot: Plot,
+    #[prop_or_default]
+    pub class: Classes,
+    /// Called with `plotly_click` events.
+    #[prop_or_default]
+    pub on_click: Option<Callback<ClickEvent>>,
+    /// Called with `plotly_hover` events.
+    #[prop_or_default]
+    pub on_hover: Option<Callback<PointsEvent>>,
+    /// Called with `plotly_unhover` events.
+    #[prop_or_default]
+    pub on_unhover: Option<Callback<PointsEvent>>,
+    /// Called with `plotly_selected` events.
+    #[prop_or_default]
+    pub on_selected: Option<Callback<SelectionEvent>>,
+    /// Called with `plotly_deselect` events.
+    #[prop_or_default]
+    pub on_deselect: Option<Callback<()>>,
+    /// Called with `plotly_relayout` events.
+    #[prop_or_default]
+    pub on_relayout: Option<Callback<RelayoutEvent>>,
+    /// Called with `plotly_restyle` events.
+    #[prop_or_default]
+    pub on_restyle: Option<Callback<RestyleEvent>>,
+}
+
+/// The event callbacks of the latest render, read by the plotly.js listeners.
+#[derive(Default)]
+struct Handlers {
+    on_click: Option<Callback<ClickEvent>>,
+    on_hover: Option<Callback<PointsEvent>>,
+    on_unhover: Option<Callback<PointsEvent>>,
+    on_selected: Option<Callback<SelectionEvent>>,
+    on_deselect: Option<Callback<()>>,
+    on_relayout: Option<Callback<RelayoutEvent>>,
+    on_restyle: Option<Callback<RestyleEvent>>,
+}
+
+impl From<&PlotlyProps> for Handlers {
+    fn from(props: &PlotlyProps) -> Self {
+        Self {
+            on_click: props.on_click.clone(),
+            on_hover: props.on_hover.clone(),
+            on_unhover: props.on_unhover.clone(),
+            on_selected: props.on_selected.clone(),
+            on_deselect: props.on_deselect.clone(),
+            on_relayout: props.on_relayout.clone(),
+            on_restyle: props.on_restyle.clone(),
+        }
+    }
+}
+
+/// A `<div>` showing a [`Plot`], see the [crate] documentation.
+#[function_component(Plotly)]
+pub fn plotly_component(props: &PlotlyProps) -> Html {
+    let handlers = use_mut_ref(Handlers::default);
+    *handlers.borrow_mut() = Handlers::from(props);
+    let listeners = use_mut_ref(Vec::<EventListener>::new);
+
+    {
+        let id = props.id.clone();
+        let listeners = listeners.clone();
+        use_effect_with(props.plot.clone(), move |plot| {
+            let plot = plot.clone();
+            spawn_local(async move {
+                if plotly::bindings::react(&id, &plot).await.is_err() {
+                    return;
+                }
+                // plotly.js only accepts listeners once the plot is drawn.
+                if listeners.borrow().is_empty() {
+                    *listeners.borrow_mut() = bind_listeners(&id, &handlers);
+                }
+            });
+        });
+    }
+    {
+        let id = props.id.clone();
+        use_effect_with((), move |_| {
+            move || {
+                listeners.borrow_mut().clear();
+                let _ = plotly::bindings::purge(&id);
+            }
+        });
+    }
+
+    html! { <div id={props.id.clone()} class={props.class.clone()}></div> }
+}
+
+/// Register listeners for all supported events, forwarding them to the
+/// current callbacks in `handlers`.
+fn bind_listeners(id: &str, handlers: &Rc<RefCell<Handlers>>) -> Vec<EventListener> {
+    let mut on_deselect = emit(handlers, |h| &h.on_deselect);
+    vec![
+        callbacks::bind_click(id, emit(handlers, |h| &h.on_click)),
+        callbacks::bind_hover(id, emit(handlers, |h| &h.on_hover)),
+        callbacks::bind_unhover(id, emit(handlers, |h| &h.on_unhover)),
+        callbacks::bind_selected(id, emit(handlers, |h| &h.on_selected)),
+        callbacks::bind_deselect(id, move || on_deselect(())),
+        callbacks::bind_relayout(id, emit(handlers, |h| &h.on_relayout)),
+        callbacks::bind_restyle(id, emit(handlers, |h| &h.on_restyle)),
+    ]
+}
+
+/// A closure emitting events to the callback selected by `callback`, if set.
+fn emit<E: 'static>(
+    handlers: &Rc<RefCell<Handlers>>,
+    callback: fn(&Handlers) -> &Option<Callback<E>>,
+) -> impl FnMut(E) + 'static {
+    let handlers = handlers.clone();
+    move |event| {
+        let callback = callback(&handlers.borrow()).clone();
+        if let Some(callback) = callback {
+            callback.emit(event);
+        }
+    }
+}